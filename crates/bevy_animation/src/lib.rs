@@ -43,7 +43,7 @@ use bevy_ecs::{prelude::*, world::EntityMutExcept};
 use bevy_math::FloatOrd;
 use bevy_platform::{collections::HashMap, hash::NoOpHash};
 use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
-use bevy_time::Time;
+use bevy_time::LocalTime;
 use bevy_transform::TransformSystems;
 use bevy_utils::{PreHashMap, PreHashMapExt, TypeIdMap};
 use serde::{Deserialize, Serialize};
@@ -975,19 +975,21 @@ fn trigger_untargeted_animation_events(
 }
 
 /// A system that advances the time for all playing animations.
+///
+/// Animations are advanced by the [`LocalTime`] of their player, scaled by its [`TimeDomain`](bevy_time::TimeDomain)s.
 pub fn advance_animations(
-    time: Res<Time>,
+    time: LocalTime,
     animation_clips: Res<Assets<AnimationClip>>,
     animation_graphs: Res<Assets<AnimationGraph>>,
-    mut players: Query<(&mut AnimationPlayer, &AnimationGraphHandle)>,
+    mut players: Query<(Entity, &mut AnimationPlayer, &AnimationGraphHandle)>,
 ) {
-    let delta_seconds = time.delta_secs();
     players
         .par_iter_mut()
-        .for_each(|(mut player, graph_handle)| {
+        .for_each(|(entity, mut player, graph_handle)| {
             let Some(animation_graph) = animation_graphs.get(graph_handle) else {
                 return;
             };
+            let delta_seconds = time.delta_secs(entity);
 
             // Tick animations, and schedule them.
 
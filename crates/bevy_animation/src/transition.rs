@@ -3,13 +3,9 @@
 //! Please note that this is an unstable temporary API. It may be replaced by a
 //! state machine in the future.
 
-use bevy_ecs::{
-    component::Component,
-    reflect::ReflectComponent,
-    system::{Query, Res},
-};
+use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent, system::Query};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_time::Time;
+use bevy_time::LocalTime;
 use core::time::Duration;
 
 use crate::{graph::AnimationNodeIndex, ActiveAnimation, AnimationPlayer};
@@ -109,22 +105,23 @@ impl AnimationTransitions {
 /// A system that alters the weight of currently-playing transitions based on
 /// the current time and decline amount.
 pub fn advance_transitions(
-    mut query: Query<(&mut AnimationTransitions, &mut AnimationPlayer)>,
-    time: Res<Time>,
+    mut query: Query<(Entity, &mut AnimationTransitions, &mut AnimationPlayer)>,
+    time: LocalTime,
 ) {
     // We use a "greedy layer" system here. The top layer (most recent
     // transition) gets as much as weight as it wants, and the remaining amount
     // is divided between all the other layers, eventually culminating in the
     // currently-playing animation receiving whatever's left. This results in a
     // nicely normalized weight.
-    for (mut animation_transitions, mut player) in query.iter_mut() {
+    for (entity, mut animation_transitions, mut player) in query.iter_mut() {
+        let delta_secs = time.delta_secs(entity);
         let mut remaining_weight = 1.0;
 
         for transition in &mut animation_transitions.transitions.iter_mut().rev() {
             // Decrease weight.
             transition.current_weight = (transition.current_weight
-                - transition.weight_decline_per_sec * time.delta_secs())
-            .max(0.0);
+                - transition.weight_decline_per_sec * delta_secs)
+                .max(0.0);
 
             // Update weight.
             let Some(ref mut animation) = player.animation_mut(transition.animation) else {
@@ -9,7 +9,8 @@ use alloc::{
 };
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
-    component::RequiredComponentsError,
+    change_detection::{record_component_history, ComponentHistory},
+    component::{compute_component, ComputedComponent, RequiredComponentsError},
     error::{DefaultErrorHandler, ErrorHandler},
     event::Event,
     intern::Interned,
@@ -413,6 +414,32 @@ impl App {
         self
     }
 
+    /// Initializes persistent [`Message`] handling for `M` by inserting a
+    /// [`PersistentMessages<M>`](bevy_ecs::message::PersistentMessages) resource,
+    /// where messages are kept until every reader acknowledged them.
+    ///
+    /// The oldest messages are dropped once more than `max_len` messages are stored, or
+    /// [`PersistentMessages::DEFAULT_MAX_LEN`](bevy_ecs::message::PersistentMessages::DEFAULT_MAX_LEN)
+    /// if it is `None`.
+    /// This does nothing if the resource already exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Message)]
+    /// # struct MyMessage;
+    /// # let mut app = App::new();
+    /// #
+    /// app.add_persistent_message::<MyMessage>(Some(1024));
+    /// ```
+    pub fn add_persistent_message<M: Message>(&mut self, max_len: Option<usize>) -> &mut Self {
+        self.main_mut().add_persistent_message::<M>(max_len);
+        self
+    }
+
     /// Inserts the [`Resource`] into the app, overwriting any existing resource of the same type.
     ///
     /// There is also an [`init_resource`](Self::init_resource) for resources that have
@@ -1086,6 +1113,93 @@ impl App {
             .try_register_required_components_with::<T, R>(constructor)
     }
 
+    /// Keeps a [`ComponentHistory`] of the previous values of `T` on every entity with `T`,
+    /// readable with the [`History`](bevy_ecs::change_detection::History) query data.
+    ///
+    /// This doesn't change how `T` is stored: the history is a separate [`ComponentHistory<T>`] component,
+    /// registered as a [required component] of `T` built with `constructor`, and the
+    /// [`record_component_history`] system records the changed values in [`Last`](crate::Last),
+    /// so several changes made during the same frame are recorded as a single entry.
+    ///
+    /// Like other requirements, this must be called before `T` is inserted into the world for the first time.
+    ///
+    /// [required component]: Component#required-components
+    ///
+    /// # Panics
+    ///
+    /// Panics if the history is already tracked for `T`, or if `T` has ever been added
+    /// on an entity before the registration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::change_detection::{ComponentHistory, History};
+    /// #[derive(Component, Clone)]
+    /// struct Health(u32);
+    ///
+    /// fn print_health_changes(query: Query<History<Health>>) {
+    ///     for health in &query {
+    ///         let values: Vec<_> = health.history().iter().map(|(_, health)| health.0).collect();
+    ///         println!("last values: {values:?}");
+    ///     }
+    /// }
+    ///
+    /// App::new()
+    ///     .track_component_history::<Health>(|| ComponentHistory::new(60))
+    ///     .add_systems(Update, print_health_changes);
+    /// ```
+    pub fn track_component_history<T: Component>(
+        &mut self,
+        constructor: fn() -> ComponentHistory<T>,
+    ) -> &mut Self {
+        self.register_required_components_with::<T, ComponentHistory<T>>(constructor)
+            .add_systems(crate::Last, record_component_history::<T>)
+    }
+
+    /// Sets up the [`ComputedComponent`] `C`, which is recomputed in [`PostUpdate`](crate::PostUpdate)
+    /// on every entity on which one of its sources changed, and removed along with its sources.
+    ///
+    /// The computing systems are part of the [`ComputedComponentSystems`](bevy_ecs::component::ComputedComponentSystems) set, and the system computing `C`
+    /// is in the [`ComputeComponent<C>`](bevy_ecs::component::ComputeComponent) set.
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::component::ComputedComponent;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Dead;
+    ///
+    /// impl ComputedComponent for Dead {
+    ///     type Sources = Health;
+    ///
+    ///     fn compute(health: &Health) -> Option<Self> {
+    ///         (health.0 == 0).then_some(Dead)
+    ///     }
+    /// }
+    ///
+    /// App::new().add_computed_component::<Dead>();
+    /// ```
+    pub fn add_computed_component<C: ComputedComponent>(&mut self) -> &mut Self {
+        let compute_set = compute_component::<C>.into_system_set();
+        let registered = self
+            .get_schedule(crate::PostUpdate)
+            .is_some_and(|schedule| schedule.graph().system_sets.contains(compute_set));
+        if !registered {
+            C::register_computed_component_observers(self.world_mut());
+            self.edit_schedule(crate::PostUpdate, C::register_computed_component_systems);
+        }
+        self
+    }
+
     /// Registers a component type as "disabling",
     /// using [default query filters](bevy_ecs::entity_disabling::DefaultQueryFilters) to exclude entities with the component from queries.
     ///
@@ -1540,8 +1654,8 @@ mod tests {
     use std::sync::Mutex;
 
     use bevy_ecs::{
-        change_detection::{DetectChanges, ResMut},
-        component::Component,
+        change_detection::{ComponentHistory, DetectChanges, ResMut},
+        component::{Component, ComputedComponent},
         entity::Entity,
         lifecycle::RemovedComponents,
         message::{Message, MessageWriter, Messages},
@@ -1983,6 +2097,66 @@ mod tests {
 
         App::new().add_plugins(Foo);
     }
+
+    #[test]
+    fn component_history_is_recorded_once_per_update() {
+        #[derive(Component, Clone)]
+        struct Counter(u32);
+
+        let mut app = App::new();
+        app.track_component_history::<Counter>(|| ComponentHistory::new(4))
+            .add_systems(Update, |mut query: Query<&mut Counter>| {
+                for mut counter in &mut query {
+                    counter.0 += 1;
+                    counter.0 += 1;
+                }
+            });
+        let entity = app.world_mut().spawn(Counter(0)).id();
+        app.update();
+        app.update();
+
+        let history = app
+            .world()
+            .get::<ComponentHistory<Counter>>(entity)
+            .unwrap();
+        assert!(history.iter().map(|(_, counter)| counter.0).eq([4, 2]));
+    }
+
+    #[test]
+    fn computed_components_are_computed_in_post_update() {
+        #[derive(Component)]
+        struct Health(u32);
+
+        #[derive(Component)]
+        struct Dead;
+
+        impl ComputedComponent for Dead {
+            type Sources = Health;
+
+            fn compute(health: &Health) -> Option<Self> {
+                (health.0 == 0).then_some(Dead)
+            }
+        }
+
+        let mut app = App::new();
+        app.add_computed_component::<Dead>()
+            .add_computed_component::<Dead>()
+            .add_systems(Update, |mut query: Query<&mut Health>| {
+                for mut health in &mut query {
+                    health.0 = health.0.saturating_sub(1);
+                }
+            });
+        let entity = app.world_mut().spawn(Health(2)).id();
+        app.update();
+        assert!(app.world().get::<Dead>(entity).is_none());
+        app.update();
+        assert!(app.world().get::<Dead>(entity).is_some());
+
+        app.world_mut().entity_mut(entity).remove::<Health>();
+        app.world_mut().flush();
+        assert!(app.world().get::<Dead>(entity).is_none());
+    }
+
     #[test]
     fn events_should_be_updated_once_per_update() {
         #[derive(Message, Clone)]
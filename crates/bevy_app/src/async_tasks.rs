@@ -0,0 +1,629 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt,
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    error::HandleError,
+    event::{EntityEvent, Event},
+    message::{Message, MessageCursor, Messages},
+    observer::{Observer, On},
+    query::QueryState,
+    resource::Resource,
+    system::{Command, Commands, EntityCommands, Local},
+    world::World,
+};
+use bevy_platform::{
+    cell::SyncCell,
+    sync::{Arc, Mutex},
+};
+use bevy_tasks::{AsyncComputeTaskPool, Task};
+
+/// Adds support for async tasks with access to the [`World`].
+///
+/// Async tasks are spawned with [`AsyncTasksExt`], and run on the [`AsyncComputeTaskPool`].
+/// They access the world through an [`AsyncWorld`], whose requests are applied in [`PreUpdate`]
+/// by [`run_async_tasks`].
+///
+/// This plugin is part of `DefaultPlugins` and `MinimalPlugins`. Spawning a task without it panics.
+#[derive(Default)]
+pub struct AsyncTasksPlugin;
+
+impl Plugin for AsyncTasksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AsyncWorld>()
+            .add_systems(PreUpdate, run_async_tasks);
+    }
+}
+
+/// A [`Task`] spawned with [`AsyncTasksExt`], running an async flow with access to the [`World`].
+///
+/// Each task lives on its own entity, which serves as its handle: despawning the entity cancels the task.
+/// The entity is despawned once the task completes.
+/// Tasks spawned for an owning entity are linked to it with [`AsyncTaskOf`], and are cancelled when
+/// their owner is despawned.
+#[derive(Component)]
+pub struct AsyncTask(Task<()>);
+
+impl AsyncTask {
+    /// Returns `true` if the task has completed.
+    pub fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+}
+
+impl fmt::Debug for AsyncTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncTask")
+            .field("is_finished", &self.is_finished())
+            .finish()
+    }
+}
+
+/// Links an [`AsyncTask`] to the entity owning it.
+///
+/// Despawning the owner despawns its tasks, cancelling them.
+#[derive(Component, Debug, PartialEq, Eq)]
+#[relationship(relationship_target = AsyncTasks)]
+pub struct AsyncTaskOf(pub Entity);
+
+/// The [`AsyncTask`]s owned by an entity. See [`AsyncTaskOf`].
+#[derive(Component, Debug, Default, PartialEq, Eq)]
+#[relationship_target(relationship = AsyncTaskOf, linked_spawn)]
+pub struct AsyncTasks(Vec<Entity>);
+
+type WorldRequest = Box<dyn FnOnce(&mut World) + Send>;
+
+#[derive(Default)]
+struct AsyncWorldState {
+    /// The number of times [`run_async_tasks`] ran.
+    frame: u64,
+    /// Wakers of the tasks waiting for the next frame.
+    frame_wakers: Vec<Waker>,
+    /// Closures waiting for access to the world.
+    requests: Vec<WorldRequest>,
+}
+
+/// A handle giving async tasks access to the [`World`].
+///
+/// Requests made through this handle are applied the next time [`run_async_tasks`] runs,
+/// which [`AsyncTasksPlugin`] schedules once per frame in [`PreUpdate`].
+///
+/// ```
+/// # use bevy_app::AsyncTasksExt;
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Resource)]
+/// struct Score(u32);
+///
+/// fn start_cutscene(mut commands: Commands) {
+///     commands.spawn_async_task(|world| async move {
+///         for _ in 0..3 {
+///             world.next_frame().await;
+///         }
+///         let score = world.with(|world| world.resource::<Score>().0).await;
+///         world.queue(move |world: &mut World| {
+///             world.insert_resource(Score(score + 10));
+///         });
+///     });
+/// }
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct AsyncWorld {
+    state: Arc<Mutex<AsyncWorldState>>,
+}
+
+impl AsyncWorld {
+    /// Runs `f` with access to the [`World`], returning a future resolving to its result.
+    ///
+    /// `f` is queued right away, and runs the next time [`run_async_tasks`] runs,
+    /// even if the returned future is never awaited.
+    pub fn with<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut World) -> R + Send + 'static,
+    ) -> AsyncWorldFuture<R> {
+        let (sender, future) = AsyncWorldFuture::channel();
+        self.push(Box::new(move |world| sender.send(f(world))));
+        future
+    }
+
+    /// Queues a [`Command`], applied the next time [`run_async_tasks`] runs.
+    pub fn queue<C: Command<T> + HandleError<T> + Send, T>(&self, command: C) {
+        self.push(Box::new(move |world| world.commands().queue(command)));
+    }
+
+    /// Returns a future resolving the next time [`run_async_tasks`] runs.
+    pub fn next_frame(&self) -> NextFrame {
+        let frame = self.state.lock().unwrap().frame;
+        NextFrame {
+            state: self.state.clone(),
+            frame: frame + 1,
+        }
+    }
+
+    /// Waits for the next message of type `M`, and returns a clone of it.
+    ///
+    /// Like [`with`](Self::with), the request is queued right away: only the messages written after
+    /// it first runs are returned. From then on, the [`Messages<M>`] are read every time
+    /// [`run_async_tasks`] runs until a message is found, whether or not the task is polled in between,
+    /// so no message is missed as long as [`Messages<M>`] is updated at most once per frame.
+    /// Messages written between two calls to this method are not returned by the second one.
+    pub fn next_message<M: Message + Clone>(&self) -> AsyncWorldFuture<M> {
+        let (sender, future) = AsyncWorldFuture::channel();
+        let async_world = self.clone();
+        self.push(Box::new(move |world| {
+            let cursor = world
+                .get_resource::<Messages<M>>()
+                .map(Messages::get_cursor_current)
+                .unwrap_or_default();
+            read_next_message(async_world, world, cursor, sender);
+        }));
+        future
+    }
+
+    /// Waits for the next trigger of the [`Event`] `E`, and returns a clone of it.
+    ///
+    /// This watches the event with a temporary [`Observer`], despawned once the event was received
+    /// or when the returned future is dropped.
+    pub async fn next_event<E: Event + Clone>(&self) -> E {
+        self.observe(|observer| observer).await
+    }
+
+    /// Waits for the next trigger of the [`EntityEvent`] `E` targeting `entity`, and returns a clone of it.
+    ///
+    /// This watches the event with a temporary [`Observer`], despawned once the event was received
+    /// or when the returned future is dropped.
+    pub async fn next_entity_event<E: EntityEvent + Clone>(&self, entity: Entity) -> E {
+        self.observe(move |observer| observer.with_entity(entity))
+            .await
+    }
+
+    async fn observe<E: Event + Clone>(
+        &self,
+        configure: impl FnOnce(Observer) -> Observer + Send + 'static,
+    ) -> E {
+        let (sender, future) = AsyncWorldFuture::channel();
+        let mut sender = Some(sender);
+        let observer = Observer::new(move |event: On<E>, mut commands: Commands| {
+            if let Some(sender) = sender.take() {
+                sender.send(event.event().clone());
+            }
+            commands.entity(event.observer()).try_despawn();
+        });
+        // Create the guard before the observer is spawned, so that it is despawned even if
+        // this future is dropped before the spawn request is applied.
+        let guard = DespawnOnDrop {
+            world: self.clone(),
+            entity: Arc::new(Mutex::new(None)),
+        };
+        let entity = guard.entity.clone();
+        self.push(Box::new(move |world| {
+            *entity.lock().unwrap() = Some(world.spawn(configure(observer)).id());
+        }));
+        future.await
+    }
+
+    fn push(&self, request: WorldRequest) {
+        self.state.lock().unwrap().requests.push(request);
+    }
+}
+
+impl fmt::Debug for AsyncWorld {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("AsyncWorld")
+            .field("frame", &state.frame)
+            .field("pending_requests", &state.requests.len())
+            .finish()
+    }
+}
+
+/// Sends the next message read by `cursor`, or reads again the next time [`run_async_tasks`] runs.
+fn read_next_message<M: Message + Clone>(
+    async_world: AsyncWorld,
+    world: &mut World,
+    mut cursor: MessageCursor<M>,
+    sender: Sender<M>,
+) {
+    // Stop reading once the future was dropped.
+    if sender.is_closed() {
+        return;
+    }
+    let message = world
+        .get_resource::<Messages<M>>()
+        .and_then(|messages| cursor.read(messages).next().cloned());
+    match message {
+        Some(message) => sender.send(message),
+        None => async_world.clone().push(Box::new(move |world| {
+            read_next_message(async_world, world, cursor, sender);
+        })),
+    }
+}
+
+/// Despawns an entity through an [`AsyncWorld`] when dropped.
+///
+/// The entity is set by a request queued before the guard is dropped, and requests are applied in order,
+/// so it is always set by the time the despawn request is applied.
+struct DespawnOnDrop {
+    world: AsyncWorld,
+    entity: Arc<Mutex<Option<Entity>>>,
+}
+
+impl Drop for DespawnOnDrop {
+    fn drop(&mut self) {
+        let entity = self.entity.clone();
+        self.world.push(Box::new(move |world| {
+            // The entity may already be despawned.
+            if let Some(entity) = entity.lock().unwrap().take() {
+                let _ = world.try_despawn(entity);
+            }
+        }));
+    }
+}
+
+struct Slot<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+struct Sender<T>(Arc<Mutex<Slot<T>>>);
+
+impl<T> Sender<T> {
+    /// Returns `true` if the receiving [`AsyncWorldFuture`] was dropped.
+    fn is_closed(&self) -> bool {
+        Arc::strong_count(&self.0) == 1
+    }
+
+    fn send(self, value: T) {
+        let waker = {
+            let mut slot = self.0.lock().unwrap();
+            slot.value = Some(value);
+            slot.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// A future resolving to the result of a closure run by [`AsyncWorld::with`].
+pub struct AsyncWorldFuture<T>(Arc<Mutex<Slot<T>>>);
+
+impl<T> AsyncWorldFuture<T> {
+    fn channel() -> (Sender<T>, Self) {
+        let slot = Arc::new(Mutex::new(Slot {
+            value: None,
+            waker: None,
+        }));
+        (Sender(slot.clone()), Self(slot))
+    }
+}
+
+impl<T> Future for AsyncWorldFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut slot = self.0.lock().unwrap();
+        match slot.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for AsyncWorldFuture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncWorldFuture").finish_non_exhaustive()
+    }
+}
+
+/// A future resolving the next time [`run_async_tasks`] runs. See [`AsyncWorld::next_frame`].
+pub struct NextFrame {
+    state: Arc<Mutex<AsyncWorldState>>,
+    frame: u64,
+}
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.frame >= self.frame {
+            Poll::Ready(())
+        } else {
+            state.frame_wakers.push(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl fmt::Debug for NextFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NextFrame")
+            .field("frame", &self.frame)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Makes a [`Send`] future [`Sync`], as required by the single-threaded task pool used without `std`.
+struct SyncFuture<F>(SyncCell<Pin<Box<F>>>);
+
+impl<F: Future> Future for SyncFuture<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        self.0.get().as_mut().poll(cx)
+    }
+}
+
+/// Extension trait for [`Commands`] and [`EntityCommands`] to spawn [`AsyncTask`]s.
+pub trait AsyncTasksExt {
+    /// Spawns the future returned by `task` on the [`AsyncComputeTaskPool`], giving it access to the
+    /// [`World`] through an [`AsyncWorld`].
+    ///
+    /// Returns the commands of the entity holding the [`AsyncTask`]: despawn it to cancel the task.
+    ///
+    /// # Panics
+    ///
+    /// When the command is applied, panics if [`AsyncTasksPlugin`] was not added to the app.
+    /// When called on [`EntityCommands`], the task is owned by that entity, and cancelled when it is despawned.
+    fn spawn_async_task<F: Future<Output = ()> + Send + 'static>(
+        &mut self,
+        task: impl FnOnce(AsyncWorld) -> F + Send + 'static,
+    ) -> EntityCommands<'_>;
+}
+
+impl AsyncTasksExt for Commands<'_, '_> {
+    fn spawn_async_task<F: Future<Output = ()> + Send + 'static>(
+        &mut self,
+        task: impl FnOnce(AsyncWorld) -> F + Send + 'static,
+    ) -> EntityCommands<'_> {
+        let mut entity = self.spawn_empty();
+        let id = entity.id();
+        entity.commands_mut().queue(move |world: &mut World| {
+            // Without the plugin, requests are never applied, and the task would wait forever.
+            let Some(async_world) = world.get_resource::<AsyncWorld>().cloned() else {
+                panic!(
+                    "Async tasks require `AsyncTasksPlugin`, which is part of `DefaultPlugins` and `MinimalPlugins`. \
+                    Consider adding it to the app."
+                );
+            };
+            let task = AsyncComputeTaskPool::get()
+                .spawn(SyncFuture(SyncCell::new(Box::pin(task(async_world)))));
+            if let Ok(mut entity) = world.get_entity_mut(id) {
+                entity.insert(AsyncTask(task));
+            }
+        });
+        entity
+    }
+}
+
+impl AsyncTasksExt for EntityCommands<'_> {
+    fn spawn_async_task<F: Future<Output = ()> + Send + 'static>(
+        &mut self,
+        task: impl FnOnce(AsyncWorld) -> F + Send + 'static,
+    ) -> EntityCommands<'_> {
+        let owner = self.id();
+        let mut entity = self.commands_mut().spawn_async_task(task);
+        entity.insert(AsyncTaskOf(owner));
+        entity
+    }
+}
+
+/// Applies the requests made through the [`AsyncWorld`], wakes the tasks waiting for the next frame,
+/// and despawns the entities of the completed [`AsyncTask`]s.
+pub fn run_async_tasks(
+    world: &mut World,
+    query: &mut QueryState<(Entity, &AsyncTask)>,
+    mut finished: Local<Vec<Entity>>,
+) {
+    let async_world = world.get_resource_or_init::<AsyncWorld>().clone();
+    let (frame_wakers, requests) = {
+        let mut state = async_world.state.lock().unwrap();
+        state.frame += 1;
+        (
+            mem::take(&mut state.frame_wakers),
+            mem::take(&mut state.requests),
+        )
+    };
+    frame_wakers.into_iter().for_each(Waker::wake);
+    for request in requests {
+        request(world);
+    }
+    world.flush();
+
+    finished.extend(
+        query
+            .iter(world)
+            .filter(|(_, task)| task.is_finished())
+            .map(|(entity, _)| entity),
+    );
+    for entity in finished.drain(..) {
+        world.despawn(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TaskPoolPlugin;
+    use bevy_ecs::{message::MessageWriter, resource::Resource};
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<u32>);
+
+    #[derive(Message, Clone)]
+    struct Ping(u32);
+
+    #[derive(EntityEvent, Clone)]
+    struct Poke(Entity);
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins((TaskPoolPlugin::default(), AsyncTasksPlugin))
+            .init_resource::<Log>()
+            .add_message::<Ping>();
+        app
+    }
+
+    /// Updates `app` until `done` returns `true`, for a bounded number of updates
+    /// during which the task pool threads make progress.
+    fn update_until(app: &mut App, mut done: impl FnMut(&mut World) -> bool) {
+        for _ in 0..100_000 {
+            app.update();
+            if done(app.world_mut()) {
+                return;
+            }
+        }
+        panic!("condition was not met");
+    }
+
+    fn log(world: &mut World) -> &[u32] {
+        &world.resource::<Log>().0
+    }
+
+    #[test]
+    fn with_and_next_frame() {
+        let mut app = setup();
+        let task = app
+            .world_mut()
+            .commands()
+            .spawn_async_task(|world| async move {
+                let value = world
+                    .with(|world| world.resource::<Log>().0.len() as u32)
+                    .await;
+                world.next_frame().await;
+                world.queue(move |world: &mut World| world.resource_mut::<Log>().0.push(value + 1));
+            })
+            .id();
+
+        update_until(&mut app, |world| log(world) == [1]);
+        update_until(&mut app, |world| world.get_entity(task).is_err());
+    }
+
+    #[test]
+    fn next_message() {
+        let mut app = setup();
+        app.world_mut()
+            .commands()
+            .spawn_async_task(|world| async move {
+                let Ping(value) = world.next_message::<Ping>().await;
+                world.queue(move |world: &mut World| world.resource_mut::<Log>().0.push(value));
+            });
+        // The task only sees the messages written once it started waiting.
+        app.add_systems(crate::Update, |mut writer: MessageWriter<Ping>| {
+            writer.write(Ping(7));
+        });
+        update_until(&mut app, |world| log(world) == [7]);
+    }
+
+    #[test]
+    fn next_message_reads_while_the_task_is_not_polled() {
+        let mut app = setup();
+        app.world_mut()
+            .commands()
+            .spawn_async_task(|world| async move {
+                let message = world.next_message::<Ping>();
+                world
+                    .with(|world| {
+                        world.write_message(Ping(3));
+                    })
+                    .await;
+                // The message buffers are swapped several times before the message future is polled.
+                for _ in 0..4 {
+                    world.next_frame().await;
+                }
+                let Ping(value) = message.await;
+                world.queue(move |world: &mut World| world.resource_mut::<Log>().0.push(value));
+            });
+        update_until(&mut app, |world| log(world) == [3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "AsyncTasksPlugin")]
+    fn spawning_without_plugin_panics() {
+        let mut app = App::new();
+        app.add_plugins(TaskPoolPlugin::default());
+        app.world_mut().commands().spawn_async_task(|_| async {});
+        app.update();
+    }
+
+    #[test]
+    fn next_entity_event() {
+        let mut app = setup();
+        let target = app.world_mut().spawn_empty().id();
+        app.world_mut()
+            .commands()
+            .spawn_async_task(move |world| async move {
+                let Poke(entity) = world.next_entity_event::<Poke>(target).await;
+                world.queue(move |world: &mut World| {
+                    world.resource_mut::<Log>().0.push(entity.index_u32());
+                });
+            });
+        update_until(&mut app, |world| {
+            world.query::<&Observer>().iter(world).next().is_some()
+        });
+
+        app.world_mut().trigger(Poke(target));
+        update_until(&mut app, |world| log(world) == [target.index_u32()]);
+        assert_eq!(
+            app.world_mut()
+                .query::<&Observer>()
+                .iter(app.world())
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn dropping_next_event_before_the_observer_spawns_despawns_it() {
+        let mut app = setup();
+        let world = app.world().resource::<AsyncWorld>().clone();
+        let mut future = Box::pin(world.next_event::<Poke>());
+        let mut context = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut context).is_pending());
+        drop(future);
+
+        app.update();
+        assert_eq!(
+            app.world_mut()
+                .query::<&Observer>()
+                .iter(app.world())
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn despawning_owner_cancels_task() {
+        let mut app = setup();
+        let owner = app.world_mut().spawn_empty().id();
+        let task = app
+            .world_mut()
+            .commands()
+            .entity(owner)
+            .spawn_async_task(|world| async move {
+                loop {
+                    world.next_frame().await;
+                }
+            })
+            .id();
+        app.update();
+        assert_eq!(
+            app.world().get::<AsyncTaskOf>(task),
+            Some(&AsyncTaskOf(owner))
+        );
+
+        app.world_mut().despawn(owner);
+        assert!(app.world().get_entity(task).is_err());
+    }
+}
@@ -24,6 +24,7 @@ extern crate alloc;
 extern crate self as bevy_app;
 
 mod app;
+mod async_tasks;
 mod hierarchy;
 mod main_schedule;
 mod panic_handler;
@@ -40,6 +41,7 @@ mod terminal_ctrl_c_handler;
 pub mod hotpatch;
 
 pub use app::*;
+pub use async_tasks::*;
 pub use hierarchy::*;
 pub use main_schedule::*;
 pub use panic_handler::*;
@@ -1,7 +1,7 @@
 use crate::{App, AppLabel, InternedAppLabel, Plugin, Plugins, PluginsState};
 use alloc::{boxed::Box, string::String, vec::Vec};
 use bevy_ecs::{
-    message::MessageRegistry,
+    message::{MessageRegistry, PersistentMessages},
     prelude::*,
     schedule::{
         InternedScheduleLabel, InternedSystemSet, ScheduleBuildSettings, ScheduleCleanupPolicy,
@@ -362,6 +362,19 @@ impl SubApp {
         self
     }
 
+    /// See [`App::add_persistent_message`].
+    pub fn add_persistent_message<M: Message>(&mut self, max_len: Option<usize>) -> &mut Self {
+        if !self.world.contains_resource::<PersistentMessages<M>>() {
+            let mut messages = PersistentMessages::<M>::default();
+            if let Some(max_len) = max_len {
+                messages.set_max_len(Some(max_len));
+            }
+            self.world.insert_resource(messages);
+        }
+
+        self
+    }
+
     /// See [`App::add_plugins`].
     pub fn add_plugins<M>(&mut self, plugins: impl Plugins<M>) -> &mut Self {
         self.run_as_app(|app| plugins.add_to_app(app));
@@ -5,11 +5,12 @@ use crate::{
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_math::Vec3;
+use bevy_time::LocalTime;
 use bevy_transform::prelude::GlobalTransform;
 use rodio::{OutputStream, OutputStreamHandle, Sink, Source, SpatialSink};
 use tracing::warn;
 
-use crate::{AudioSink, AudioSinkPlayback};
+use crate::{sinks::TimePausedSink, AudioSink, AudioSinkPlayback};
 
 /// Used internally to play audio on the current "audio device"
 ///
@@ -342,6 +343,7 @@ pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
                 AudioSink,
                 PlaybackSettings,
                 PlaybackRemoveMarker,
+                AppliedTimeDomain,
             )>();
         }
     }
@@ -352,11 +354,78 @@ pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
                 SpatialAudioSink,
                 PlaybackSettings,
                 PlaybackRemoveMarker,
+                AppliedTimeDomain,
             )>();
         }
     }
 }
 
+/// The speed of the [`TimeDomain`](bevy_time::TimeDomain)s last applied to an audio sink.
+#[derive(Component)]
+pub(crate) struct AppliedTimeDomain {
+    relative_speed: f32,
+    base_speed: f32,
+}
+
+impl Default for AppliedTimeDomain {
+    fn default() -> Self {
+        Self {
+            relative_speed: 1.0,
+            base_speed: 1.0,
+        }
+    }
+}
+
+impl AppliedTimeDomain {
+    fn apply(&mut self, sink: &impl TimePausedSink, relative_speed: f32) {
+        if self.relative_speed != 0.0 {
+            // Keep any change made to the speed of the sink since the last time.
+            self.base_speed = sink.speed() / self.relative_speed;
+        }
+        if relative_speed != 0.0 {
+            sink.set_speed(self.base_speed * relative_speed);
+        }
+        // Sinks paused with `AudioSinkPlayback::pause` stay paused when their domains are unpaused.
+        if (relative_speed == 0.0) != (self.relative_speed == 0.0) {
+            sink.set_time_paused(relative_speed == 0.0);
+        }
+        self.relative_speed = relative_speed;
+    }
+}
+
+/// Scales the speed, and so the pitch, of audio sinks by the [`TimeDomain`](bevy_time::TimeDomain)s
+/// of their entity, pausing them while any of their domains is paused.
+pub(crate) fn apply_time_domains(
+    mut commands: Commands,
+    time: LocalTime,
+    mut sinks: Query<(
+        Entity,
+        AnyOf<(&AudioSink, &SpatialAudioSink)>,
+        Option<&mut AppliedTimeDomain>,
+    )>,
+) {
+    for (entity, sinks, applied) in &mut sinks {
+        let relative_speed = time.relative_speed(entity);
+        let mut inserted = None;
+        let applied = match applied {
+            Some(applied) if applied.relative_speed == relative_speed => continue,
+            Some(applied) => applied.into_inner(),
+            None if relative_speed == 1.0 => continue,
+            None => inserted.insert(AppliedTimeDomain::default()),
+        };
+
+        match sinks {
+            (Some(sink), _) => applied.apply(sink, relative_speed),
+            (_, Some(sink)) => applied.apply(sink, relative_speed),
+            (None, None) => {}
+        }
+
+        if let Some(inserted) = inserted {
+            commands.entity(entity).insert(inserted);
+        }
+    }
+}
+
 /// Run Condition to only play audio if the audio output is available
 pub(crate) fn audio_output_available(audio_output: Res<AudioOutput>) -> bool {
     audio_output.stream_handle.is_some()
@@ -58,6 +58,7 @@ pub use sinks::*;
 use bevy_app::prelude::*;
 use bevy_asset::{Asset, AssetApp};
 use bevy_ecs::prelude::*;
+use bevy_time::Time;
 use bevy_transform::TransformSystems;
 
 use audio_output::*;
@@ -90,7 +91,12 @@ impl Plugin for AudioPlugin {
             )
             .add_systems(
                 PostUpdate,
-                (update_emitter_positions, update_listener_positions).in_set(AudioPlaybackSystems),
+                (
+                    update_emitter_positions,
+                    update_listener_positions,
+                    apply_time_domains.run_if(resource_exists::<Time>),
+                )
+                    .in_set(AudioPlaybackSystems),
             )
             .init_resource::<AudioOutput>();
 
@@ -2,7 +2,10 @@ use crate::Volume;
 use bevy_ecs::component::Component;
 use bevy_math::Vec3;
 use bevy_transform::prelude::Transform;
-use core::time::Duration;
+use core::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 pub use rodio::source::SeekError;
 use rodio::{Sink, SpatialSink};
 
@@ -40,6 +43,8 @@ pub trait AudioSinkPlayback {
     /// Resumes playback of a paused sink.
     ///
     /// No effect if not paused.
+    /// While a [`TimeDomain`](bevy_time::TimeDomain) of the sink's entity is paused,
+    /// the sink only resumes once the domain is unpaused.
     fn play(&self);
 
     /// Returns the position of the sound that's being played.
@@ -91,6 +96,8 @@ pub trait AudioSinkPlayback {
     /// Returns true if the sink is paused.
     ///
     /// Sinks can be paused and resumed using [`pause`](Self::pause) and [`play`](Self::play).
+    /// This does not account for the pause of the [`TimeDomain`](bevy_time::TimeDomain)s
+    /// of the sink's entity, which keep the sink silent without changing this.
     fn is_paused(&self) -> bool;
 
     /// Stops the sink.
@@ -151,12 +158,20 @@ pub struct AudioSink {
     /// user's intended volume setting, even if the underlying sink's volume is
     /// 0.
     pub(crate) managed_volume: Option<Volume>,
+
+    /// Whether the sink was paused with [`pause`](Self::pause).
+    paused: AtomicBool,
+
+    /// Whether the sink is paused by a paused [`TimeDomain`](bevy_time::TimeDomain) of its entity.
+    time_paused: AtomicBool,
 }
 
 impl AudioSink {
     /// Create a new audio sink.
     pub fn new(sink: Sink) -> Self {
         Self {
+            paused: AtomicBool::new(sink.is_paused()),
+            time_paused: AtomicBool::new(false),
             sink,
             managed_volume: None,
         }
@@ -186,7 +201,10 @@ impl AudioSinkPlayback for AudioSink {
     }
 
     fn play(&self) {
-        self.sink.play();
+        self.paused.store(false, Ordering::Relaxed);
+        if !self.time_paused.load(Ordering::Relaxed) {
+            self.sink.play();
+        }
     }
 
     fn position(&self) -> Duration {
@@ -198,11 +216,12 @@ impl AudioSinkPlayback for AudioSink {
     }
 
     fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
         self.sink.pause();
     }
 
     fn is_paused(&self) -> bool {
-        self.sink.is_paused()
+        self.paused.load(Ordering::Relaxed)
     }
 
     fn stop(&self) {
@@ -255,12 +274,20 @@ pub struct SpatialAudioSink {
     /// user's intended volume setting, even if the underlying sink's volume is
     /// 0.
     pub(crate) managed_volume: Option<Volume>,
+
+    /// Whether the sink was paused with [`pause`](Self::pause).
+    paused: AtomicBool,
+
+    /// Whether the sink is paused by a paused [`TimeDomain`](bevy_time::TimeDomain) of its entity.
+    time_paused: AtomicBool,
 }
 
 impl SpatialAudioSink {
     /// Create a new spatial audio sink.
     pub fn new(sink: SpatialSink) -> Self {
         Self {
+            paused: AtomicBool::new(sink.is_paused()),
+            time_paused: AtomicBool::new(false),
             sink,
             managed_volume: None,
         }
@@ -290,7 +317,10 @@ impl AudioSinkPlayback for SpatialAudioSink {
     }
 
     fn play(&self) {
-        self.sink.play();
+        self.paused.store(false, Ordering::Relaxed);
+        if !self.time_paused.load(Ordering::Relaxed) {
+            self.sink.play();
+        }
     }
 
     fn position(&self) -> Duration {
@@ -302,11 +332,12 @@ impl AudioSinkPlayback for SpatialAudioSink {
     }
 
     fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
         self.sink.pause();
     }
 
     fn is_paused(&self) -> bool {
-        self.sink.is_paused()
+        self.paused.load(Ordering::Relaxed)
     }
 
     fn stop(&self) {
@@ -333,6 +364,36 @@ impl AudioSinkPlayback for SpatialAudioSink {
     }
 }
 
+/// Pauses an audio sink while a [`TimeDomain`](bevy_time::TimeDomain) of its entity is paused,
+/// without overriding the pause requested with [`AudioSinkPlayback::pause`].
+pub(crate) trait TimePausedSink: AudioSinkPlayback {
+    /// Pauses or resumes the sink for its time domains, only resuming it if it was not paused with
+    /// [`AudioSinkPlayback::pause`].
+    fn set_time_paused(&self, time_paused: bool);
+}
+
+impl TimePausedSink for AudioSink {
+    fn set_time_paused(&self, time_paused: bool) {
+        self.time_paused.store(time_paused, Ordering::Relaxed);
+        if time_paused || self.paused.load(Ordering::Relaxed) {
+            self.sink.pause();
+        } else {
+            self.sink.play();
+        }
+    }
+}
+
+impl TimePausedSink for SpatialAudioSink {
+    fn set_time_paused(&self, time_paused: bool) {
+        self.time_paused.store(time_paused, Ordering::Relaxed);
+        if time_paused || self.paused.load(Ordering::Relaxed) {
+            self.sink.pause();
+        } else {
+            self.sink.play();
+        }
+    }
+}
+
 impl SpatialAudioSink {
     /// Set the two ears position.
     pub fn set_ears_position(&self, left_position: Vec3, right_position: Vec3) {
@@ -416,4 +477,25 @@ mod tests {
         let audio_sink = AudioSink::new(sink);
         test_audio_sink_playback(audio_sink);
     }
+
+    #[test]
+    fn time_pause_keeps_the_requested_pause() {
+        let (sink, _queue_rx) = Sink::new_idle();
+        let audio_sink = AudioSink::new(sink);
+
+        // Paused while its time domain is paused: stays paused once the domain is unpaused.
+        audio_sink.set_time_paused(true);
+        audio_sink.pause();
+        audio_sink.set_time_paused(false);
+        assert!(audio_sink.is_paused());
+        assert!(audio_sink.sink.is_paused());
+
+        // Resumed while its time domain is paused: only plays once the domain is unpaused.
+        audio_sink.set_time_paused(true);
+        audio_sink.play();
+        assert!(!audio_sink.is_paused());
+        assert!(audio_sink.sink.is_paused());
+        audio_sink.set_time_paused(false);
+        assert!(!audio_sink.sink.is_paused());
+    }
 }
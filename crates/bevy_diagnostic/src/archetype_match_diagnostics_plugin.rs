@@ -0,0 +1,144 @@
+use alloc::format;
+use core::{any::type_name, marker::PhantomData, time::Duration};
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    query::{QueryData, QueryFilter, QueryIterationStrategy},
+};
+
+use crate::{
+    Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic, DEFAULT_MAX_HISTORY_LENGTH,
+};
+
+/// Adds diagnostics about how a query with the data `D` and the filter `F` matches the archetypes and tables
+/// of the world.
+///
+/// This is a probe: the diagnostics are measured on a [`QueryState`] owned by this plugin, not on the queries
+/// of the systems of the app. Since every `Query<D, F>` matches the same archetypes and tables, the matched
+/// counts and the iteration strategy apply to all of them, but the time spent updating the archetype cache
+/// is the time spent by the probe itself, which is a good estimate of the cost paid by each such query.
+///
+/// The diagnostics are named `archetype_match/<query type>/<measurement>`, see [`Self::path`] for the
+/// measurements.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct ArchetypeMatchDiagnosticsPlugin<D: QueryData + 'static, F: QueryFilter + 'static = ()> {
+    /// The total number of values to keep.
+    pub max_history_length: usize,
+    _marker: PhantomData<fn() -> (D, F)>,
+}
+
+impl<D: QueryData + 'static, F: QueryFilter + 'static> Default
+    for ArchetypeMatchDiagnosticsPlugin<D, F>
+{
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_HISTORY_LENGTH)
+    }
+}
+
+impl<D: QueryData + 'static, F: QueryFilter + 'static> ArchetypeMatchDiagnosticsPlugin<D, F> {
+    /// Creates a new `ArchetypeMatchDiagnosticsPlugin` with the specified `max_history_length`.
+    pub fn new(max_history_length: usize) -> Self {
+        Self {
+            max_history_length,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Number of archetypes matched by the probe.
+    pub const MATCHED_ARCHETYPES: &'static str = "matched_archetypes";
+
+    /// Number of tables matched by the probe.
+    pub const MATCHED_TABLES: &'static str = "matched_tables";
+
+    /// `1` if the probe iterates over tables, `0` if it iterates over archetypes.
+    pub const DENSE: &'static str = "dense";
+
+    /// Time spent by the probe processing new archetypes since the previous measurement, in ms.
+    pub const UPDATE_ARCHETYPES_TIME: &'static str = "update_archetypes_time";
+
+    /// Returns the path of the given measurement of the probe, such as [`Self::MATCHED_ARCHETYPES`].
+    pub fn path(measurement: &str) -> DiagnosticPath {
+        DiagnosticPath::new(format!(
+            "archetype_match/{}/{measurement}",
+            type_name::<QueryState<D, F>>()
+        ))
+    }
+
+    /// Updates the measurements of the probe, using its own `Query<D, F>`.
+    pub fn diagnostic_system(
+        query: Query<D, F>,
+        mut diagnostics: Diagnostics,
+        mut last_update_time: Local<Duration>,
+    ) {
+        let stats = query.stats();
+        diagnostics.add_measurement(&Self::path(Self::MATCHED_ARCHETYPES), || {
+            stats.matched_archetypes as f64
+        });
+        diagnostics.add_measurement(&Self::path(Self::MATCHED_TABLES), || {
+            stats.matched_tables as f64
+        });
+        diagnostics.add_measurement(&Self::path(Self::DENSE), || match stats.strategy {
+            QueryIterationStrategy::Dense => 1.0,
+            QueryIterationStrategy::Archetypal => 0.0,
+        });
+        let update_time = stats.update_archetypes_time - *last_update_time;
+        *last_update_time = stats.update_archetypes_time;
+        diagnostics.add_measurement(&Self::path(Self::UPDATE_ARCHETYPES_TIME), || {
+            update_time.as_secs_f64() * 1000.0
+        });
+    }
+}
+
+impl<D: QueryData + 'static, F: QueryFilter + 'static> Plugin
+    for ArchetypeMatchDiagnosticsPlugin<D, F>
+{
+    fn build(&self, app: &mut App) {
+        for measurement in [Self::MATCHED_ARCHETYPES, Self::MATCHED_TABLES, Self::DENSE] {
+            app.register_diagnostic(
+                Diagnostic::new(Self::path(measurement))
+                    .with_max_history_length(self.max_history_length),
+            );
+        }
+        app.register_diagnostic(
+            Diagnostic::new(Self::path(Self::UPDATE_ARCHETYPES_TIME))
+                .with_suffix("ms")
+                .with_max_history_length(self.max_history_length),
+        )
+        .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArchetypeMatchDiagnosticsPlugin;
+    use crate::DiagnosticsStore;
+    use bevy_app::App;
+    use bevy_ecs::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    #[test]
+    fn archetype_matches_are_measured() {
+        type Plugin = ArchetypeMatchDiagnosticsPlugin<&'static A>;
+
+        let mut app = App::new();
+        app.add_plugins(Plugin::default());
+        app.world_mut().spawn(A);
+        app.world_mut().spawn((A, B));
+        app.update();
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        let value = |measurement| store.get(&Plugin::path(measurement)).unwrap().value();
+        assert_eq!(value(Plugin::MATCHED_ARCHETYPES), Some(2.0));
+        assert_eq!(value(Plugin::MATCHED_TABLES), Some(2.0));
+        assert_eq!(value(Plugin::DENSE), Some(1.0));
+    }
+}
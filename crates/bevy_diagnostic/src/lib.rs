@@ -15,6 +15,7 @@ extern crate std;
 
 extern crate alloc;
 
+mod archetype_match_diagnostics_plugin;
 mod diagnostic;
 mod entity_count_diagnostics_plugin;
 mod frame_count;
@@ -22,7 +23,10 @@ mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
 #[cfg(feature = "sysinfo_plugin")]
 mod system_information_diagnostics_plugin;
+#[cfg(feature = "std")]
+mod system_time_diagnostics_plugin;
 
+pub use archetype_match_diagnostics_plugin::ArchetypeMatchDiagnosticsPlugin;
 pub use diagnostic::*;
 
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
@@ -31,6 +35,8 @@ pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::{LogDiagnosticsPlugin, LogDiagnosticsState};
 #[cfg(feature = "sysinfo_plugin")]
 pub use system_information_diagnostics_plugin::{SystemInfo, SystemInformationDiagnosticsPlugin};
+#[cfg(feature = "std")]
+pub use system_time_diagnostics_plugin::SystemTimeDiagnosticsPlugin;
 
 use bevy_app::prelude::*;
 
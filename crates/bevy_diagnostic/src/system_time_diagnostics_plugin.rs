@@ -0,0 +1,166 @@
+use alloc::format;
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, SystemKey, SystemTimings},
+};
+use bevy_platform::{collections::HashMap, time::Instant};
+
+use crate::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore, DEFAULT_MAX_HISTORY_LENGTH,
+};
+
+/// Adds a "system time" diagnostic for every system run by a schedule using the
+/// [`CriticalPath`](bevy_ecs::schedule::ExecutorKind::CriticalPath) executor.
+///
+/// Each diagnostic is named `system_time/<schedule>/<system name>` and measures the run time of the system
+/// in milliseconds, on the frames where the system ran. Systems with the same name in the same schedule,
+/// including every system when the `bevy_ecs/debug` feature is disabled, get a `#<n>` suffix.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct SystemTimeDiagnosticsPlugin {
+    /// The total number of values to keep for each system.
+    pub max_history_length: usize,
+}
+
+impl Default for SystemTimeDiagnosticsPlugin {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_HISTORY_LENGTH)
+    }
+}
+
+impl SystemTimeDiagnosticsPlugin {
+    /// Creates a new `SystemTimeDiagnosticsPlugin` with the specified `max_history_length`.
+    pub fn new(max_history_length: usize) -> Self {
+        Self { max_history_length }
+    }
+}
+
+impl Plugin for SystemTimeDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let max_history_length = self.max_history_length;
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<SystemTimings>()
+            .add_systems(
+                Last,
+                move |timings: Res<SystemTimings>,
+                      diagnostics: ResMut<DiagnosticsStore>,
+                      measured: Local<MeasuredSystems>| {
+                    Self::diagnostic_system(timings, diagnostics, measured, max_history_length);
+                },
+            );
+    }
+}
+
+impl SystemTimeDiagnosticsPlugin {
+    /// The prefix of the path of every system time diagnostic.
+    pub const SYSTEM_TIME: DiagnosticPath = DiagnosticPath::const_new("system_time");
+
+    /// Returns the path of the diagnostic measuring the system with the given name in the given schedule.
+    pub fn system_path(schedule: InternedScheduleLabel, name: &str) -> DiagnosticPath {
+        DiagnosticPath::from_components([
+            Self::SYSTEM_TIME.as_str(),
+            &format!("{schedule:?}"),
+            name,
+        ])
+    }
+
+    /// Adds the latest run time of every system in [`SystemTimings`] that ran since the last update
+    /// to its diagnostic, registering the diagnostics of systems that ran for the first time.
+    fn diagnostic_system(
+        timings: Res<SystemTimings>,
+        mut diagnostics: ResMut<DiagnosticsStore>,
+        mut measured: Local<MeasuredSystems>,
+        max_history_length: usize,
+    ) {
+        if !timings.is_changed() {
+            return;
+        }
+
+        let time = Instant::now();
+        for (schedule, system, timing) in timings.iter() {
+            let (path, runs) = measured.0.entry((schedule, system)).or_insert_with(|| {
+                let mut path = Self::system_path(schedule, &timing.name);
+                let mut n = 1;
+                while diagnostics.get(&path).is_some() {
+                    n += 1;
+                    path = Self::system_path(schedule, &format!("{}#{n}", timing.name));
+                }
+                diagnostics.add(
+                    Diagnostic::new(path.clone())
+                        .with_suffix("ms")
+                        .with_max_history_length(max_history_length),
+                );
+                (path, 0)
+            });
+
+            // Skip systems that didn't run since the last measurement.
+            if *runs == timing.runs {
+                continue;
+            }
+            *runs = timing.runs;
+
+            if let Some(diagnostic) = diagnostics.get_mut(path)
+                && diagnostic.is_enabled
+            {
+                diagnostic.add_measurement(DiagnosticMeasurement {
+                    time,
+                    value: timing.last.as_secs_f64() * 1000.0,
+                });
+            }
+        }
+    }
+}
+
+/// The diagnostic path of each measured system, and how many runs of it were measured.
+#[derive(Default)]
+struct MeasuredSystems(HashMap<(InternedScheduleLabel, SystemKey), (DiagnosticPath, u64)>);
+
+#[cfg(test)]
+mod tests {
+    use super::SystemTimeDiagnosticsPlugin;
+    use crate::{Diagnostic, DiagnosticsStore};
+    use alloc::vec::Vec;
+    use bevy_app::{App, Update};
+    use bevy_ecs::{
+        schedule::{ExecutorKind, IntoScheduleConfigs},
+        system::Local,
+    };
+
+    #[test]
+    fn system_times_are_measured() {
+        let mut app = App::new();
+        app.add_plugins(SystemTimeDiagnosticsPlugin::default())
+            .add_systems(Update, (|| {}, || {}))
+            .add_systems(
+                Update,
+                (|| {}).run_if(|mut runs: Local<u32>| {
+                    *runs += 1;
+                    *runs % 2 == 1
+                }),
+            )
+            .edit_schedule(Update, |schedule| {
+                schedule.set_executor_kind(ExecutorKind::CriticalPath);
+            });
+        app.update();
+        app.update();
+        app.update();
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        let mut history_lens: Vec<_> = store
+            .iter()
+            .inspect(|diagnostic| {
+                assert!(diagnostic
+                    .path()
+                    .as_str()
+                    .starts_with(SystemTimeDiagnosticsPlugin::SYSTEM_TIME.as_str()));
+            })
+            .map(Diagnostic::history_len)
+            .collect();
+        history_lens.sort();
+        // Every system has its own diagnostic, and the conditional system only ran twice.
+        assert_eq!(history_lens, [2, 3, 3]);
+    }
+}
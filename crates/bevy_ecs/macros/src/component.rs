@@ -56,6 +56,9 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         Err(e) => return e.into_compile_error().into(),
     };
 
+    let many_relationship = attrs.relationship.as_ref().is_some_and(|r| r.many);
+    let many_relationship_target = attrs.relationship_target.as_ref().is_some_and(|r| r.many);
+
     let relationship = match derive_relationship(&ast, &attrs, &bevy_ecs_path) {
         Ok(value) => value,
         Err(err) => err.into_compile_error().into(),
@@ -98,7 +101,8 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_insert))
+        let relationship_trait = relationship_trait_name(many_relationship);
+        Some(quote!(<Self as #bevy_ecs_path::relationship::#relationship_trait>::on_insert))
     } else {
         attrs
             .on_insert
@@ -115,7 +119,8 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_replace))
+        let relationship_trait = relationship_trait_name(many_relationship);
+        Some(quote!(<Self as #bevy_ecs_path::relationship::#relationship_trait>::on_replace))
     } else if attrs.relationship_target.is_some() {
         if attrs.on_replace.is_some() {
             return syn::Error::new(
@@ -126,7 +131,8 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_replace))
+        let target_trait = relationship_target_trait_name(many_relationship_target);
+        Some(quote!(<Self as #bevy_ecs_path::relationship::#target_trait>::on_replace))
     } else {
         attrs
             .on_replace
@@ -135,6 +141,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
 
     let on_despawn_path = if attrs
         .relationship_target
+        .as_ref()
         .is_some_and(|target| target.linked_spawn)
     {
         if attrs.on_despawn.is_some() {
@@ -146,7 +153,8 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_despawn))
+        let target_trait = relationship_target_trait_name(many_relationship_target);
+        Some(quote!(<Self as #bevy_ecs_path::relationship::#target_trait>::on_despawn))
     } else {
         attrs
             .on_despawn
@@ -199,7 +207,17 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         .then_some(quote! { #bevy_ecs_path::component::Immutable })
         .unwrap_or(quote! { #bevy_ecs_path::component::Mutable });
 
-    let clone_behavior = if relationship_target.is_some() || relationship.is_some() {
+    let is_many = many_relationship || many_relationship_target;
+
+    let clone_behavior = if many_relationship_target {
+        // Many-to-many targets are rebuilt by the hooks of their (cloned) sources.
+        quote!(#bevy_ecs_path::component::ComponentCloneBehavior::Ignore)
+    } else if is_many {
+        quote!(
+            use #bevy_ecs_path::component::{DefaultCloneBehaviorBase, DefaultCloneBehaviorViaClone};
+            (&&&#bevy_ecs_path::component::DefaultCloneBehaviorSpecialization::<Self>::default()).default_clone_behavior()
+        )
+    } else if relationship_target.is_some() || relationship.is_some() {
         quote!(
             use #bevy_ecs_path::relationship::{
                 RelationshipCloneBehaviorBase, RelationshipCloneBehaviorViaClone, RelationshipCloneBehaviorViaReflect,
@@ -217,6 +235,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     };
 
     let relationship_accessor = if (relationship.is_some() || relationship_target.is_some())
+        && !is_many
         && let Data::Struct(DataStruct {
             fields,
             struct_token,
@@ -530,11 +549,13 @@ struct Require {
 struct Relationship {
     relationship_target: Type,
     allow_self_referential: bool,
+    many: bool,
 }
 
 struct RelationshipTarget {
     relationship: Type,
     linked_spawn: bool,
+    many: bool,
 }
 
 // values for `storage` attribute
@@ -718,6 +739,24 @@ fn storage_path(bevy_ecs_path: &Path, ty: StorageTy) -> TokenStream2 {
     quote! { #bevy_ecs_path::component::StorageType::#storage_type }
 }
 
+fn relationship_trait_name(many: bool) -> Ident {
+    let name = if many {
+        "ManyRelationship"
+    } else {
+        "Relationship"
+    };
+    Ident::new(name, Span::call_site())
+}
+
+fn relationship_target_trait_name(many: bool) -> Ident {
+    let name = if many {
+        "ManyRelationshipTarget"
+    } else {
+        "RelationshipTarget"
+    };
+    Ident::new(name, Span::call_site())
+}
+
 fn hook_register_function_call(
     bevy_ecs_path: &Path,
     hook: TokenStream2,
@@ -737,18 +776,23 @@ mod kw {
     syn::custom_keyword!(relationship);
     syn::custom_keyword!(linked_spawn);
     syn::custom_keyword!(allow_self_referential);
+    syn::custom_keyword!(many);
 }
 
 impl Parse for Relationship {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         let mut relationship_target: Option<Type> = None;
         let mut allow_self_referential: bool = false;
+        let mut many: bool = false;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(kw::allow_self_referential) {
                 input.parse::<kw::allow_self_referential>()?;
                 allow_self_referential = true;
+            } else if lookahead.peek(kw::many) {
+                input.parse::<kw::many>()?;
+                many = true;
             } else if lookahead.peek(kw::relationship_target) {
                 input.parse::<kw::relationship_target>()?;
                 input.parse::<Token![=]>()?;
@@ -765,6 +809,7 @@ impl Parse for Relationship {
                 syn::Error::new(input.span(), "Missing `relationship_target = X` attribute")
             })?,
             allow_self_referential,
+            many,
         })
     }
 }
@@ -773,12 +818,16 @@ impl Parse for RelationshipTarget {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         let mut relationship: Option<Type> = None;
         let mut linked_spawn: bool = false;
+        let mut many: bool = false;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(kw::linked_spawn) {
                 input.parse::<kw::linked_spawn>()?;
                 linked_spawn = true;
+            } else if lookahead.peek(kw::many) {
+                input.parse::<kw::many>()?;
+                many = true;
             } else if lookahead.peek(kw::relationship) {
                 input.parse::<kw::relationship>()?;
                 input.parse::<Token![=]>()?;
@@ -795,6 +844,7 @@ impl Parse for RelationshipTarget {
                 syn::Error::new(input.span(), "Missing `relationship = X` attribute")
             })?,
             linked_spawn,
+            many,
         })
     }
 }
@@ -831,6 +881,35 @@ fn derive_relationship(
     let relationship_target = &relationship.relationship_target;
     let allow_self_referential = relationship.allow_self_referential;
 
+    if relationship.many {
+        let collection = &field.ty;
+        return Ok(Some(quote! {
+            impl #impl_generics #bevy_ecs_path::relationship::ManyRelationship for #struct_name #type_generics #where_clause {
+                type RelationshipTarget = #relationship_target;
+                const ALLOW_SELF_REFERENTIAL: bool = #allow_self_referential;
+                type Collection = #collection;
+
+                #[inline]
+                fn collection(&self) -> &Self::Collection {
+                    &self.#relationship_member
+                }
+
+                #[inline]
+                fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+                    &mut self.#relationship_member
+                }
+
+                #[inline]
+                fn from_collection(collection: Self::Collection) -> Self {
+                    Self {
+                        #(#members: core::default::Default::default(),)*
+                        #relationship_member: collection
+                    }
+                }
+            }
+        }));
+    }
+
     Ok(Some(quote! {
         impl #impl_generics #bevy_ecs_path::relationship::Relationship for #struct_name #type_generics #where_clause {
             type RelationshipTarget = #relationship_target;
@@ -893,8 +972,13 @@ fn derive_relationship_target(
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
     let linked_spawn = relationship_target.linked_spawn;
+    let target_trait = if relationship_target.many {
+        quote!(ManyRelationshipTarget)
+    } else {
+        quote!(RelationshipTarget)
+    };
     Ok(Some(quote! {
-        impl #impl_generics #bevy_ecs_path::relationship::RelationshipTarget for #struct_name #type_generics #where_clause {
+        impl #impl_generics #bevy_ecs_path::relationship::#target_trait for #struct_name #type_generics #where_clause {
             const LINKED_SPAWN: bool = #linked_spawn;
             type Relationship = #relationship;
             type Collection = #collection;
@@ -644,6 +644,17 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
 /// When `allow_self_referential` is enabled, be careful when using recursive traversal methods
 /// like `iter_ancestors` or `root_ancestor`, as they will loop infinitely if an entity points to itself.
 ///
+/// Many-to-many relationships, where the source also stores a collection of targets:
+/// ```ignore
+/// #[derive(Component)]
+/// #[relationship(relationship_target = Members, many)]
+/// pub struct MemberOf(pub Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = MemberOf, many)]
+/// pub struct Members(Vec<Entity>);
+/// ```
+///
 /// ## Hooks
 /// ```ignore
 /// #[derive(Component)]
@@ -0,0 +1,319 @@
+use alloc::collections::VecDeque;
+use core::fmt::Debug;
+
+use crate::{
+    change_detection::{DetectChanges, Ref, Tick},
+    component::Component,
+    query::{Changed, QueryData},
+    system::Query,
+};
+
+/// A bounded ring buffer of the previous values of the component `T` on an entity, along with the [`Tick`]
+/// at which each value was set.
+///
+/// Component history is opt-in, and doesn't change how `T` itself is stored: it is a separate component
+/// living next to `T`, usually added as a [required component] of `T` so that every entity with `T` keeps
+/// its own history, and filled by the [`record_component_history`] system which captures the value of
+/// every `T` that changed since it last ran.
+/// Several changes between two runs of that system are recorded as a single entry.
+///
+/// Values are copied either with [`Clone`] ([`ComponentHistory::new`]), or through reflection
+/// ([`ComponentHistory::new_reflect`]) for components which don't implement it.
+///
+/// Use the [`History`] query data to read the current value of a component alongside its history.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::change_detection::{record_component_history, ComponentHistory, History};
+/// #[derive(Component, Clone, PartialEq, Debug)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.register_required_components_with::<Health, ComponentHistory<Health>>(|| {
+///     ComponentHistory::new(8)
+/// });
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(record_component_history::<Health>);
+///
+/// let entity = world.spawn(Health(10)).id();
+/// schedule.run(&mut world);
+/// world.get_mut::<Health>(entity).unwrap().0 = 7;
+/// schedule.run(&mut world);
+///
+/// let mut query = world.query::<History<Health>>();
+/// let health = query.get(&world, entity).unwrap();
+/// assert_eq!(health.current(), &Health(7));
+/// assert_eq!(health.previous(1), Some(&Health(10)));
+/// ```
+///
+/// Ticks stored in the history are not clamped by [`World::check_change_ticks`](crate::world::World::check_change_ticks),
+/// so comparisons involving entries older than [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE) may be wrong.
+///
+/// [required component]: Component#required-components
+#[derive(Component)]
+pub struct ComponentHistory<T: Component> {
+    entries: VecDeque<(Tick, T)>,
+    capacity: usize,
+    clone: fn(&T) -> T,
+}
+
+impl<T: Component + Clone> ComponentHistory<T> {
+    /// Creates an empty history which keeps up to `capacity` values, copied with [`Clone`].
+    pub fn new(capacity: usize) -> Self {
+        Self::with_clone_fn(capacity, T::clone)
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl<T: Component + bevy_reflect::FromReflect> ComponentHistory<T> {
+    /// Creates an empty history which keeps up to `capacity` values, copied through reflection.
+    pub fn new_reflect(capacity: usize) -> Self {
+        Self::with_clone_fn(capacity, |value| {
+            T::from_reflect(value).expect("a reflected value should convert back to its own type")
+        })
+    }
+}
+
+impl<T: Component> ComponentHistory<T> {
+    /// Creates an empty history which keeps up to `capacity` values, copied with `clone`.
+    pub fn with_clone_fn(capacity: usize, clone: fn(&T) -> T) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            clone,
+        }
+    }
+
+    /// Returns the maximum number of values kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of values currently kept.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no value has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the `n`th most recent recorded value and the tick at which it was set,
+    /// where `0` is the latest recorded value.
+    pub fn get(&self, n: usize) -> Option<(Tick, &T)> {
+        self.entries.get(n).map(|(tick, value)| (*tick, value))
+    }
+
+    /// Iterates over the recorded values and the tick at which each was set, from the most recent to the oldest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Tick, &T)> + ExactSizeIterator {
+        self.entries.iter().map(|(tick, value)| (*tick, value))
+    }
+
+    /// Iterates over the recorded values set after `last_run`, from the most recent to the oldest.
+    ///
+    /// `this_run` is the current tick of the world, used like in [`Tick::is_newer_than`].
+    pub fn changed_since(
+        &self,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> impl Iterator<Item = (Tick, &T)> {
+        self.iter()
+            .take_while(move |(tick, _)| tick.is_newer_than(last_run, this_run))
+    }
+
+    /// Records `value`, set at `tick`, evicting the oldest value if the history is full.
+    pub fn push(&mut self, tick: Tick, value: &T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_back();
+        }
+        self.entries.push_front((tick, (self.clone)(value)));
+    }
+
+    /// Removes every recorded value.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl<T: Component + Debug> Debug for ComponentHistory<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ComponentHistory")
+            .field("entries", &self.entries)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+/// Records the value of every `T` that changed since this system last ran into its [`ComponentHistory`].
+pub fn record_component_history<T: Component>(
+    mut query: Query<(Ref<T>, &mut ComponentHistory<T>), Changed<T>>,
+) {
+    for (value, mut history) in &mut query {
+        history.push(value.last_changed(), &value);
+    }
+}
+
+/// [`QueryData`] reading the current value of the component `T` alongside its [`ComponentHistory`].
+///
+/// Only matches entities which keep a history of `T`. See [`ComponentHistory`] for how to enable it.
+#[derive(QueryData)]
+pub struct History<T: Component> {
+    current: Ref<'static, T>,
+    history: &'static ComponentHistory<T>,
+}
+
+impl<'w, 's, T: Component> HistoryItem<'w, 's, T> {
+    /// Returns the current value of the component.
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// Returns the current value of the component, with its change ticks.
+    pub fn current_ref(&self) -> &Ref<'w, T> {
+        &self.current
+    }
+
+    /// Returns the recorded history of the component.
+    pub fn history(&self) -> &'w ComponentHistory<T> {
+        self.history
+    }
+
+    /// Returns the value the component had `n` recordings before its current value, so that `1` is the
+    /// value it had right before the current one.
+    ///
+    /// The current value is not part of the result: `previous(0)` always returns `None`, use
+    /// [`current`](Self::current) instead. A current value which hasn't been recorded yet is skipped,
+    /// so the result doesn't depend on whether [`record_component_history`] ran since the last change.
+    pub fn previous(&self, n: usize) -> Option<&'w T> {
+        if n == 0 {
+            return None;
+        }
+        let current_is_recorded = self
+            .history
+            .get(0)
+            .is_some_and(|(tick, _)| tick == self.current.last_changed());
+        let index = if current_is_recorded { n } else { n - 1 };
+        self.history.get(index).map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record_component_history, ComponentHistory, History};
+    use crate::{component::Component, schedule::Schedule, world::World};
+    use alloc::vec::Vec;
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Value(u32);
+
+    fn setup(constructor: fn() -> ComponentHistory<Value>) -> (World, Schedule) {
+        let mut world = World::new();
+        world.register_required_components_with::<Value, ComponentHistory<Value>>(constructor);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(record_component_history::<Value>);
+        (world, schedule)
+    }
+
+    #[test]
+    fn history_records_changes() {
+        let (mut world, mut schedule) = setup(|| ComponentHistory::new(8));
+        let entity = world.spawn(Value(0)).id();
+        let unchanged = world.spawn(Value(10)).id();
+        schedule.run(&mut world);
+        for i in 1..4 {
+            world.get_mut::<Value>(entity).unwrap().0 = i;
+            schedule.run(&mut world);
+        }
+        // Changes between two recordings are merged.
+        world.get_mut::<Value>(entity).unwrap().0 = 10;
+        world.get_mut::<Value>(entity).unwrap().0 = 20;
+        schedule.run(&mut world);
+
+        let mut query = world.query::<History<Value>>();
+        let item = query.get(&world, entity).unwrap();
+        let values: Vec<_> = item.history().iter().map(|(_, value)| value.0).collect();
+        assert_eq!(values, [20, 3, 2, 1, 0]);
+        assert_eq!(item.previous(1), Some(&Value(3)));
+        let unchanged = world.get::<ComponentHistory<Value>>(unchanged).unwrap();
+        assert_eq!(unchanged.len(), 1);
+
+        let (tick, _) = item.history().get(2).unwrap();
+        let recent: Vec<_> = item
+            .history()
+            .changed_since(tick, world.read_change_tick())
+            .map(|(_, value)| value.0)
+            .collect();
+        assert_eq!(recent, [20, 3]);
+    }
+
+    #[test]
+    fn previous_is_relative_to_the_current_value() {
+        let (mut world, mut schedule) = setup(|| ComponentHistory::new(8));
+        let entity = world.spawn(Value(0)).id();
+        schedule.run(&mut world);
+        world.get_mut::<Value>(entity).unwrap().0 = 1;
+        schedule.run(&mut world);
+
+        let mut query = world.query::<History<Value>>();
+        let item = query.get(&world, entity).unwrap();
+        assert_eq!(item.previous(0), None);
+        assert_eq!(item.previous(1), Some(&Value(0)));
+        assert_eq!(item.previous(2), None);
+
+        // The current value hasn't been recorded yet.
+        world.get_mut::<Value>(entity).unwrap().0 = 2;
+        let item = query.get(&world, entity).unwrap();
+        assert_eq!(item.previous(1), Some(&Value(1)));
+        assert_eq!(item.previous(2), Some(&Value(0)));
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let (mut world, mut schedule) = setup(|| ComponentHistory::new(2));
+        let entity = world.spawn(Value(0)).id();
+        for i in 1..5 {
+            schedule.run(&mut world);
+            world.get_mut::<Value>(entity).unwrap().0 = i;
+        }
+        schedule.run(&mut world);
+
+        let history = world.get::<ComponentHistory<Value>>(entity).unwrap();
+        let values: Vec<_> = history.iter().map(|(_, value)| value.0).collect();
+        assert_eq!(values, [4, 3]);
+    }
+
+    #[test]
+    fn entities_without_history_do_not_match() {
+        let mut world = World::new();
+        world.spawn(Value(0));
+        assert_eq!(world.query::<History<Value>>().iter(&world).count(), 0);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn history_through_reflection() {
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        struct Reflected(u32);
+
+        let mut world = World::new();
+        world.register_required_components_with::<Reflected, ComponentHistory<Reflected>>(|| {
+            ComponentHistory::new_reflect(4)
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(record_component_history::<Reflected>);
+
+        let entity = world.spawn(Reflected(1)).id();
+        schedule.run(&mut world);
+        world.get_mut::<Reflected>(entity).unwrap().0 = 2;
+        schedule.run(&mut world);
+
+        let history = world.get::<ComponentHistory<Reflected>>(entity).unwrap();
+        assert_eq!(history.get(1).map(|(_, value)| value), Some(&Reflected(1)));
+    }
+}
@@ -0,0 +1,324 @@
+use alloc::vec::Vec;
+use core::{
+    any::type_name,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+use variadics_please::all_tuples;
+
+use crate::{
+    component::Component,
+    entity::Entity,
+    lifecycle::Remove,
+    observer::On,
+    query::{Changed, Or, QueryFilter, QueryItem, ReadOnlyQueryData},
+    schedule::{InternedSystemSet, IntoScheduleConfigs, Schedule, SystemSet},
+    system::{Commands, Query},
+    world::World,
+};
+
+/// A component whose value is automatically computed from other components of the same entity.
+///
+/// A **computed component** is deterministically derived from a set of [`Sources`](ComputedComponent::Sources)
+/// components. Its [`compute`](ComputedComponent::compute) method is called for every entity on which
+/// one of the sources changed, and the result is inserted on the entity.
+/// If the result is [`None`], or if one of the sources is removed, the computed component is removed.
+///
+/// This is the per-entity counterpart of computed states in `bevy_state`.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::component::ComputedComponent;
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct MaxHealth(u32);
+///
+/// #[derive(Component, PartialEq, Debug)]
+/// struct Wounded;
+///
+/// impl ComputedComponent for Wounded {
+///     /// A single component, or a tuple of components, to compute `Wounded` from.
+///     type Sources = (Health, MaxHealth);
+///
+///     fn compute((health, max_health): (&Health, &MaxHealth)) -> Option<Self> {
+///         (health.0 < max_health.0 / 2).then_some(Wounded)
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// Wounded::register_computed_component_observers(&mut world);
+/// Wounded::register_computed_component_systems(&mut schedule);
+///
+/// let player = world.spawn((Health(100), MaxHealth(100))).id();
+/// schedule.run(&mut world);
+/// assert_eq!(world.get::<Wounded>(player), None);
+///
+/// world.get_mut::<Health>(player).unwrap().0 = 20;
+/// schedule.run(&mut world);
+/// assert_eq!(world.get::<Wounded>(player), Some(&Wounded));
+/// ```
+pub trait ComputedComponent: Component + Sized {
+    /// The components from which [`Self`] is derived.
+    ///
+    /// This can either be a single type that implements [`Component`], or a tuple of them.
+    type Sources: ComponentSources;
+
+    /// Computes the value of [`Self`] from its [`Sources`](ComputedComponent::Sources).
+    /// This function gets called for every entity on which one of the sources changed.
+    ///
+    /// If the result is [`None`], [`Self`] is removed from the entity.
+    fn compute(
+        sources: QueryItem<'_, '_, <Self::Sources as ComponentSources>::Data>,
+    ) -> Option<Self>;
+
+    /// Adds the system computing [`Self`] to `schedule`, in the [`ComputeComponent<Self>`] set.
+    ///
+    /// It is ordered after the [`ComputeComponent`] sets of its sources, so that computed components
+    /// derived from other computed components are up to date within a single run of the schedule.
+    /// It is called by `App::add_computed_component`, but can be called manually if `App` is not used.
+    fn register_computed_component_systems(schedule: &mut Schedule) {
+        let mut source_sets = Vec::new();
+        Self::Sources::source_sets(&mut source_sets);
+        schedule
+            .configure_sets(ComputeComponent::<Self>::default().in_set(ComputedComponentSystems));
+        for source_set in source_sets {
+            schedule.configure_sets(ComputeComponent::<Self>::default().after(source_set));
+        }
+        schedule.add_systems(compute_component::<Self>.in_set(ComputeComponent::<Self>::default()));
+    }
+
+    /// Adds the observers removing [`Self`] when one of its sources is removed.
+    /// It is called by `App::add_computed_component`, but can be called manually if `App` is not used.
+    fn register_computed_component_observers(world: &mut World) {
+        Self::Sources::observe_removals::<Self>(world);
+    }
+}
+
+/// The set of components from which a [`ComputedComponent`] is derived.
+///
+/// It is implemented for every type that implements [`Component`], and tuples of them.
+pub trait ComponentSources: 'static {
+    /// The [`ReadOnlyQueryData`] fetching the sources, passed to [`ComputedComponent::compute`].
+    type Data: ReadOnlyQueryData + 'static;
+
+    /// The [`QueryFilter`] matching entities on which one of the sources changed.
+    type Changed: QueryFilter + 'static;
+
+    /// Adds the [`ComputeComponent`] set of each source to `sets`.
+    fn source_sets(sets: &mut Vec<InternedSystemSet>);
+
+    /// Adds observers removing `C` when one of the sources is removed.
+    fn observe_removals<C: Component>(world: &mut World);
+}
+
+impl<S: Component> ComponentSources for S {
+    type Data = &'static S;
+    type Changed = Changed<S>;
+
+    fn source_sets(sets: &mut Vec<InternedSystemSet>) {
+        sets.push(ComputeComponent::<S>::default().intern());
+    }
+
+    fn observe_removals<C: Component>(world: &mut World) {
+        world.add_observer(remove_computed_component::<S, C>);
+    }
+}
+
+macro_rules! impl_component_sources {
+    ($(#[$meta:meta])* $($source: ident),*) => {
+        $(#[$meta])*
+        impl<$($source: ComponentSources),*> ComponentSources for ($($source,)*) {
+            type Data = ($($source::Data,)*);
+            type Changed = Or<($($source::Changed,)*)>;
+
+            fn source_sets(sets: &mut Vec<InternedSystemSet>) {
+                $($source::source_sets(sets);)*
+            }
+
+            fn observe_removals<C: Component>(world: &mut World) {
+                $($source::observe_removals::<C>(world);)*
+            }
+        }
+    };
+}
+
+all_tuples!(
+    #[doc(fake_variadic)]
+    impl_component_sources,
+    1,
+    15,
+    S
+);
+
+/// The [`SystemSet`] containing the systems computing every [`ComputedComponent`].
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ComputedComponentSystems;
+
+/// The [`SystemSet`] containing the system computing the [`ComputedComponent`] `C`.
+///
+/// It is ordered after the sets of the sources of `C`, and is part of [`ComputedComponentSystems`].
+pub struct ComputeComponent<C: Component>(PhantomData<fn() -> C>);
+
+impl<C: Component> Default for ComputeComponent<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: Component> Debug for ComputeComponent<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ComputeComponent")
+            .field(&type_name::<C>())
+            .finish()
+    }
+}
+
+impl<C: Component> Hash for ComputeComponent<C> {
+    fn hash<H: Hasher>(&self, _state: &mut H) {
+        // all sets of a given type are the same
+    }
+}
+
+impl<C: Component> Clone for ComputeComponent<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: Component> Copy for ComputeComponent<C> {}
+
+impl<C: Component> PartialEq for ComputeComponent<C> {
+    #[inline]
+    fn eq(&self, _other: &Self) -> bool {
+        // all sets of a given type are equal
+        true
+    }
+}
+
+impl<C: Component> Eq for ComputeComponent<C> {}
+
+impl<C: Component> SystemSet for ComputeComponent<C> {
+    fn dyn_clone(&self) -> alloc::boxed::Box<dyn SystemSet> {
+        alloc::boxed::Box::new(*self)
+    }
+}
+
+/// Computes `C` on every entity on which one of its sources changed since this system last ran.
+pub fn compute_component<C: ComputedComponent>(
+    query: Query<
+        (Entity, <C::Sources as ComponentSources>::Data),
+        <C::Sources as ComponentSources>::Changed,
+    >,
+    mut commands: Commands,
+) {
+    for (entity, sources) in &query {
+        match C::compute(sources) {
+            Some(value) => commands.entity(entity).insert(value),
+            None => commands.entity(entity).remove::<C>(),
+        };
+    }
+}
+
+fn remove_computed_component<S: Component, C: Component>(
+    remove: On<Remove, S>,
+    mut commands: Commands,
+) {
+    commands.entity(remove.entity).try_remove::<C>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ComputedComponent;
+    use crate::{
+        change_detection::DetectChanges, component::Component, schedule::Schedule, world::World,
+    };
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct MaxHealth(u32);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Ratio(u32);
+
+    impl ComputedComponent for Ratio {
+        type Sources = (Health, MaxHealth);
+
+        fn compute((health, max_health): (&Health, &MaxHealth)) -> Option<Self> {
+            (max_health.0 > 0).then(|| Ratio(health.0 * 100 / max_health.0))
+        }
+    }
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Low;
+
+    impl ComputedComponent for Low {
+        type Sources = Ratio;
+
+        fn compute(ratio: &Ratio) -> Option<Self> {
+            (ratio.0 < 50).then_some(Low)
+        }
+    }
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        // Registered in reverse order, to check that the sets order them.
+        Low::register_computed_component_observers(&mut world);
+        Low::register_computed_component_systems(&mut schedule);
+        Ratio::register_computed_component_observers(&mut world);
+        Ratio::register_computed_component_systems(&mut schedule);
+        (world, schedule)
+    }
+
+    #[test]
+    fn computed_components_follow_their_sources() {
+        let (mut world, mut schedule) = setup();
+        let entity = world.spawn((Health(10), MaxHealth(100))).id();
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Ratio>(entity), Some(&Ratio(10)));
+        assert_eq!(world.get::<Low>(entity), Some(&Low));
+
+        world.get_mut::<Health>(entity).unwrap().0 = 80;
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Ratio>(entity), Some(&Ratio(80)));
+        assert_eq!(world.get::<Low>(entity), None);
+
+        world.get_mut::<MaxHealth>(entity).unwrap().0 = 0;
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Ratio>(entity), None);
+    }
+
+    #[test]
+    fn computed_components_are_only_recomputed_on_change() {
+        let (mut world, mut schedule) = setup();
+        let entity = world.spawn((Health(10), MaxHealth(100))).id();
+        schedule.run(&mut world);
+        let tick = world
+            .entity(entity)
+            .get_ref::<Ratio>()
+            .unwrap()
+            .last_changed();
+
+        schedule.run(&mut world);
+        let ratio = world.entity(entity).get_ref::<Ratio>().unwrap();
+        assert_eq!(ratio.last_changed(), tick);
+    }
+
+    #[test]
+    fn computed_components_are_removed_with_their_sources() {
+        let (mut world, mut schedule) = setup();
+        let entity = world.spawn((Health(10), MaxHealth(100))).id();
+        schedule.run(&mut world);
+
+        world.entity_mut(entity).remove::<Health>();
+        world.flush();
+        assert_eq!(world.get::<Ratio>(entity), None);
+        assert_eq!(world.get::<Low>(entity), None);
+    }
+}
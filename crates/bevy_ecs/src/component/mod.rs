@@ -1,11 +1,13 @@
 //! Types for declaring and storing [`Component`]s.
 
 mod clone;
+mod computed;
 mod info;
 mod register;
 mod required;
 
 pub use clone::*;
+pub use computed::*;
 pub use info::*;
 pub use register::*;
 pub use required::*;
@@ -11,7 +11,7 @@ use crate::{
     entity::{hash_map::EntityHashMap, Entity, EntityAllocator, EntityMapper},
     query::DebugCheckedUnwrap,
     relationship::RelationshipHookMode,
-    world::World,
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
 use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
 use bevy_platform::collections::{hash_map::Entry, HashMap, HashSet};
@@ -378,9 +378,9 @@ pub struct EntityCloner {
 }
 
 /// An expandable scratch space for defining a dynamic bundle.
-struct BundleScratch<'a> {
-    component_ids: Vec<ComponentId>,
-    component_ptrs: Vec<PtrMut<'a>>,
+pub(crate) struct BundleScratch<'a> {
+    pub(crate) component_ids: Vec<ComponentId>,
+    pub(crate) component_ptrs: Vec<PtrMut<'a>>,
 }
 
 impl<'a> BundleScratch<'a> {
@@ -513,6 +513,69 @@ impl EntityCloner {
         Self::clone_entity_mapped_internal(&mut self.state, &mut self.filter, world, source, mapper)
     }
 
+    /// Clones a single `component` value pointed to by `source` into `bundle_scratch`, using the clone handler
+    /// this cloner would use for it. Entities referenced by the value are kept as they are.
+    ///
+    /// Returns `false` if no value was written, either because the component is configured to be ignored
+    /// or because its handler only queued a deferred clone operation. Deferred operations are kept until
+    /// [`apply_deferred_clones`](Self::apply_deferred_clones) or [`discard_deferred_clones`](Self::discard_deferred_clones) is called.
+    ///
+    /// # Safety
+    /// - `component` must be registered in `world`.
+    /// - `source` must point to a valid value of the type represented by `component`.
+    /// - The caller must not hold any mutable references to the data accessed through `world`.
+    pub(crate) unsafe fn clone_component_value<'b>(
+        &mut self,
+        world: UnsafeWorldCell<'_>,
+        entity: Entity,
+        component: ComponentId,
+        source: Ptr<'_>,
+        bundle_scratch_allocator: &'b Bump,
+        bundle_scratch: &mut BundleScratch<'b>,
+    ) -> bool {
+        let info = world.components().get_info_unchecked(component);
+        let Some(handler) = self.state.clone_handler(info) else {
+            return false;
+        };
+
+        #[cfg(feature = "bevy_reflect")]
+        let app_registry = world
+            .get_resource::<crate::reflect::AppTypeRegistry>()
+            .cloned();
+        #[cfg(not(feature = "bevy_reflect"))]
+        let app_registry = Option::<()>::None;
+
+        let source_component = SourceComponent { ptr: source, info };
+        let mut mapper = ();
+        let mut ctx = ComponentCloneCtx::new(
+            component,
+            entity,
+            entity,
+            bundle_scratch_allocator,
+            bundle_scratch,
+            world.entity_allocator(),
+            info,
+            &mut self.state,
+            &mut mapper,
+            app_registry.as_ref(),
+        );
+        (handler)(&source_component, &mut ctx);
+        ctx.target_component_written()
+    }
+
+    /// Runs the clone operations deferred by [`clone_component_value`](Self::clone_component_value).
+    pub(crate) fn apply_deferred_clones(&mut self, world: &mut World) {
+        world.flush();
+        for deferred in self.state.deferred_commands.drain(..) {
+            (deferred)(world, &mut ());
+        }
+    }
+
+    /// Drops the clone operations deferred by [`clone_component_value`](Self::clone_component_value) without running them.
+    pub(crate) fn discard_deferred_clones(&mut self) {
+        self.state.deferred_commands.clear();
+    }
+
     #[track_caller]
     #[inline]
     fn clone_entity_mapped_internal(
@@ -613,23 +676,13 @@ impl EntityCloner {
             }
 
             filter.clone_components(source_archetype, target_archetype, |component| {
-                let handler = match state.clone_behavior_overrides.get(&component).or_else(|| {
-                    world
-                        .components()
-                        .get_info(component)
-                        .map(ComponentInfo::clone_behavior)
-                }) {
-                    Some(behavior) => match behavior {
-                        ComponentCloneBehavior::Default => state.default_clone_fn,
-                        ComponentCloneBehavior::Ignore => return,
-                        ComponentCloneBehavior::Custom(custom) => *custom,
-                    },
-                    None => state.default_clone_fn,
-                };
-
                 // SAFETY: This component exists because it is present on the archetype.
                 let info = unsafe { world.components().get_info_unchecked(component) };
 
+                let Some(handler) = state.clone_handler(info) else {
+                    return;
+                };
+
                 // SAFETY:
                 // - There are no other mutable references to source entity.
                 // - `component` is from `source_entity`'s archetype
@@ -768,6 +821,22 @@ struct EntityClonerState {
     deferred_commands: VecDeque<Box<dyn FnOnce(&mut World, &mut dyn EntityMapper)>>,
 }
 
+impl EntityClonerState {
+    /// Returns the clone handler used for the component described by `info`,
+    /// or `None` if the component should not be cloned.
+    fn clone_handler(&self, info: &ComponentInfo) -> Option<ComponentCloneFn> {
+        let behavior = self
+            .clone_behavior_overrides
+            .get(&info.id())
+            .unwrap_or(info.clone_behavior());
+        match behavior {
+            ComponentCloneBehavior::Default => Some(self.default_clone_fn),
+            ComponentCloneBehavior::Ignore => None,
+            ComponentCloneBehavior::Custom(custom) => Some(*custom),
+        }
+    }
+}
+
 impl Default for EntityClonerState {
     fn default() -> Self {
         Self {
@@ -733,6 +733,22 @@ impl EntityAllocator {
         self.inner.free(freed);
     }
 
+    /// Returns the entities pending reuse in the order they were freed, and the next fresh [`EntityIndex`] to give out.
+    pub(crate) fn parts(&mut self) -> (Vec<Entity>, u32) {
+        self.inner.parts()
+    }
+
+    /// Replaces the state of this allocator with the given free list and next fresh [`EntityIndex`].
+    ///
+    /// This disconnects all [`RemoteAllocator`]s built from this allocator.
+    pub(crate) fn restore_from_parts(
+        &mut self,
+        free: impl IntoIterator<Item = Entity>,
+        next_index: u32,
+    ) {
+        self.inner = remote_allocator::Allocator::from_parts(free, next_index);
+    }
+
     /// Allocates some [`Entity`].
     /// The result could have come from a [`free`](Self::free) or be a brand new [`EntityIndex`].
     ///
@@ -993,6 +1009,23 @@ impl Entities {
         Entity::from_index_and_generation(index, meta.generation)
     }
 
+    /// Sets the [`EntityGeneration`] of a despawned `index`, returning the [`Entity`] that now refers to it.
+    ///
+    /// # Safety
+    ///
+    /// - `index` must be despawned (have no location) already.
+    pub(crate) unsafe fn set_generation(
+        &mut self,
+        index: EntityIndex,
+        generation: EntityGeneration,
+    ) -> Entity {
+        self.ensure_index_index_is_valid(index);
+        // SAFETY: We just did `ensure_index`
+        let meta = unsafe { self.meta.get_unchecked_mut(index.index() as usize) };
+        meta.generation = generation;
+        Entity::from_index_and_generation(index, generation)
+    }
+
     /// Mark an [`EntityIndex`] as spawned or despawned in the given tick.
     ///
     /// # Safety
@@ -933,6 +933,33 @@ impl Allocator {
         // SAFETY: `free` takes `&mut self`, and this lifetime is captured by the iterator.
         unsafe { self.shared.alloc_many(count) }
     }
+
+    /// Constructs a new [`Allocator`] that will next give out the `free` entities, last to first,
+    /// and then fresh indices starting at `next_index`.
+    pub(super) fn from_parts(free: impl IntoIterator<Item = Entity>, next_index: u32) -> Self {
+        let mut allocator = Self::new();
+        allocator
+            .shared
+            .fresh
+            .next_entity_index
+            .store(next_index, Ordering::Relaxed);
+        for entity in free {
+            allocator.free(entity);
+        }
+        allocator
+    }
+
+    /// Returns the entities pending reuse in the order they were freed, followed by the next fresh index.
+    ///
+    /// Remote allocations may still be taking entities from the free list concurrently,
+    /// so entities that are being allocated remotely at this moment may be included.
+    pub(super) fn parts(&mut self) -> (Vec<Entity>, u32) {
+        let len = self.num_free();
+        // SAFETY: We have `&mut self`, so `free` can not be called while iterating.
+        // Every index below the length was initialized by a previous `free`.
+        let free = unsafe { self.shared.free.buffer.iter(0..len) }.collect();
+        (free, self.total_entity_indices())
+    }
 }
 
 impl Drop for Allocator {
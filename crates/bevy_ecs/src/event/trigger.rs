@@ -3,12 +3,13 @@ use crate::{
     component::ComponentId,
     entity::Entity,
     event::{EntityEvent, Event},
-    observer::{CachedObservers, TriggerContext},
+    observer::{CachedObservers, ObserverMap, TriggerContext},
     traversal::Traversal,
     world::DeferredWorld,
 };
 use bevy_ptr::PtrMut;
 use core::{fmt, marker::PhantomData};
+use smallvec::SmallVec;
 
 /// [`Trigger`] determines _how_ an [`Event`] is triggered when [`World::trigger`](crate::world::World::trigger) is called.
 /// This decides which [`Observer`](crate::observer::Observer)s will run, what data gets passed to them, and the order they will
@@ -94,28 +95,26 @@ impl GlobalTrigger {
         mut world: DeferredWorld,
         observers: &CachedObservers,
         trigger_context: &TriggerContext,
-        mut event: PtrMut,
+        event: PtrMut,
     ) {
         // SAFETY: `observers` is the only active reference to something in `world`
         unsafe {
             world.as_unsafe_world_cell().increment_trigger_id();
         }
-        for (observer, runner) in observers.global_observers() {
-            // SAFETY:
-            // - `observers` come from `world` and match the `event` type, enforced by the call to `trigger_internal`
-            // - the passed in event pointer is an `Event`, enforced by the call to `trigger_internal`
-            // - `trigger` is a matching trigger type, as it comes from `self`, which is the Trigger for `event`, enforced by `trigger_internal`
-            // - `trigger_context`'s event_key matches `E`, enforced by the call to `trigger_internal`
-            // - this abides by the nuances defined in the `Trigger` safety docs
-            unsafe {
-                (runner)(
-                    world.reborrow(),
-                    *observer,
-                    trigger_context,
-                    event.reborrow(),
-                    self.into(),
-                );
-            }
+        // SAFETY:
+        // - `observers` come from `world` and match the `event` type, enforced by the call to `trigger_internal`
+        // - the passed in event pointer is an `Event`, enforced by the call to `trigger_internal`
+        // - `trigger` is a matching trigger type, as it comes from `self`, which is the Trigger for `event`, enforced by `trigger_internal`
+        // - `trigger_context`'s event_key matches `E`, enforced by the call to `trigger_internal`
+        // - this abides by the nuances defined in the `Trigger` safety docs
+        unsafe {
+            run_observers(
+                world,
+                &[observers.global_observers()],
+                event,
+                self.into(),
+                trigger_context,
+            );
         }
     }
 }
@@ -175,48 +174,83 @@ unsafe impl<E: EntityEvent + for<'a> Event<Trigger<'a> = Self>> Trigger<E> for E
 pub unsafe fn trigger_entity_internal(
     mut world: DeferredWorld,
     observers: &CachedObservers,
-    mut event: PtrMut,
-    mut trigger: PtrMut,
+    event: PtrMut,
+    trigger: PtrMut,
     target_entity: Entity,
     trigger_context: &TriggerContext,
 ) {
-    // SAFETY: there are no outstanding world references
+    // SAFETY: `observers` is the only active reference to something in `world`. It borrows the
+    // cached observers, which are disjoint from the `last_trigger_id` mutated here.
     unsafe {
         world.as_unsafe_world_cell().increment_trigger_id();
     }
-    for (observer, runner) in observers.global_observers() {
+    let mut maps: SmallVec<[&ObserverMap; 2]> = SmallVec::new();
+    maps.push(observers.global_observers());
+    if let Some(map) = observers.entity_observers().get(&target_entity) {
+        maps.push(map);
+    }
+    // SAFETY:
+    // - `observers` come from `world` and match the `event` type, enforced by the call to `trigger_entity_internal`
+    // - the passed in event pointer is an `Event`, enforced by the call to `trigger_entity_internal`
+    // - `trigger` is a matching trigger type, enforced by the call to `trigger_entity_internal`
+    // - `trigger_context`'s event_key matches `E`, enforced by the call to `trigger_entity_internal`
+    unsafe {
+        run_observers(world, &maps, event, trigger, trigger_context);
+    }
+}
+
+/// Runs the observers of every map in `maps` for the current target, by descending
+/// [priority](crate::observer::Observer::with_priority). Observers with the same priority run in the order of `maps`.
+///
+/// Stops early if an observer calls [`On::stop_immediate_propagation`](crate::observer::On::stop_immediate_propagation).
+///
+/// # Safety
+/// - `maps` must come from the `world` [`DeferredWorld`], and correspond to observers that match the `event` type
+/// - `event` must point to an [`Event`]
+/// - `trigger` must correspond to the [`Event::Trigger`] type expected by the `event`
+/// - `trigger_context`'s [`TriggerContext::event_key`] must correspond to the `event` type.
+/// - Read, understand, and abide by the [`Trigger`] safety documentation
+unsafe fn run_observers(
+    mut world: DeferredWorld,
+    maps: &[&ObserverMap],
+    mut event: PtrMut,
+    mut trigger: PtrMut,
+    trigger_context: &TriggerContext,
+) {
+    trigger_context.immediate_propagation_stopped.set(false);
+    let mut cursors: SmallVec<[usize; 4]> = SmallVec::from_elem(0, maps.len());
+    loop {
+        let mut next: Option<(usize, i32)> = None;
+        for (index, map) in maps.iter().enumerate() {
+            if let Some(&(.., priority)) = map.entries().get(cursors[index])
+                && next.is_none_or(|(_, best)| priority > best)
+            {
+                next = Some((index, priority));
+            }
+        }
+        let Some((index, _)) = next else {
+            return;
+        };
+        let (observer, runner, _) = maps[index].entries()[cursors[index]];
+        cursors[index] += 1;
+
         // SAFETY:
-        // - `observers` come from `world` and match the `event` type, enforced by the call to `trigger_entity_internal`
-        // - the passed in event pointer is an `Event`, enforced by the call to `trigger_entity_internal`
-        // - `trigger` is a matching trigger type, enforced by the call to `trigger_entity_internal`
-        // - `trigger_context`'s event_key matches `E`, enforced by the call to `trigger_entity_internal`
+        // - `maps` come from `world` and match the `event` type, enforced by the call to `run_observers`
+        // - the passed in event pointer is an `Event`, enforced by the call to `run_observers`
+        // - `trigger` is a matching trigger type, enforced by the call to `run_observers`
+        // - `trigger_context`'s event_key matches `E`, enforced by the call to `run_observers`
         unsafe {
             (runner)(
                 world.reborrow(),
-                *observer,
+                observer,
                 trigger_context,
                 event.reborrow(),
                 trigger.reborrow(),
             );
         }
-    }
 
-    if let Some(map) = observers.entity_observers().get(&target_entity) {
-        for (observer, runner) in map {
-            // SAFETY:
-            // - `observers` come from `world` and match the `event` type, enforced by the call to `trigger_entity_internal`
-            // - the passed in event pointer is an `Event`, enforced by the call to `trigger_entity_internal`
-            // - `trigger` is a matching trigger type, enforced by the call to `trigger_entity_internal`
-            // - `trigger_context`'s event_key matches `E`, enforced by the call to `trigger_entity_internal`
-            unsafe {
-                (runner)(
-                    world.reborrow(),
-                    *observer,
-                    trigger_context,
-                    event.reborrow(),
-                    trigger.reborrow(),
-                );
-            }
+        if trigger_context.immediate_propagation_stopped.get() {
+            return;
         }
     }
 }
@@ -376,68 +410,41 @@ impl<'a> EntityComponentsTrigger<'a> {
         &mut self,
         mut world: DeferredWorld,
         observers: &CachedObservers,
-        mut event: PtrMut,
+        event: PtrMut,
         entity: Entity,
         trigger_context: &TriggerContext,
     ) {
-        // SAFETY:
-        // - `observers` come from `world` and match the event type `E`, enforced by the call to `trigger`
-        // - the passed in event pointer comes from `event`, which is an `Event`
-        // - `trigger` is a matching trigger type, as it comes from `self`, which is the Trigger for `E`
-        // - `trigger_context`'s event_key matches `E`, enforced by the call to `trigger`
+        // SAFETY: `observers` is the only active reference to something in `world`. It borrows the
+        // cached observers, which are disjoint from the `last_trigger_id` mutated here.
         unsafe {
-            trigger_entity_internal(
-                world.reborrow(),
-                observers,
-                event.reborrow(),
-                self.into(),
-                entity,
-                trigger_context,
-            );
+            world.as_unsafe_world_cell().increment_trigger_id();
         }
 
-        // Trigger observers watching for a specific component
+        let mut maps: SmallVec<[&ObserverMap; 4]> = SmallVec::new();
+        maps.push(observers.global_observers());
+        if let Some(map) = observers.entity_observers().get(&entity) {
+            maps.push(map);
+        }
+        // Include observers watching for a specific component
         for id in self.components {
             if let Some(component_observers) = observers.component_observers().get(id) {
-                for (observer, runner) in component_observers.global_observers() {
-                    // SAFETY:
-                    // - `observers` come from `world` and match the `event` type, enforced by the call to `trigger_internal`
-                    // - the passed in event pointer is an `Event`, enforced by the call to `trigger_internal`
-                    // - `trigger` is a matching trigger type, enforced by the call to `trigger_internal`
-                    // - `trigger_context`'s event_key matches `E`, enforced by the call to `trigger_internal`
-                    unsafe {
-                        (runner)(
-                            world.reborrow(),
-                            *observer,
-                            trigger_context,
-                            event.reborrow(),
-                            self.into(),
-                        );
-                    }
-                }
-
+                maps.push(component_observers.global_observers());
                 if let Some(map) = component_observers
                     .entity_component_observers()
                     .get(&entity)
                 {
-                    for (observer, runner) in map {
-                        // SAFETY:
-                        // - `observers` come from `world` and match the `event` type, enforced by the call to `trigger_internal`
-                        // - the passed in event pointer is an `Event`, enforced by the call to `trigger_internal`
-                        // - `trigger` is a matching trigger type, enforced by the call to `trigger_internal`
-                        // - `trigger_context`'s event_key matches `E`, enforced by the call to `trigger_internal`
-                        unsafe {
-                            (runner)(
-                                world.reborrow(),
-                                *observer,
-                                trigger_context,
-                                event.reborrow(),
-                                self.into(),
-                            );
-                        }
-                    }
+                    maps.push(map);
                 }
             }
         }
+
+        // SAFETY:
+        // - `observers` come from `world` and match the event type `E`, enforced by the call to `trigger`
+        // - the passed in event pointer comes from `event`, which is an `Event`
+        // - `trigger` is a matching trigger type, as it comes from `self`, which is the Trigger for `E`
+        // - `trigger_context`'s event_key matches `E`, enforced by the call to `trigger`
+        unsafe {
+            run_observers(world, &maps, event, self.into(), trigger_context);
+        }
     }
 }
@@ -8,6 +8,7 @@ mod message_registry;
 mod message_writer;
 mod messages;
 mod mut_iterators;
+mod persistent_messages;
 mod update;
 
 pub use iterators::*;
@@ -18,6 +19,7 @@ pub use message_registry::*;
 pub use message_writer::*;
 pub use messages::*;
 pub use mut_iterators::*;
+pub use persistent_messages::*;
 pub use update::*;
 
 pub use bevy_ecs_macros::Message;
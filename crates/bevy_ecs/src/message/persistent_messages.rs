@@ -0,0 +1,568 @@
+use crate::{
+    change_detection::MaybeLocation,
+    message::{Message, MessageId, MessageInstance},
+    resource::Resource,
+    system::{Local, ResMut, SystemName, SystemParam},
+    world::{FromWorld, World},
+};
+use alloc::{collections::VecDeque, string::ToString, vec::Vec};
+use bevy_platform::sync::{Arc, Weak};
+use bevy_utils::prelude::DebugName;
+use core::marker::PhantomData;
+use log::warn;
+
+/// A message collection where each message persists until every registered reader has acknowledged it.
+///
+/// Unlike [`Messages<M>`](super::Messages), which drops messages after two updates whether they
+/// were read or not, this storage keeps them until all of its [`PersistentMessageReader`]s have
+/// called [`ack`](PersistentMessageReader::ack) past them. This makes it suitable for messages
+/// written in schedules which do not run every frame, such as `FixedUpdate`, and read elsewhere.
+///
+/// Each [`PersistentMessageReader`] registers itself when its system is initialized, and sees
+/// all messages still stored at that time. It is unregistered once its system is dropped, for example
+/// after [`World::run_system_once`](crate::system::RunSystemOnce::run_system_once), and the messages
+/// only it did not acknowledge are dropped. When the last reader is unregistered, all stored messages
+/// are dropped. Messages written while no reader is registered are kept for the readers registered
+/// later, for example by a schedule which runs for the first time.
+///
+/// To bound the memory used when no reader acknowledges the messages, at most
+/// [`max_len`](Self::max_len) messages are stored, [`DEFAULT_MAX_LEN`](Self::DEFAULT_MAX_LEN)
+/// by default. When it is exceeded, the oldest messages are dropped, and a warning is logged for
+/// each reader falling behind. [`readers`](Self::readers) can also be used to monitor how far
+/// behind each reader is.
+///
+/// Messages are written with a [`PersistentMessageWriter`]. No update system is needed.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::message::{PersistentMessageReader, PersistentMessageWriter, PersistentMessages};
+/// #[derive(Message)]
+/// struct Collision(Entity, Entity);
+///
+/// fn detect(mut writer: PersistentMessageWriter<Collision>) {
+///     // ...
+/// }
+///
+/// fn play_sounds(mut reader: PersistentMessageReader<Collision>) {
+///     for Collision(a, b) in reader.read() {
+///         // ...
+///     }
+///     reader.ack();
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(PersistentMessages::<Collision>::with_max_len(1024));
+/// ```
+#[derive(Debug, Resource)]
+pub struct PersistentMessages<M: Message> {
+    messages: VecDeque<MessageInstance<M>>,
+    /// The id of the oldest stored message, or of the next written message if none are stored.
+    start: usize,
+    readers: Vec<PersistentReaderState>,
+    next_reader_id: usize,
+    max_len: Option<usize>,
+}
+
+/// The state of a [`PersistentMessageReader`], as reported by [`PersistentMessages::readers`].
+#[derive(Debug, Clone)]
+pub struct PersistentReaderState {
+    id: usize,
+    /// Dangles once the [`PersistentReaderId`] of this reader is dropped.
+    alive: Weak<()>,
+    /// The id of the next message this reader has not acknowledged.
+    cursor: usize,
+    name: Option<DebugName>,
+    missed: usize,
+}
+
+impl PersistentReaderState {
+    /// Returns the name of the system this reader belongs to, once it has run.
+    pub fn name(&self) -> Option<&DebugName> {
+        self.name.as_ref()
+    }
+
+    /// Returns the total number of messages dropped before this reader acknowledged them,
+    /// because the [`max_len`](PersistentMessages::max_len) of the storage was exceeded.
+    pub fn missed(&self) -> usize {
+        self.missed
+    }
+}
+
+// Derived Default impl would incorrectly require M: Default
+impl<M: Message> Default for PersistentMessages<M> {
+    fn default() -> Self {
+        Self {
+            messages: VecDeque::new(),
+            start: 0,
+            readers: Vec::new(),
+            next_reader_id: 0,
+            max_len: Some(Self::DEFAULT_MAX_LEN),
+        }
+    }
+}
+
+impl<M: Message> PersistentMessages<M> {
+    /// The [`max_len`](Self::max_len) of the default storage.
+    pub const DEFAULT_MAX_LEN: usize = 4096;
+
+    /// Creates an empty storage holding at most `max_len` messages.
+    pub fn with_max_len(max_len: usize) -> Self {
+        Self {
+            max_len: Some(max_len),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the maximum number of stored messages, if any.
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Sets the maximum number of stored messages, dropping the oldest ones if needed.
+    ///
+    /// With `None`, messages are kept until every reader acknowledged them, and messages written
+    /// while no reader is registered are kept forever.
+    pub fn set_max_len(&mut self, max_len: Option<usize>) {
+        self.max_len = max_len;
+        self.enforce_max_len();
+    }
+
+    /// Writes a `message`, which is then stored until every reader acknowledged it.
+    /// This method returns the [ID](`MessageId`) of the written `message`.
+    #[track_caller]
+    pub fn write(&mut self, message: M) -> MessageId<M> {
+        self.unregister_dropped_readers();
+        let message_id = MessageId {
+            id: self.start + self.messages.len(),
+            caller: MaybeLocation::caller(),
+            _marker: PhantomData,
+        };
+        self.messages.push_back(MessageInstance {
+            message_id,
+            message,
+        });
+        self.enforce_max_len();
+        message_id
+    }
+
+    /// Writes a list of `messages` all at once.
+    /// This method returns the [IDs](`MessageId`) of the written `messages`.
+    #[track_caller]
+    pub fn write_batch(&mut self, messages: impl IntoIterator<Item = M>) -> Vec<MessageId<M>> {
+        messages
+            .into_iter()
+            .map(|message| self.write(message))
+            .collect()
+    }
+
+    /// Returns the number of stored messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if no messages are stored.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Iterates over the states of the registered readers.
+    pub fn readers(&self) -> impl Iterator<Item = &PersistentReaderState> {
+        self.readers
+            .iter()
+            .filter(|reader| reader.alive.strong_count() > 0)
+    }
+
+    /// Returns the number of stored messages the reader with the given `state` has not acknowledged.
+    pub fn unacknowledged(&self, state: &PersistentReaderState) -> usize {
+        self.start + self.messages.len() - state.cursor.max(self.start)
+    }
+
+    fn register_reader(&mut self) -> (usize, Arc<()>) {
+        self.unregister_dropped_readers();
+        let id = self.next_reader_id;
+        self.next_reader_id += 1;
+        let alive = Arc::new(());
+        self.readers.push(PersistentReaderState {
+            id,
+            alive: Arc::downgrade(&alive),
+            cursor: self.start,
+            name: None,
+            missed: 0,
+        });
+        (id, alive)
+    }
+
+    /// Returns the state of the reader with the given `id`, registering it again
+    /// if it is missing, for example because this resource was replaced.
+    fn reader_state(&mut self, id: usize, alive: &Arc<()>) -> &mut PersistentReaderState {
+        let index = match self.readers.iter().position(|reader| reader.id == id) {
+            Some(index) => index,
+            None => {
+                self.readers.push(PersistentReaderState {
+                    id,
+                    alive: Arc::downgrade(alive),
+                    cursor: self.start,
+                    name: None,
+                    missed: 0,
+                });
+                self.readers.len() - 1
+            }
+        };
+        &mut self.readers[index]
+    }
+
+    /// Removes the readers whose system has been dropped, along with the messages only they kept alive.
+    fn unregister_dropped_readers(&mut self) {
+        let len = self.readers.len();
+        self.readers
+            .retain(|reader| reader.alive.strong_count() > 0);
+        if self.readers.len() < len {
+            self.drop_acknowledged();
+        }
+    }
+
+    /// Drops the messages acknowledged by every reader, or all of them if there are no readers.
+    fn drop_acknowledged(&mut self) {
+        let oldest = self.readers.iter().map(|reader| reader.cursor).min();
+        let excess = match oldest {
+            Some(oldest) => oldest.saturating_sub(self.start),
+            None => self.messages.len(),
+        };
+        self.messages.drain(..excess);
+        self.start += excess;
+    }
+
+    fn enforce_max_len(&mut self) {
+        let Some(max_len) = self.max_len else {
+            return;
+        };
+        let Some(excess) = self.messages.len().checked_sub(max_len) else {
+            return;
+        };
+        self.messages.drain(..excess);
+        self.start += excess;
+
+        for reader in &mut self.readers {
+            let Some(missed) = self.start.checked_sub(reader.cursor).filter(|n| *n > 0) else {
+                continue;
+            };
+            if reader.missed == 0 {
+                let name = reader
+                    .name
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_else(|| "A reader which has not run yet".into());
+                warn!(
+                    "{name} fell behind reading persistent messages of type {}: the oldest unacknowledged messages were dropped. Consider acknowledging messages more often or increasing `max_len`.",
+                    DebugName::type_name::<M>()
+                );
+            }
+            reader.missed += missed;
+            reader.cursor = self.start;
+        }
+    }
+
+    fn acknowledge(&mut self, reader: usize, until: usize) {
+        let end = self.start + self.messages.len();
+        if let Some(reader) = self.readers.iter_mut().find(|state| state.id == reader) {
+            reader.cursor = reader.cursor.max(until.min(end));
+        }
+        self.unregister_dropped_readers();
+        self.drop_acknowledged();
+    }
+}
+
+/// The id of a [`PersistentMessageReader`] in [`PersistentMessages<M>`], registered when its
+/// system is initialized and unregistered when it is dropped.
+pub struct PersistentReaderId<M: Message> {
+    id: usize,
+    alive: Arc<()>,
+    _marker: PhantomData<M>,
+}
+
+impl<M: Message> FromWorld for PersistentReaderId<M> {
+    fn from_world(world: &mut World) -> Self {
+        let (id, alive) = world
+            .get_resource_or_init::<PersistentMessages<M>>()
+            .register_reader();
+        Self {
+            id,
+            alive,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Reads [`Message`]s of type `M` from [`PersistentMessages<M>`], which keeps them until
+/// they are explicitly acknowledged by every reader.
+///
+/// Messages returned by [`read`](Self::read) are returned again on the next read, until
+/// [`ack`](Self::ack) or [`ack_until`](Self::ack_until) is called.
+///
+/// # Concurrency
+///
+/// Acknowledging messages requires mutable access to [`PersistentMessages<M>`], so systems with
+/// this param can not run concurrently with other readers or writers of the same message type.
+#[derive(SystemParam)]
+pub struct PersistentMessageReader<'w, 's, M: Message> {
+    reader: Local<'s, PersistentReaderId<M>>,
+    #[system_param(validation_message = "Persistent message not initialized")]
+    messages: ResMut<'w, PersistentMessages<M>>,
+    name: SystemName,
+}
+
+impl<'w, 's, M: Message> PersistentMessageReader<'w, 's, M> {
+    fn state(&mut self) -> &PersistentReaderState {
+        let name = &self.name;
+        let state = self
+            .messages
+            .reader_state(self.reader.id, &self.reader.alive);
+        state.name.get_or_insert_with(|| name.name());
+        state
+    }
+
+    /// Iterates over the messages this reader has not acknowledged yet.
+    pub fn read(&mut self) -> impl ExactSizeIterator<Item = &M> {
+        self.read_with_id().map(|(message, _)| message)
+    }
+
+    /// Like [`read`](Self::read), except also returning the [`MessageId`] of the messages.
+    pub fn read_with_id(&mut self) -> impl ExactSizeIterator<Item = (&M, MessageId<M>)> {
+        let cursor = self.state().cursor;
+        let messages = &self.messages;
+        messages
+            .messages
+            .range(cursor - messages.start..)
+            .map(|instance| (&instance.message, instance.message_id))
+    }
+
+    /// Returns the number of messages this reader has not acknowledged yet.
+    pub fn len(&mut self) -> usize {
+        let cursor = self.state().cursor;
+        self.messages.start + self.messages.len() - cursor
+    }
+
+    /// Returns `true` if this reader has acknowledged all messages.
+    pub fn is_empty(&mut self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total number of messages dropped before this reader acknowledged them.
+    ///
+    /// See [`PersistentMessages::set_max_len`].
+    pub fn missed(&mut self) -> usize {
+        self.state().missed
+    }
+
+    /// Acknowledges all messages, so that this reader does not read them again.
+    ///
+    /// Messages acknowledged by every reader are dropped.
+    pub fn ack(&mut self) {
+        self.state();
+        self.messages.acknowledge(self.reader.id, usize::MAX);
+    }
+
+    /// Acknowledges all messages up to and including the one with the given `id`.
+    ///
+    /// This is useful to only process part of the messages during each run.
+    pub fn ack_until(&mut self, id: MessageId<M>) {
+        self.state();
+        self.messages.acknowledge(self.reader.id, id.id + 1);
+    }
+}
+
+/// Writes [`Message`]s of type `M` to [`PersistentMessages<M>`].
+#[derive(SystemParam)]
+pub struct PersistentMessageWriter<'w, M: Message> {
+    #[system_param(validation_message = "Persistent message not initialized")]
+    messages: ResMut<'w, PersistentMessages<M>>,
+}
+
+impl<'w, M: Message> PersistentMessageWriter<'w, M> {
+    /// Writes a `message`, which can later be read by [`PersistentMessageReader`]s.
+    /// This method returns the [ID](`MessageId`) of the written `message`.
+    #[track_caller]
+    pub fn write(&mut self, message: M) -> MessageId<M> {
+        self.messages.write(message)
+    }
+
+    /// Writes a list of `messages` all at once.
+    /// This method returns the [IDs](`MessageId`) of the written `messages`.
+    #[track_caller]
+    pub fn write_batch(&mut self, messages: impl IntoIterator<Item = M>) -> Vec<MessageId<M>> {
+        self.messages.write_batch(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        schedule::{IntoScheduleConfigs, Schedule},
+        system::{Res, ResMut, RunSystemOnce},
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Message, Clone, Copy, Debug, PartialEq)]
+    struct TestMessage(u32);
+
+    #[derive(Resource, Default)]
+    struct Received(Vec<(&'static str, u32)>);
+
+    #[derive(Resource)]
+    struct Ack(bool);
+
+    fn reader(
+        name: &'static str,
+    ) -> impl FnMut(PersistentMessageReader<TestMessage>, ResMut<Received>, Res<Ack>) {
+        move |mut reader, mut received, ack| {
+            received
+                .0
+                .extend(reader.read().map(|message| (name, message.0)));
+            if ack.0 {
+                reader.ack();
+            }
+        }
+    }
+
+    #[test]
+    fn messages_persist_until_every_reader_acknowledges() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        world.insert_resource(Ack(true));
+        world.init_resource::<PersistentMessages<TestMessage>>();
+        world
+            .resource_mut::<PersistentMessages<TestMessage>>()
+            .write(TestMessage(0));
+
+        let mut fast = Schedule::default();
+        fast.add_systems(reader("fast"));
+        let mut slow = Schedule::default();
+        slow.add_systems(reader("slow"));
+        fast.initialize(&mut world).unwrap();
+        slow.initialize(&mut world).unwrap();
+
+        world
+            .resource_mut::<PersistentMessages<TestMessage>>()
+            .write(TestMessage(1));
+        fast.run(&mut world);
+        fast.run(&mut world);
+        assert_eq!(world.resource::<PersistentMessages<TestMessage>>().len(), 2);
+
+        slow.run(&mut world);
+        assert!(world
+            .resource::<PersistentMessages<TestMessage>>()
+            .is_empty());
+        assert_eq!(
+            world.resource::<Received>().0,
+            vec![("fast", 0), ("fast", 1), ("slow", 0), ("slow", 1)]
+        );
+    }
+
+    #[test]
+    fn unacknowledged_messages_are_read_again() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        world.insert_resource(Ack(false));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                |mut writer: PersistentMessageWriter<TestMessage>| {
+                    writer.write(TestMessage(7));
+                },
+                reader("reader"),
+            )
+                .chain(),
+        );
+        schedule.run(&mut world);
+        world.resource_mut::<Ack>().0 = true;
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(
+            world.resource::<Received>().0,
+            vec![("reader", 7), ("reader", 7), ("reader", 7), ("reader", 7)]
+        );
+        assert!(world
+            .resource::<PersistentMessages<TestMessage>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn dropped_readers_do_not_keep_messages_alive() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        world.insert_resource(Ack(true));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(reader("reader"));
+        schedule.initialize(&mut world).unwrap();
+        world
+            .resource_mut::<PersistentMessages<TestMessage>>()
+            .write(TestMessage(0));
+
+        let unread = world
+            .run_system_once(|mut reader: PersistentMessageReader<TestMessage>| reader.len())
+            .unwrap();
+        assert_eq!(unread, 1);
+        schedule.run(&mut world);
+        let messages = world.resource::<PersistentMessages<TestMessage>>();
+        assert!(messages.is_empty());
+        assert_eq!(messages.readers().count(), 1);
+
+        // Once the last reader is dropped, the messages it kept alive are dropped as well.
+        world
+            .resource_mut::<PersistentMessages<TestMessage>>()
+            .write(TestMessage(1));
+        drop(schedule);
+        let mut messages = world.resource_mut::<PersistentMessages<TestMessage>>();
+        messages.write(TestMessage(2));
+        assert_eq!(messages.readers().count(), 0);
+        assert_eq!(messages.len(), 1);
+    }
+
+    #[test]
+    fn max_len_drops_oldest_messages() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        world.insert_resource(Ack(true));
+        world.insert_resource(PersistentMessages::<TestMessage>::with_max_len(2));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(reader("reader"));
+        schedule.initialize(&mut world).unwrap();
+
+        world
+            .resource_mut::<PersistentMessages<TestMessage>>()
+            .write_batch([TestMessage(0), TestMessage(1), TestMessage(2)]);
+        let messages = world.resource::<PersistentMessages<TestMessage>>();
+        let state = messages.readers().next().unwrap();
+        assert_eq!(state.missed(), 1);
+        assert_eq!(messages.unacknowledged(state), 2);
+
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<Received>().0,
+            vec![("reader", 1), ("reader", 2)]
+        );
+    }
+
+    #[test]
+    fn messages_without_readers_are_bounded() {
+        let mut messages = PersistentMessages::<TestMessage>::default();
+        let max_len = PersistentMessages::<TestMessage>::DEFAULT_MAX_LEN;
+        for i in 0..max_len as u32 + 10 {
+            messages.write(TestMessage(i));
+        }
+        assert_eq!(messages.readers().count(), 0);
+        assert_eq!(messages.len(), max_len);
+
+        // Readers registered later see the newest messages.
+        let mut world = World::new();
+        world.insert_resource(messages);
+        let first = world
+            .run_system_once(|mut reader: PersistentMessageReader<TestMessage>| {
+                reader.read().next().copied()
+            })
+            .unwrap();
+        assert_eq!(first, Some(TestMessage(10)));
+    }
+}
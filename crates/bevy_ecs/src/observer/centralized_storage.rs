@@ -9,10 +9,14 @@
 //!     - These are split by target type, in order to allow for different lookup strategies.
 //!     - [`CachedComponentObservers`] is one of these maps, which contains observers that are specifically targeted at a component.
 
+use alloc::vec::Vec;
 use bevy_platform::collections::HashMap;
 
 use crate::{
-    archetype::ArchetypeFlags, component::ComponentId, entity::EntityHashMap, event::EventKey,
+    archetype::ArchetypeFlags,
+    component::ComponentId,
+    entity::{Entity, EntityHashMap},
+    event::EventKey,
     observer::ObserverRunner,
 };
 
@@ -144,8 +148,92 @@ impl CachedObservers {
     }
 }
 
-/// Map between an observer entity and its [`ObserverRunner`]
-pub type ObserverMap = EntityHashMap<ObserverRunner>;
+/// Map between an observer entity and its [`ObserverRunner`].
+///
+/// Observers are sorted by descending [priority](crate::observer::Observer::with_priority),
+/// then by registration order, which is the order they run in.
+#[derive(Default, Debug, Clone)]
+pub struct ObserverMap {
+    observers: Vec<(Entity, ObserverRunner, i32)>,
+    /// The index of each observer in `observers`.
+    indices: EntityHashMap<usize>,
+}
+
+impl ObserverMap {
+    /// Returns the [`ObserverRunner`] of the given `observer`, if it is in the map.
+    pub fn get(&self, observer: &Entity) -> Option<&ObserverRunner> {
+        self.indices
+            .get(observer)
+            .map(|&index| &self.observers[index].1)
+    }
+
+    /// Returns `true` if the given `observer` is in the map.
+    pub fn contains_key(&self, observer: &Entity) -> bool {
+        self.get(observer).is_some()
+    }
+
+    /// Returns the number of observers in the map.
+    pub fn len(&self) -> usize {
+        self.observers.len()
+    }
+
+    /// Returns `true` if the map contains no observers.
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    /// Iterates over the observers and their runners, in the order they run.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&Entity, &ObserverRunner)> {
+        self.observers
+            .iter()
+            .map(|(entity, runner, _)| (entity, runner))
+    }
+
+    /// Iterates over the observers, their runners and their priorities, in the order they run.
+    pub(crate) fn entries(&self) -> &[(Entity, ObserverRunner, i32)] {
+        &self.observers
+    }
+
+    pub(super) fn insert(&mut self, observer: Entity, runner: ObserverRunner, priority: i32) {
+        self.remove(&observer);
+        let index = self
+            .observers
+            .partition_point(|(.., other)| *other >= priority);
+        self.observers.insert(index, (observer, runner, priority));
+        self.reindex_from(index);
+    }
+
+    pub(super) fn remove(&mut self, observer: &Entity) {
+        if let Some(index) = self.indices.remove(observer) {
+            self.observers.remove(index);
+            self.reindex_from(index);
+        }
+    }
+
+    /// Updates the indices of the observers from `start`, after they were shifted.
+    fn reindex_from(&mut self, start: usize) {
+        for (index, (entity, ..)) in self.observers.iter().enumerate().skip(start) {
+            self.indices.insert(*entity, index);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ObserverMap {
+    type Item = (&'a Entity, &'a ObserverRunner);
+    type IntoIter = core::iter::Map<
+        core::slice::Iter<'a, (Entity, ObserverRunner, i32)>,
+        fn(&'a (Entity, ObserverRunner, i32)) -> (&'a Entity, &'a ObserverRunner),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        fn entry<'a>(
+            (entity, runner, _): &'a (Entity, ObserverRunner, i32),
+        ) -> (&'a Entity, &'a ObserverRunner) {
+            (entity, runner)
+        }
+        self.observers.iter().map(entry as _)
+    }
+}
 
 /// Collection of [`ObserverRunner`] for [`Observer`](crate::observer::Observer) registered to a particular event targeted at a specific component.
 ///
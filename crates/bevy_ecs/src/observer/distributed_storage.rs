@@ -318,6 +318,22 @@ impl Observer {
         self
     }
 
+    /// Sets the priority of this observer. Defaults to `0`.
+    ///
+    /// When an event is triggered, the observers matching each target run by descending priority,
+    /// whether they watch the target or not. Observers with the same priority are guaranteed to run in the
+    /// order they were spawned, within each of these groups, which run one after the other: observers watching
+    /// no entity nor component, then observers watching the target entity, then observers watching one of the
+    /// target components, and finally observers watching one of the target components on the target entity.
+    /// An observer can prevent the observers with a lower priority from running for the current target
+    /// with [`On::stop_immediate_propagation`](crate::observer::On::stop_immediate_propagation).
+    ///
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.descriptor.priority = priority;
+        self
+    }
+
     /// Sets the error handler to use for this observer.
     ///
     /// See the [`error` module-level documentation](crate::error) for more information.
@@ -379,6 +395,9 @@ pub struct ObserverDescriptor {
 
     /// The entities the observer is watching.
     pub(super) entities: Vec<Entity>,
+
+    /// The priority of the observer, higher priorities running first.
+    pub(super) priority: i32,
 }
 
 impl ObserverDescriptor {
@@ -417,6 +436,11 @@ impl ObserverDescriptor {
     pub fn entities(&self) -> &[Entity] {
         &self.entities
     }
+
+    /// Returns the priority of the observer.
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
 }
 
 /// A [`ComponentHook`] used by [`Observer`] to handle its [`on-add`](`crate::lifecycle::ComponentHooks::on_add`).
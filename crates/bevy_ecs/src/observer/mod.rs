@@ -132,14 +132,16 @@ impl World {
             let cache = observers.get_observers_mut(event_key);
 
             if descriptor.components.is_empty() && descriptor.entities.is_empty() {
-                cache
-                    .global_observers
-                    .insert(observer_entity, observer_state.runner);
+                cache.global_observers.insert(
+                    observer_entity,
+                    observer_state.runner,
+                    descriptor.priority,
+                );
             } else if descriptor.components.is_empty() {
                 // Observer is not targeting any components so register it as an entity observer
                 for &watched_entity in &observer_state.descriptor.entities {
                     let map = cache.entity_observers.entry(watched_entity).or_default();
-                    map.insert(observer_entity, observer_state.runner);
+                    map.insert(observer_entity, observer_state.runner, descriptor.priority);
                 }
             } else {
                 // Register observer for each watched component
@@ -156,9 +158,11 @@ impl World {
                             });
                     if descriptor.entities.is_empty() {
                         // Register for all triggers targeting the component
-                        observers
-                            .global_observers
-                            .insert(observer_entity, observer_state.runner);
+                        observers.global_observers.insert(
+                            observer_entity,
+                            observer_state.runner,
+                            descriptor.priority,
+                        );
                     } else {
                         // Register for each watched entity
                         for &watched_entity in &descriptor.entities {
@@ -166,7 +170,7 @@ impl World {
                                 .entity_component_observers
                                 .entry(watched_entity)
                                 .or_default();
-                            map.insert(observer_entity, observer_state.runner);
+                            map.insert(observer_entity, observer_state.runner, descriptor.priority);
                         }
                     }
                 }
@@ -439,12 +443,101 @@ mod tests {
         world.add_observer(|_: On<Add, A>, mut res: ResMut<Order>| res.observed("add_2"));
 
         world.spawn(A).flush();
-        assert_eq!(vec!["add_2", "add_1"], world.resource::<Order>().0);
+        assert_eq!(vec!["add_1", "add_2"], world.resource::<Order>().0);
         // we have one A entity and two observers
         assert_eq!(world.query::<&A>().query(&world).count(), 1);
         assert_eq!(world.query::<&Observer>().query(&world).count(), 2);
     }
 
+    #[test]
+    fn observer_priority() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let entity = world.spawn_empty().id();
+        world.spawn(
+            Observer::new(|_: On<EntityEventA>, mut res: ResMut<Order>| res.observed("entity_low"))
+                .with_entity(entity)
+                .with_priority(-1),
+        );
+        world.add_observer(|_: On<EntityEventA>, mut res: ResMut<Order>| res.observed("global"));
+        world.spawn(
+            Observer::new(|_: On<EntityEventA>, mut res: ResMut<Order>| {
+                res.observed("entity_high");
+            })
+            .with_entity(entity)
+            .with_priority(10),
+        );
+        world.spawn(
+            Observer::new(|_: On<EntityEventA>, mut res: ResMut<Order>| {
+                res.observed("global_high");
+            })
+            .with_priority(5),
+        );
+
+        world.trigger(EntityEventA(entity));
+        assert_eq!(
+            vec!["entity_high", "global_high", "global", "entity_low"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_priority_components() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.add_observer(|_: On<Add, A>, mut res: ResMut<Order>| res.observed("add_a"));
+        world.spawn(
+            Observer::new(|_: On<Add, B>, mut res: ResMut<Order>| res.observed("add_b"))
+                .with_priority(1),
+        );
+
+        world.spawn((A, B)).flush();
+        assert_eq!(vec!["add_b", "add_a"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_stop_immediate_propagation() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.spawn(
+            Observer::new(|mut event: On<EventA>, mut res: ResMut<Order>| {
+                res.observed("validate");
+                event.stop_immediate_propagation();
+            })
+            .with_priority(1),
+        );
+        world.add_observer(|_: On<EventA>, mut res: ResMut<Order>| res.observed("mutate"));
+
+        world.trigger(EventA);
+        world.trigger(EventA);
+        assert_eq!(vec!["validate", "validate"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_stop_immediate_propagation_per_target() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let parent = world.spawn_empty().id();
+        let child = world.spawn(ChildOf(parent)).id();
+
+        world.spawn(
+            Observer::new(|mut event: On<EventPropagating>, mut res: ResMut<Order>| {
+                res.observed("first");
+                event.stop_immediate_propagation();
+            })
+            .with_priority(1),
+        );
+        world
+            .add_observer(|_: On<EventPropagating>, mut res: ResMut<Order>| res.observed("second"));
+
+        world.trigger(EventPropagating(child));
+        assert_eq!(vec!["first", "first"], world.resource::<Order>().0);
+    }
+
     #[test]
     fn observer_multiple_events() {
         let mut world = World::new();
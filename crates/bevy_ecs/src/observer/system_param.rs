@@ -9,6 +9,7 @@ use crate::{
 };
 use bevy_ptr::Ptr;
 use core::{
+    cell::Cell,
     fmt::Debug,
     marker::PhantomData,
     ops::{Deref, DerefMut},
@@ -123,6 +124,20 @@ impl<'w, 't, E: Event, B: Bundle> On<'w, 't, E, B> {
     pub fn caller(&self) -> MaybeLocation {
         self.trigger_context.caller
     }
+
+    /// Prevents the remaining observers of the current target from running.
+    ///
+    /// Observers run by descending [priority](Observer::with_priority), so this stops every observer
+    /// that would have run after this one for the current target. Unlike [`On::propagate`], this does not
+    /// stop [`EntityEvent`] propagation to the next [`Entity`] in the [`Traversal`].
+    pub fn stop_immediate_propagation(&mut self) {
+        self.trigger_context.immediate_propagation_stopped.set(true);
+    }
+
+    /// Returns `true` if [`stop_immediate_propagation`](On::stop_immediate_propagation) was called for the current target.
+    pub fn is_immediate_propagation_stopped(&self) -> bool {
+        self.trigger_context.immediate_propagation_stopped.get()
+    }
 }
 
 impl<
@@ -197,4 +212,22 @@ pub struct TriggerContext {
     pub event_key: EventKey,
     /// The location of the source code that triggered the observer.
     pub caller: MaybeLocation,
+    /// Whether an observer stopped the remaining observers of the current target from running.
+    pub(crate) immediate_propagation_stopped: Cell<bool>,
+}
+
+impl TriggerContext {
+    /// Creates a new [`TriggerContext`] for the given [`EventKey`] and `caller`.
+    pub fn new(event_key: EventKey, caller: MaybeLocation) -> Self {
+        Self {
+            event_key,
+            caller,
+            immediate_propagation_stopped: Cell::new(false),
+        }
+    }
+
+    /// Returns `true` if an observer called [`On::stop_immediate_propagation`] for the current target.
+    pub fn is_immediate_propagation_stopped(&self) -> bool {
+        self.immediate_propagation_stopped.get()
+    }
 }
@@ -4,6 +4,7 @@ use crate::{
     component::{Component, ComponentId, Components, StorageType},
     entity::{Entities, Entity},
     query::{DebugCheckedUnwrap, FilteredAccess, StorageSwitch, WorldQuery},
+    relationship::{Relationship, RelationshipIndex},
     storage::{ComponentSparseSet, Table, TableRow},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
@@ -317,6 +318,115 @@ unsafe impl<T: Component> QueryFilter for Without<T> {
     }
 }
 
+/// Filter that selects entities with the `R` [`Relationship`], and enables looking them up by
+/// relationship target with [`Query::iter_related_to`](crate::system::Query::iter_related_to).
+///
+/// On its own this behaves like [`With<R>`]. It also declares read access to the
+/// [`RelationshipIndex<R>`] resource (creating it if needed), which the relationship hooks keep up to date.
+/// Lookups by target are therefore proportional to the number of matching entities rather than
+/// the number of entities with `R`.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::RelatedTo;
+/// # #[derive(Component)]
+/// # struct Name(&'static str);
+/// # #[derive(Resource)]
+/// # struct Selected(Entity);
+/// fn print_selected_children(
+///     selected: Res<Selected>,
+///     children: Query<&Name, RelatedTo<ChildOf>>,
+/// ) {
+///     for name in children.iter_related_to::<ChildOf>(selected.0) {
+///         println!("{} is a child of the selected entity", name.0);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_selected_children);
+/// ```
+pub struct RelatedTo<R>(PhantomData<R>);
+
+// SAFETY:
+// `update_component_access` adds a `With` filter for `R` and a resource read for `RelationshipIndex<R>`.
+// This is sound because [`QueryFilter::filter_fetch`] does not access any components,
+// and `matches_component_set` returns whether the set contains `R`.
+unsafe impl<R: Relationship> WorldQuery for RelatedTo<R> {
+    type Fetch<'w> = ();
+    type State = (ComponentId, ComponentId);
+
+    fn shrink_fetch<'wlong: 'wshort, 'wshort>(_: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {}
+
+    #[inline]
+    unsafe fn init_fetch(
+        _world: UnsafeWorldCell,
+        _state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) {
+    }
+
+    const IS_DENSE: bool = {
+        match R::STORAGE_TYPE {
+            StorageType::Table => true,
+            StorageType::SparseSet => false,
+        }
+    };
+
+    #[inline]
+    unsafe fn set_archetype(
+        _fetch: &mut (),
+        _state: &Self::State,
+        _archetype: &Archetype,
+        _table: &Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table(_fetch: &mut (), _state: &Self::State, _table: &Table) {}
+
+    #[inline]
+    fn update_component_access(&(relationship, index): &Self::State, access: &mut FilteredAccess) {
+        access.and_with(relationship);
+        access.add_resource_read(index);
+    }
+
+    fn init_state(world: &mut World) -> Self::State {
+        let relationship = world.register_component::<R>();
+        let index = world.init_resource::<RelationshipIndex<R>>();
+        (relationship, index)
+    }
+
+    fn get_state(components: &Components) -> Option<Self::State> {
+        Some((
+            components.component_id::<R>()?,
+            components.resource_id::<RelationshipIndex<R>>()?,
+        ))
+    }
+
+    fn matches_component_set(
+        &(relationship, _): &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        set_contains_id(relationship)
+    }
+}
+
+// SAFETY: WorldQuery impl performs no component access
+unsafe impl<R: Relationship> QueryFilter for RelatedTo<R> {
+    const IS_ARCHETYPAL: bool = true;
+
+    #[inline(always)]
+    unsafe fn filter_fetch(
+        _state: &Self::State,
+        _fetch: &mut Self::Fetch<'_>,
+        _entity: Entity,
+        _table_row: TableRow,
+    ) -> bool {
+        true
+    }
+}
+
 /// A filter that tests if any of the given filters apply.
 ///
 /// This is useful for example if a system with multiple components in a query only wants to run
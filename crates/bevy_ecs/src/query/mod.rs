@@ -14,6 +14,7 @@ mod filter;
 mod iter;
 mod par_iter;
 mod state;
+mod stats;
 mod world_query;
 
 pub use access::*;
@@ -26,6 +27,7 @@ pub use filter::*;
 pub use iter::*;
 pub use par_iter::*;
 pub use state::*;
+pub use stats::*;
 pub use world_query::*;
 
 /// A debug checked version of [`Option::unwrap_unchecked`]. Will panic in
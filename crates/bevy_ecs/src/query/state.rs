@@ -23,8 +23,9 @@ use log::warn;
 use tracing::Span;
 
 use super::{
-    NopWorldQuery, QueryBuilder, QueryData, QueryEntityError, QueryFilter, QueryManyIter,
-    QueryManyUniqueIter, QuerySingleError, ROQueryItem, ReadOnlyQueryData,
+    stats::ArchetypeUpdateStats, NopWorldQuery, QueryBuilder, QueryData, QueryEntityError,
+    QueryFilter, QueryIterationStrategy, QueryManyIter, QueryManyUniqueIter, QuerySingleError,
+    QueryStats, ROQueryItem, ReadOnlyQueryData,
 };
 
 /// An ID for either a table or an archetype. Used for Query iteration.
@@ -85,6 +86,7 @@ pub struct QueryState<D: QueryData, F: QueryFilter = ()> {
     pub(super) is_dense: bool,
     pub(crate) fetch_state: D::State,
     pub(crate) filter_state: F::State,
+    archetype_update_stats: ArchetypeUpdateStats,
     #[cfg(feature = "trace")]
     par_iter_span: Span,
 }
@@ -160,6 +162,24 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         self.matched_archetypes.ones().map(ArchetypeId::new)
     }
 
+    /// Returns how this query matches the archetypes and tables of its world,
+    /// and how much time was spent keeping that cache up to date.
+    ///
+    /// The matches reflect the last call to [`Self::update_archetypes`].
+    pub fn stats(&self) -> QueryStats {
+        QueryStats {
+            matched_archetypes: self.matched_archetypes.count_ones(..),
+            matched_tables: self.matched_tables.count_ones(..),
+            strategy: if self.is_dense {
+                QueryIterationStrategy::Dense
+            } else {
+                QueryIterationStrategy::Archetypal
+            },
+            archetype_updates: self.archetype_update_stats.count,
+            update_archetypes_time: self.archetype_update_stats.time,
+        }
+    }
+
     /// Creates a new [`QueryState`] from a given [`World`] and inherits the result of `world.id()`.
     pub fn new(world: &mut World) -> Self {
         let mut state = Self::new_uninitialized(world);
@@ -242,6 +262,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             component_access,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
+            archetype_update_stats: ArchetypeUpdateStats::default(),
             #[cfg(feature = "trace")]
             par_iter_span: tracing::info_span!(
                 "par_for_each",
@@ -284,6 +305,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             component_access,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
+            archetype_update_stats: ArchetypeUpdateStats::default(),
             #[cfg(feature = "trace")]
             par_iter_span: tracing::info_span!(
                 "par_for_each",
@@ -505,6 +527,13 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     /// If `world` does not match the one used to call `QueryState::new` for this instance.
     pub fn update_archetypes_unsafe_world_cell(&mut self, world: UnsafeWorldCell) {
         self.validate_world(world.id());
+        // skip if we are already up to date
+        if self.archetype_generation == world.archetypes().generation() {
+            return;
+        }
+        #[cfg(feature = "std")]
+        let start = bevy_platform::time::Instant::now();
+
         if self.component_access.required.is_empty() {
             let archetypes = world.archetypes();
             let old_generation =
@@ -518,10 +547,6 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
                 }
             }
         } else {
-            // skip if we are already up to date
-            if self.archetype_generation == world.archetypes().generation() {
-                return;
-            }
             // if there are required components, we can optimize by only iterating through archetypes
             // that contain at least one of the required components
             let potential_archetypes = self
@@ -555,6 +580,12 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             }
             self.archetype_generation = world.archetypes().generation();
         }
+
+        self.archetype_update_stats.count += 1;
+        #[cfg(feature = "std")]
+        {
+            self.archetype_update_stats.time += start.elapsed();
+        }
     }
 
     /// # Panics
@@ -706,6 +737,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             component_access: self_access,
             matched_tables: self.matched_tables.clone(),
             matched_archetypes: self.matched_archetypes.clone(),
+            archetype_update_stats: ArchetypeUpdateStats::default(),
             #[cfg(feature = "trace")]
             par_iter_span: tracing::info_span!(
                 "par_for_each",
@@ -853,6 +885,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             component_access: joined_component_access,
             matched_tables,
             matched_archetypes,
+            archetype_update_stats: ArchetypeUpdateStats::default(),
             #[cfg(feature = "trace")]
             par_iter_span: tracing::info_span!(
                 "par_for_each",
@@ -1800,6 +1833,7 @@ mod tests {
         component::Component,
         entity_disabling::DefaultQueryFilters,
         prelude::*,
+        query::QueryIterationStrategy,
         system::{QueryLens, RunSystemOnce},
         world::{EntityRef, FilteredEntityMut, FilteredEntityRef},
     };
@@ -2064,6 +2098,39 @@ mod tests {
         world.query::<(&A, &B)>().transmute::<&B>(&world2);
     }
 
+    #[test]
+    fn query_stats() {
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct Sparse;
+
+        let mut world = World::new();
+        world.spawn(A(0));
+        world.spawn((A(0), B(0)));
+        world.spawn((A(0), Sparse));
+
+        let mut query = world.query::<&A>();
+        let stats = query.stats();
+        assert_eq!(stats.matched_archetypes, 3);
+        assert_eq!(stats.matched_tables, 2);
+        assert_eq!(stats.strategy, QueryIterationStrategy::Dense);
+        assert_eq!(stats.archetype_updates, 1);
+
+        // Querying without structural changes doesn't update the cache.
+        query.iter(&world).count();
+        assert_eq!(query.stats().archetype_updates, 1);
+
+        world.spawn((A(0), C(0)));
+        query.iter(&world).count();
+        let stats = query.stats();
+        assert_eq!(stats.matched_archetypes, 4);
+        assert_eq!(stats.archetype_updates, 2);
+
+        let stats = world.query::<(&A, &Sparse)>().stats();
+        assert_eq!(stats.matched_archetypes, 1);
+        assert_eq!(stats.strategy, QueryIterationStrategy::Archetypal);
+    }
+
     /// Regression test for issue #14528
     #[test]
     fn transmute_from_sparse_to_dense() {
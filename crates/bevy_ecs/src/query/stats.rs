@@ -0,0 +1,45 @@
+use core::time::Duration;
+
+/// How a [`QueryState`](super::QueryState) iterates over the entities it matches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QueryIterationStrategy {
+    /// The query iterates over the [`Table`](crate::storage::Table)s it matches.
+    ///
+    /// This is used when every component accessed or filtered by the query is stored in tables,
+    /// and is the fastest way to iterate.
+    Dense,
+    /// The query iterates over the [`Archetype`](crate::archetype::Archetype)s it matches.
+    ///
+    /// This is used as soon as the query accesses or filters a component stored in a sparse set.
+    Archetypal,
+}
+
+/// A snapshot of how a [`QueryState`](super::QueryState) matches the archetypes and tables of its world,
+/// and of the cost of keeping that cache up to date.
+///
+/// Obtained with [`QueryState::stats`](super::QueryState::stats) or [`Query::stats`](crate::system::Query::stats).
+/// A query matching many archetypes or tables for few entities is a sign of fragmentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueryStats {
+    /// The number of archetypes matched by the query.
+    pub matched_archetypes: usize,
+    /// The number of tables matched by the query.
+    pub matched_tables: usize,
+    /// The iteration strategy used by the query.
+    pub strategy: QueryIterationStrategy,
+    /// The number of times the query processed new archetypes in
+    /// [`QueryState::update_archetypes`](super::QueryState::update_archetypes).
+    pub archetype_updates: u32,
+    /// The total time spent processing new archetypes in
+    /// [`QueryState::update_archetypes`](super::QueryState::update_archetypes).
+    ///
+    /// This is only measured with the `std` feature, and is zero otherwise.
+    pub update_archetypes_time: Duration,
+}
+
+/// Running totals of the archetype updates of a [`QueryState`](super::QueryState).
+#[derive(Clone, Copy, Default)]
+pub(super) struct ArchetypeUpdateStats {
+    pub(super) count: u32,
+    pub(super) time: Duration,
+}
@@ -0,0 +1,530 @@
+use alloc::{format, vec::Vec};
+
+use bevy_utils::prelude::DebugName;
+use log::warn;
+
+use crate::{
+    component::{Component, Mutable},
+    entity::{Entity, EntityHashSet},
+    error::CommandWithEntity,
+    lifecycle::HookContext,
+    relationship::{RelationshipHookMode, RelationshipSourceCollection},
+    world::{DeferredWorld, EntityWorldMut},
+};
+
+/// A [`Component`] on a "source" [`Entity`] that references a collection of target entities, creating a "many-to-many"
+/// relationship between them. Every [`ManyRelationship`] has a corresponding [`ManyRelationshipTarget`] type (and vice-versa),
+/// which exists on each "target" entity and contains the list of all "source" entities that relate to it.
+///
+/// This is the many-to-many counterpart of [`Relationship`](crate::relationship::Relationship): both sides store a
+/// [`RelationshipSourceCollection`]. The [`ManyRelationship`] component is the "source of truth", and the
+/// [`ManyRelationshipTarget`] components reflect it. When a [`ManyRelationship`] is inserted, the source entity is added
+/// to the [`ManyRelationshipTarget`] of every target in its collection. When a target is despawned (or its
+/// [`ManyRelationshipTarget`] removed), it is removed from the collection of every source that pointed to it, and the
+/// [`ManyRelationship`] component is removed from sources that no longer relate to anything.
+///
+/// [`ManyRelationship`] and [`ManyRelationshipTarget`] should always be derived via the [`Component`] trait, using the
+/// `many` flag of the `relationship` and `relationship_target` attributes, to ensure the hooks are set up properly.
+/// The same field rules as [`Relationship`](crate::relationship::Relationship) apply.
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// # use bevy_ecs::entity::Entity;
+/// #[derive(Component)]
+/// #[relationship(relationship_target = Members, many)]
+/// pub struct MemberOf(pub Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = MemberOf, many)]
+/// pub struct Members(Vec<Entity>);
+/// ```
+///
+/// Source entities can be spawned along with their target using [`SpawnManyRelated`](crate::spawn::SpawnManyRelated)
+/// or the [`many_related`](crate::many_related) macro, the counterparts of [`SpawnRelated`](crate::spawn::SpawnRelated)
+/// and [`related`](crate::related).
+///
+/// Like [`RelationshipTarget`](crate::relationship::RelationshipTarget), `#[relationship_target(many, linked_spawn)]`
+/// despawns every source entity when a target entity is despawned, even if those sources also relate to other targets.
+pub trait ManyRelationship: Component + Sized {
+    /// The [`Component`] added to the "target" entities of this [`ManyRelationship`], which contains the list of all
+    /// "source" entities that relate to the "target".
+    type RelationshipTarget: ManyRelationshipTarget<Relationship = Self>;
+
+    /// If `true`, the collection is allowed to contain its own entity.
+    ///
+    /// See [`Relationship::ALLOW_SELF_REFERENTIAL`](crate::relationship::Relationship::ALLOW_SELF_REFERENTIAL).
+    const ALLOW_SELF_REFERENTIAL: bool = false;
+
+    /// The collection type that stores the "target" entities of this [`ManyRelationship`].
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`ManyRelationship::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`ManyRelationship::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    /// If this method is used, then the hooks [`on_replace`](ManyRelationship::on_replace) have to run before and
+    /// [`on_insert`](ManyRelationship::on_insert) after it.
+    /// This happens automatically when this method is called with [`EntityWorldMut::modify_component`].
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates this [`ManyRelationship`] from the given [`ManyRelationship::Collection`].
+    ///
+    /// The collection should not contain duplicates.
+    fn from_collection(collection: Self::Collection) -> Self;
+
+    /// The `on_insert` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    fn on_insert(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            caller,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip => return,
+            RelationshipHookMode::RunIfNotLinked => {
+                if <Self::RelationshipTarget as ManyRelationshipTarget>::LINKED_SPAWN {
+                    return;
+                }
+            }
+        }
+        let targets: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        let mut seen = EntityHashSet::with_capacity(targets.len());
+        let mut invalid = Vec::new();
+        for target_entity in targets {
+            if !seen.insert(target_entity) {
+                warn!(
+                    "{}The {}({target_entity:?}) relationship on entity {entity:?} lists the same target more than once. The duplicate has been removed.",
+                    caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+                    DebugName::type_name::<Self>(),
+                );
+                invalid.push(target_entity);
+                continue;
+            }
+
+            if !Self::ALLOW_SELF_REFERENTIAL && target_entity == entity {
+                warn!(
+                    "{}The {}({target_entity:?}) relationship on entity {entity:?} points to itself. The invalid target has been removed.\nIf this is intended behavior self-referential relations can be enabled with the allow_self_referential attribute: #[relationship(allow_self_referential)]",
+                    caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+                    DebugName::type_name::<Self>(),
+                );
+                invalid.push(target_entity);
+                continue;
+            }
+
+            if let Ok(mut entity_commands) = world.commands().get_entity(target_entity) {
+                // Deferring is necessary for batch mode
+                entity_commands
+                    .entry::<Self::RelationshipTarget>()
+                    .and_modify(move |mut relationship_target| {
+                        let collection = relationship_target.collection_mut_risky();
+                        if !collection.iter().any(|source| source == entity) {
+                            collection.add(entity);
+                        }
+                    })
+                    .or_insert_with(move || {
+                        let mut target = Self::RelationshipTarget::with_capacity(1);
+                        target.collection_mut_risky().add(entity);
+                        target
+                    });
+            } else {
+                warn!(
+                    "{}The {}({target_entity:?}) relationship on entity {entity:?} relates to an entity that does not exist. The invalid target has been removed.",
+                    caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+                    DebugName::type_name::<Self>(),
+                );
+                invalid.push(target_entity);
+            }
+        }
+
+        if !invalid.is_empty() {
+            let command = move |mut entity: EntityWorldMut| {
+                remove_many_relationship_targets::<Self>(&mut entity, &invalid);
+            };
+            world.commands().queue_silenced(command.with_entity(entity));
+        }
+    }
+
+    /// The `on_replace` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_replace(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip => return,
+            RelationshipHookMode::RunIfNotLinked => {
+                if <Self::RelationshipTarget as ManyRelationshipTarget>::LINKED_SPAWN {
+                    return;
+                }
+            }
+        }
+        let targets: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        for target_entity in targets {
+            if let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity)
+                && let Some(mut relationship_target) =
+                    target_entity_mut.get_mut::<Self::RelationshipTarget>()
+            {
+                relationship_target.collection_mut_risky().remove(entity);
+                if relationship_target.is_empty() {
+                    let command = |mut entity: EntityWorldMut| {
+                        // Only remove if still empty, as an identical relationship may have been inserted on top.
+                        if entity
+                            .get::<Self::RelationshipTarget>()
+                            .is_some_and(ManyRelationshipTarget::is_empty)
+                        {
+                            entity.remove::<Self::RelationshipTarget>();
+                        }
+                    };
+
+                    world
+                        .commands()
+                        .queue_silenced(command.with_entity(target_entity));
+                }
+            }
+        }
+    }
+
+    /// Iterates the target entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of target entities in this collection.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this collection has no targets.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+}
+
+/// The iterator type for the target entities in a [`ManyRelationship`] collection,
+/// as defined in the [`RelationshipSourceCollection`] trait.
+pub type ManyTargetIter<'w, R> =
+    <<R as ManyRelationship>::Collection as RelationshipSourceCollection>::SourceIter<'w>;
+
+/// The iterator type for the source entities in a [`ManyRelationshipTarget`] collection,
+/// as defined in the [`RelationshipSourceCollection`] trait.
+pub type ManySourceIter<'w, R> =
+    <<R as ManyRelationshipTarget>::Collection as RelationshipSourceCollection>::SourceIter<'w>;
+
+/// A [`Component`] containing the collection of entities that relate to this [`Entity`] via the associated
+/// [`ManyRelationship`] type. See the [`ManyRelationship`] documentation for more information.
+pub trait ManyRelationshipTarget: Component<Mutability = Mutable> + Sized {
+    /// If this is true, when despawning an entity, every source entity relating to it will also be despawned.
+    ///
+    /// This defaults to false when derived.
+    const LINKED_SPAWN: bool;
+    /// The [`ManyRelationship`] that populates this [`ManyRelationshipTarget`] collection.
+    type Relationship: ManyRelationship<RelationshipTarget = Self>;
+    /// The collection type that stores the "source" entities for this [`ManyRelationshipTarget`] component.
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`ManyRelationshipTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+    /// Returns a mutable reference to the stored [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyRelationshipTarget`] from the given [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as constructing the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// The `on_replace` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_replace(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip | RelationshipHookMode::RunIfNotLinked => return,
+        }
+        let (entities, mut commands) = world.entities_and_commands();
+        let relationship_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source_entity in relationship_target.iter() {
+            let command = move |mut source: EntityWorldMut| {
+                remove_many_relationship_targets::<Self::Relationship>(&mut source, &[entity]);
+            };
+            commands.queue_silenced(command.with_entity(source_entity));
+        }
+    }
+
+    /// The `on_despawn` component hook that despawns entities stored in an entity's [`ManyRelationshipTarget`] when
+    /// that entity is despawned.
+    // note: think of this as "on_drop"
+    fn on_despawn(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let (entities, mut commands) = world.entities_and_commands();
+        let relationship_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source_entity in relationship_target.iter() {
+            commands.entity(source_entity).try_despawn();
+        }
+    }
+
+    /// Creates this [`ManyRelationshipTarget`] with the given pre-allocated entity capacity.
+    fn with_capacity(capacity: usize) -> Self {
+        let collection =
+            <Self::Collection as RelationshipSourceCollection>::with_capacity(capacity);
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> ManySourceIter<'_, Self> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of entities in this collection.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this entity collection is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+}
+
+/// Removes `targets` from the `R` collection of `entity` without running the relationship hooks,
+/// removing `R` entirely if no targets remain.
+pub(crate) fn remove_many_relationship_targets<R: ManyRelationship>(
+    entity: &mut EntityWorldMut,
+    targets: &[Entity],
+) {
+    let id = entity.id();
+    let is_empty = entity.world_scope(|world| {
+        let is_empty = DeferredWorld::from(&mut *world)
+            .modify_component_with_relationship_hook_mode::<R, _>(
+                id,
+                RelationshipHookMode::Skip,
+                |relationship| {
+                    let collection = relationship.collection_mut_risky();
+                    for target in targets {
+                        collection.remove(*target);
+                    }
+                    collection.is_empty()
+                },
+            )
+            .ok()
+            .flatten();
+        world.flush();
+        is_empty
+    });
+    if is_empty == Some(true) {
+        entity.remove::<R>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::Component, entity::Entity, relationship::ManyRelationshipTarget, system::Query,
+        world::World,
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Members, many)]
+    struct MemberOf(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = MemberOf, many)]
+    struct Members(Vec<Entity>);
+
+    fn members(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<Members>(entity)
+            .map(|members| members.iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn many_relationship_links_all_targets() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(MemberOf(vec![a, b])).id();
+        let y = world.spawn(MemberOf(vec![b])).id();
+
+        assert_eq!(members(&world, a), vec![x]);
+        assert_eq!(members(&world, b), vec![x, y]);
+
+        world.entity_mut(x).insert(MemberOf(vec![a]));
+        assert_eq!(members(&world, a), vec![x]);
+        assert_eq!(members(&world, b), vec![y]);
+
+        world.entity_mut(y).remove::<MemberOf>();
+        assert!(!world.entity(b).contains::<Members>());
+    }
+
+    #[test]
+    fn despawning_target_removes_it_from_sources() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(MemberOf(vec![a, b])).id();
+        let y = world.spawn(MemberOf(vec![a])).id();
+
+        world.despawn(a);
+        assert_eq!(world.get::<MemberOf>(x).unwrap().0, vec![b]);
+        assert!(!world.entity(y).contains::<MemberOf>());
+        assert_eq!(members(&world, b), vec![x]);
+    }
+
+    #[test]
+    fn invalid_many_relationship_targets_are_removed() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let dead = world.spawn_empty().id();
+        world.despawn(dead);
+        let x = world.spawn_empty().id();
+        world.entity_mut(x).insert(MemberOf(vec![a, x, dead]));
+
+        assert_eq!(world.get::<MemberOf>(x).unwrap().0, vec![a]);
+        assert_eq!(members(&world, a), vec![x]);
+        assert!(!world.entity(x).contains::<Members>());
+    }
+
+    #[test]
+    fn duplicate_many_relationship_targets_are_removed() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(MemberOf(vec![a, b, a, a])).id();
+
+        assert_eq!(world.get::<MemberOf>(x).unwrap().0, vec![b, a]);
+        assert_eq!(members(&world, a), vec![x]);
+        assert_eq!(members(&world, b), vec![x]);
+
+        world.entity_mut(x).add_many_targets::<MemberOf>(&[b, b]);
+        world.entity_mut(a).add_many_related::<MemberOf>(&[x, x]);
+        assert_eq!(world.get::<MemberOf>(x).unwrap().0, vec![b, a]);
+        assert_eq!(members(&world, a), vec![x]);
+        assert_eq!(members(&world, b), vec![x]);
+    }
+
+    #[test]
+    fn many_related_methods() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn_empty().id();
+        let y = world.spawn_empty().id();
+
+        world.entity_mut(a).add_many_related::<MemberOf>(&[x, y]);
+        world.entity_mut(x).add_many_targets::<MemberOf>(&[a, b]);
+        assert_eq!(world.get::<MemberOf>(x).unwrap().0, vec![a, b]);
+        assert_eq!(members(&world, a), vec![y, x]);
+        assert_eq!(members(&world, b), vec![x]);
+
+        world.entity_mut(a).remove_many_related::<MemberOf>(&[y]);
+        assert!(!world.entity(y).contains::<MemberOf>());
+        world.entity_mut(x).remove_many_targets::<MemberOf>(&[b]);
+        assert!(!world.entity(b).contains::<Members>());
+
+        let z = world
+            .entity_mut(b)
+            .with_many_related::<MemberOf>(())
+            .get::<Members>()
+            .unwrap()
+            .iter()
+            .next()
+            .unwrap();
+        assert_eq!(world.get::<MemberOf>(z).unwrap().0, vec![b]);
+
+        world.entity_mut(a).detach_all_many_related::<MemberOf>();
+        assert!(!world.entity(x).contains::<MemberOf>());
+    }
+
+    #[test]
+    fn many_relationship_spawn_helpers() {
+        use crate::spawn::{Spawn, SpawnManyRelated, WithOneRelated};
+
+        let mut world = World::new();
+        let x = world.spawn_empty().id();
+        let a = world
+            .spawn(Members::spawn_many((Spawn(()), WithOneRelated(x))))
+            .id();
+        let b = world.spawn(crate::many_related!(Members[(), ()])).id();
+        world.entity_mut(x).add_many_targets::<MemberOf>(&[b]);
+
+        let spawned = members(&world, a)[0];
+        assert_ne!(spawned, x);
+        assert_eq!(world.get::<MemberOf>(spawned).unwrap().0, vec![a]);
+        assert_eq!(world.get::<MemberOf>(x).unwrap().0, vec![a, b]);
+        assert_eq!(members(&world, a), vec![spawned, x]);
+        assert_eq!(members(&world, b).len(), 3);
+    }
+
+    #[test]
+    fn many_relationship_query_traversal() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(MemberOf(vec![a])).id();
+        let c = world.spawn(MemberOf(vec![a, b])).id();
+        world.entity_mut(a).insert(MemberOf(vec![c]));
+
+        let mut sources = world.query::<&Members>();
+        let sources = sources.query(&world);
+        let descendants: Vec<_> = sources.iter_many_descendants::<Members>(a).collect();
+        assert_eq!(descendants, vec![b, c]);
+
+        let mut targets = world.query::<&MemberOf>();
+        let targets: Query<&MemberOf> = targets.query(&world);
+        assert_eq!(
+            targets.many_related::<MemberOf>(c).collect::<Vec<_>>(),
+            vec![a, b]
+        );
+        let ancestors: Vec<_> = targets.iter_many_ancestors::<MemberOf>(b).collect();
+        assert_eq!(ancestors, vec![a, c]);
+    }
+
+    #[test]
+    fn many_relationship_linked_spawn() {
+        #[derive(Component)]
+        #[relationship(relationship_target = Owners, many)]
+        struct OwnedBy(Vec<Entity>);
+
+        #[derive(Component)]
+        #[relationship_target(relationship = OwnedBy, many, linked_spawn)]
+        struct Owners(Vec<Entity>);
+
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(OwnedBy(vec![a, b])).id();
+
+        world.despawn(a);
+        assert!(world.get_entity(x).is_err());
+        assert!(!world.entity(b).contains::<Owners>());
+    }
+}
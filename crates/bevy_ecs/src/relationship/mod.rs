@@ -1,6 +1,8 @@
 //! This module provides functionality to link entities to each other using specialized components called "relationships". See the [`Relationship`] trait for more info.
 
+mod many_to_many;
 mod related_methods;
+mod relationship_index;
 mod relationship_query;
 mod relationship_source_collection;
 
@@ -11,7 +13,9 @@ use core::marker::PhantomData;
 use alloc::format;
 
 use bevy_utils::prelude::DebugName;
+pub use many_to_many::*;
 pub use related_methods::*;
+pub use relationship_index::*;
 pub use relationship_query::*;
 pub use relationship_source_collection::*;
 
@@ -138,6 +142,11 @@ pub trait Relationship: Component + Sized {
             ..
         }: HookContext,
     ) {
+        let target_entity = world.entity(entity).get::<Self>().unwrap().get();
+        // The index mirrors the component values, so it is maintained regardless of the hook mode.
+        if let Some(mut index) = world.get_resource_mut::<RelationshipIndex<Self>>() {
+            index.add(target_entity, entity);
+        }
         match relationship_hook_mode {
             RelationshipHookMode::Run => {}
             RelationshipHookMode::Skip => return,
@@ -147,7 +156,6 @@ pub trait Relationship: Component + Sized {
                 }
             }
         }
-        let target_entity = world.entity(entity).get::<Self>().unwrap().get();
         if !Self::ALLOW_SELF_REFERENTIAL && target_entity == entity {
             warn!(
                 "{}The {}({target_entity:?}) relationship on entity {entity:?} points to itself. The invalid {} relationship has been removed.\nIf this is intended behavior self-referential relations can be enabled with the allow_self_referential attribute: #[relationship(allow_self_referential)]",
@@ -206,6 +214,10 @@ pub trait Relationship: Component + Sized {
             ..
         }: HookContext,
     ) {
+        let target_entity = world.entity(entity).get::<Self>().unwrap().get();
+        if let Some(mut index) = world.get_resource_mut::<RelationshipIndex<Self>>() {
+            index.remove(target_entity, entity);
+        }
         match relationship_hook_mode {
             RelationshipHookMode::Run => {}
             RelationshipHookMode::Skip => return,
@@ -215,7 +227,6 @@ pub trait Relationship: Component + Sized {
                 }
             }
         }
-        let target_entity = world.entity(entity).get::<Self>().unwrap().get();
         if let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity)
             && let Some(mut relationship_target) =
                 target_entity_mut.get_mut::<Self::RelationshipTarget>()
@@ -3,7 +3,8 @@ use crate::{
     entity::{hash_set::EntityHashSet, Entity},
     prelude::Children,
     relationship::{
-        Relationship, RelationshipHookMode, RelationshipSourceCollection, RelationshipTarget,
+        ManyRelationship, Relationship, RelationshipHookMode, RelationshipSourceCollection,
+        RelationshipTarget,
     },
     system::{Commands, EntityCommands},
     world::{DeferredWorld, EntityWorldMut, World},
@@ -372,6 +373,107 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Spawns an entity related to this entity (with the `R` many-to-many relationship) by taking a bundle.
+    pub fn with_many_related<R: ManyRelationship>(&mut self, bundle: impl Bundle) -> &mut Self {
+        let target = self.id();
+        self.world_scope(|world| {
+            let mut collection = R::Collection::with_capacity(1);
+            collection.add(target);
+            world.spawn((bundle, R::from_collection(collection)));
+        });
+        self
+    }
+
+    /// Adds this entity to the `R` [`ManyRelationship`] of each of the given `related` entities.
+    pub fn add_many_related<R: ManyRelationship>(&mut self, related: &[Entity]) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for related in related {
+                world
+                    .entity_mut(*related)
+                    .modify_or_insert_many_relation::<R>(|collection| {
+                        if !collection.iter().any(|target| target == id) {
+                            collection.add(id);
+                        }
+                    });
+            }
+        });
+        self
+    }
+
+    /// Removes this entity from the `R` [`ManyRelationship`] of each of the given `related` entities.
+    pub fn remove_many_related<R: ManyRelationship>(&mut self, related: &[Entity]) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for related in related {
+                if let Ok(mut related) = world.get_entity_mut(*related)
+                    && related
+                        .get::<R>()
+                        .is_some_and(|relationship| relationship.iter().any(|target| target == id))
+                {
+                    related.modify_or_insert_many_relation::<R>(|collection| {
+                        collection.remove(id);
+                    });
+                }
+            }
+        });
+        self
+    }
+
+    /// Removes the many-to-many relation `R` between this entity and all of its related source entities.
+    pub fn detach_all_many_related<R: ManyRelationship>(&mut self) -> &mut Self {
+        self.remove::<R::RelationshipTarget>()
+    }
+
+    /// Adds the given `targets` to this entity's `R` [`ManyRelationship`], inserting it if needed.
+    pub fn add_many_targets<R: ManyRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        self.modify_or_insert_many_relation::<R>(|collection| {
+            for target in targets {
+                if !collection.iter().any(|existing| existing == *target) {
+                    collection.add(*target);
+                }
+            }
+        });
+        self
+    }
+
+    /// Removes the given `targets` from this entity's `R` [`ManyRelationship`].
+    ///
+    /// The `R` component is removed once it no longer has any targets.
+    pub fn remove_many_targets<R: ManyRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        if self.contains::<R>() {
+            self.modify_or_insert_many_relation::<R>(|collection| {
+                for target in targets {
+                    collection.remove(*target);
+                }
+            });
+        }
+        self
+    }
+
+    fn modify_or_insert_many_relation<R: ManyRelationship>(
+        &mut self,
+        f: impl FnOnce(&mut R::Collection),
+    ) {
+        if !self.contains::<R>() {
+            let mut collection = R::Collection::new();
+            f(&mut collection);
+            if !collection.is_empty() {
+                self.insert(R::from_collection(collection));
+            }
+            return;
+        }
+
+        let is_empty = self.modify_component::<R, _>(|relationship| {
+            let collection = relationship.collection_mut_risky();
+            f(collection);
+            collection.is_empty()
+        });
+        if is_empty == Some(true) {
+            self.remove::<R>();
+        }
+    }
+
     fn modify_or_insert_relation_with_relationship_hook_mode<R: Relationship>(
         &mut self,
         entity: Entity,
@@ -558,6 +660,60 @@ impl<'a> EntityCommands<'a> {
             entity.remove_recursive::<S, B>();
         })
     }
+
+    /// Spawns an entity related to this entity (with the `R` many-to-many relationship) by taking a bundle.
+    pub fn with_many_related<R: ManyRelationship>(&mut self, bundle: impl Bundle) -> &mut Self {
+        let mut collection = R::Collection::with_capacity(1);
+        collection.add(self.id());
+        self.commands
+            .spawn((bundle, R::from_collection(collection)));
+        self
+    }
+
+    /// Adds this entity to the `R` [`ManyRelationship`] of each of the given `related` entities.
+    pub fn add_many_related<R: ManyRelationship>(&mut self, related: &[Entity]) -> &mut Self {
+        let related: Box<[Entity]> = related.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_many_related::<R>(&related);
+        })
+    }
+
+    /// Removes this entity from the `R` [`ManyRelationship`] of each of the given `related` entities.
+    pub fn remove_many_related<R: ManyRelationship>(&mut self, related: &[Entity]) -> &mut Self {
+        let related: Box<[Entity]> = related.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_many_related::<R>(&related);
+        })
+    }
+
+    /// Removes the many-to-many relation `R` between this entity and all of its related source entities.
+    pub fn detach_all_many_related<R: ManyRelationship>(&mut self) -> &mut Self {
+        self.queue(|mut entity: EntityWorldMut| {
+            entity.detach_all_many_related::<R>();
+        })
+    }
+
+    /// Adds the given `targets` to this entity's `R` [`ManyRelationship`], inserting it if needed.
+    pub fn add_many_targets<R: ManyRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_many_targets::<R>(&targets);
+        })
+    }
+
+    /// Removes the given `targets` from this entity's `R` [`ManyRelationship`].
+    ///
+    /// The `R` component is removed once it no longer has any targets.
+    pub fn remove_many_targets<R: ManyRelationship>(&mut self, targets: &[Entity]) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_many_targets::<R>(&targets);
+        })
+    }
 }
 
 /// Directly spawns related "source" entities with the given [`Relationship`], targeting
@@ -0,0 +1,133 @@
+use core::marker::PhantomData;
+
+use crate::{
+    entity::{Entity, EntityHashMap, EntityIndexSet},
+    relationship::Relationship,
+    resource::Resource,
+    world::{FromWorld, World},
+};
+
+/// A [`Resource`] that indexes the sources of the `R` [`Relationship`] by their target entity.
+///
+/// The index is created on demand by the [`RelatedTo`](crate::query::RelatedTo) query filter and is kept
+/// up to date by the `on_insert` and `on_replace` hooks of `R`, so looking up every source of a given target
+/// only touches the matching entities. See [`Query::iter_related_to`](crate::system::Query::iter_related_to).
+///
+/// Unlike the [`RelationshipTarget`](crate::relationship::RelationshipTarget) component, this is stored
+/// outside of the target entity, so it can be read alongside any component access.
+pub struct RelationshipIndex<R: Relationship> {
+    sources: EntityHashMap<EntityIndexSet>,
+    empty: EntityIndexSet,
+    _marker: PhantomData<R>,
+}
+
+impl<R: Relationship> Resource for RelationshipIndex<R> {}
+
+impl<R: Relationship> FromWorld for RelationshipIndex<R> {
+    fn from_world(world: &mut World) -> Self {
+        let mut index = Self {
+            sources: EntityHashMap::default(),
+            empty: EntityIndexSet::new(),
+            _marker: PhantomData,
+        };
+        let mut query = world.query::<(Entity, &R)>();
+        for (source, relationship) in query.iter(world) {
+            index.add(relationship.get(), source);
+        }
+        index
+    }
+}
+
+impl<R: Relationship> RelationshipIndex<R> {
+    /// Returns the entities whose `R` relationship targets `target`.
+    pub fn sources(&self, target: Entity) -> &EntityIndexSet {
+        self.sources.get(&target).unwrap_or(&self.empty)
+    }
+
+    /// Iterates every entity that is currently the target of at least one `R` relationship.
+    pub fn targets(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.sources.keys().copied()
+    }
+
+    pub(crate) fn add(&mut self, target: Entity, source: Entity) {
+        self.sources.entry(target).or_default().insert(source);
+    }
+
+    pub(crate) fn remove(&mut self, target: Entity, source: Entity) {
+        if let Some(sources) = self.sources.get_mut(&target) {
+            sources.swap_remove(&source);
+            if sources.is_empty() {
+                self.sources.remove(&target);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::Component, hierarchy::ChildOf, query::RelatedTo,
+        relationship::RelationshipIndex, world::World,
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Value(u32);
+
+    #[test]
+    fn related_to_lookup() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        world.spawn((Value(0), ChildOf(a)));
+        world.spawn((Value(1), ChildOf(b)));
+
+        let mut query = world.query_filtered::<&Value, RelatedTo<ChildOf>>();
+        world.spawn((Value(2), ChildOf(a)));
+        world.spawn(ChildOf(a));
+
+        let query = query.query(&world);
+        let values: Vec<_> = query.iter_related_to::<ChildOf>(a).collect();
+        assert_eq!(values, vec![&Value(0), &Value(2)]);
+    }
+
+    #[test]
+    fn index_follows_relationship_changes() {
+        let mut world = World::new();
+        world.init_resource::<RelationshipIndex<ChildOf>>();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn(ChildOf(a)).id();
+        let d = world.spawn(ChildOf(a)).id();
+
+        world.entity_mut(c).insert(ChildOf(b));
+        world.despawn(d);
+        let index = world.resource::<RelationshipIndex<ChildOf>>();
+        assert!(index.sources(a).is_empty());
+        assert_eq!(
+            index.sources(b).iter().copied().collect::<Vec<_>>(),
+            vec![c]
+        );
+        assert_eq!(index.targets().collect::<Vec<_>>(), vec![b]);
+
+        world.despawn(b);
+        assert_eq!(
+            world
+                .resource::<RelationshipIndex<ChildOf>>()
+                .targets()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn related_to_requires_filter() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        world.spawn((Value(0), ChildOf(a)));
+
+        let mut query = world.query::<&Value>();
+        let _ = query.query(&world).iter_related_to::<ChildOf>(a).count();
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
-    entity::Entity,
+    entity::{Entity, EntityHashSet},
     query::{QueryData, QueryFilter},
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{ManyRelationship, ManyRelationshipTarget, Relationship, RelationshipTarget},
     system::Query,
 };
 use alloc::collections::VecDeque;
@@ -141,6 +141,62 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     {
         AncestorIter::new(self, entity)
     }
+
+    /// If the given `entity` contains the `R` [`ManyRelationship`] component, returns the
+    /// target entities of that relationship.
+    pub fn many_related<R: ManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyRelationship::iter)
+    }
+
+    /// If the given `entity` contains the `S` [`ManyRelationshipTarget`] component, returns the
+    /// source entities stored on that component.
+    pub fn many_relationship_sources<S: ManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyRelationshipTarget::iter)
+    }
+
+    /// Iterates all entities that transitively relate to the given `entity` through the `S`
+    /// [`ManyRelationshipTarget`], in breadth-first order.
+    ///
+    /// Each entity is yielded at most once, so graphs containing loops are supported.
+    pub fn iter_many_descendants<S: ManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> ManyDescendantIter<'w, 's, D, F, S>
+    where
+        D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+    {
+        ManyDescendantIter::new(self, entity)
+    }
+
+    /// Iterates all entities the given `entity` transitively relates to through the `R`
+    /// [`ManyRelationship`], in breadth-first order.
+    ///
+    /// Each entity is yielded at most once, so graphs containing loops are supported.
+    pub fn iter_many_ancestors<R: ManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> ManyAncestorIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        ManyAncestorIter::new(self, entity)
+    }
 }
 
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
@@ -270,3 +326,112 @@ where
         self.next
     }
 }
+
+/// An [`Iterator`] of [`Entity`]s over the sources that transitively relate to an [`Entity`]
+/// through a [`ManyRelationshipTarget`].
+///
+/// Traverses the graph breadth-first, visiting each entity once.
+pub struct ManyDescendantIter<'w, 's, D: QueryData, F: QueryFilter, S: ManyRelationshipTarget>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+{
+    sources_query: &'w Query<'w, 's, D, F>,
+    vecdeque: VecDeque<Entity>,
+    visited: EntityHashSet,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, S: ManyRelationshipTarget>
+    ManyDescendantIter<'w, 's, D, F, S>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+{
+    /// Returns a new [`ManyDescendantIter`].
+    pub fn new(sources_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        let mut iter = ManyDescendantIter {
+            sources_query,
+            vecdeque: VecDeque::new(),
+            visited: EntityHashSet::default(),
+        };
+        iter.visited.insert(entity);
+        iter.visit(entity);
+        iter
+    }
+
+    fn visit(&mut self, entity: Entity) {
+        if let Ok(sources) = self.sources_query.get(entity) {
+            for source in sources.iter() {
+                if self.visited.insert(source) {
+                    self.vecdeque.push_back(source);
+                }
+            }
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, S: ManyRelationshipTarget> Iterator
+    for ManyDescendantIter<'w, 's, D, F, S>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w S>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+        self.visit(entity);
+        Some(entity)
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s over the targets an [`Entity`] transitively relates to
+/// through a [`ManyRelationship`].
+///
+/// Traverses the graph breadth-first, visiting each entity once.
+pub struct ManyAncestorIter<'w, 's, D: QueryData, F: QueryFilter, R: ManyRelationship>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    targets_query: &'w Query<'w, 's, D, F>,
+    vecdeque: VecDeque<Entity>,
+    visited: EntityHashSet,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: ManyRelationship> ManyAncestorIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    /// Returns a new [`ManyAncestorIter`].
+    pub fn new(targets_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        let mut iter = ManyAncestorIter {
+            targets_query,
+            vecdeque: VecDeque::new(),
+            visited: EntityHashSet::default(),
+        };
+        iter.visited.insert(entity);
+        iter.visit(entity);
+        iter
+    }
+
+    fn visit(&mut self, entity: Entity) {
+        if let Ok(targets) = self.targets_query.get(entity) {
+            for target in targets.iter() {
+                if self.visited.insert(target) {
+                    self.vecdeque.push_back(target);
+                }
+            }
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: ManyRelationship> Iterator
+    for ManyAncestorIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+        self.visit(entity);
+        Some(entity)
+    }
+}
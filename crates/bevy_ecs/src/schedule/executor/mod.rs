@@ -1,6 +1,8 @@
 #[cfg(feature = "std")]
 mod multi_threaded;
 mod single_threaded;
+#[cfg(feature = "std")]
+mod system_timings;
 
 use alloc::{vec, vec::Vec};
 use bevy_utils::prelude::DebugName;
@@ -10,6 +12,8 @@ pub use self::single_threaded::SingleThreadedExecutor;
 
 #[cfg(feature = "std")]
 pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
+#[cfg(feature = "std")]
+pub use self::system_timings::{SystemTiming, SystemTimings};
 
 use fixedbitset::FixedBitSet;
 
@@ -19,8 +23,8 @@ use crate::{
     prelude::{IntoSystemSet, SystemSet},
     query::FilteredAccessSet,
     schedule::{
-        ConditionWithAccess, InternedSystemSet, SystemKey, SystemSetKey, SystemTypeSet,
-        SystemWithAccess,
+        ConditionWithAccess, InternedScheduleLabel, InternedSystemSet, SystemKey, SystemSetKey,
+        SystemTypeSet, SystemWithAccess,
     },
     system::{RunSystemError, System, SystemIn, SystemParamValidationError, SystemStateFlags},
     world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
@@ -64,6 +68,14 @@ pub enum ExecutorKind {
     #[cfg(feature = "std")]
     #[cfg_attr(all(not(target_arch = "wasm32"), feature = "multi_threaded"), default)]
     MultiThreaded,
+    /// Like [`MultiThreaded`](ExecutorKind::MultiThreaded), but measures how long each system runs
+    /// and uses this history to start the systems on the longest remaining chain of dependencies first.
+    ///
+    /// Useful for schedules with a few expensive systems among many cheap ones, where starting the
+    /// expensive chains late leaves threads idle at the end of the schedule.
+    /// The measured run times are published to the [`SystemTimings`] resource if it exists.
+    #[cfg(feature = "std")]
+    CriticalPath,
 }
 
 /// Holds systems and conditions of a [`Schedule`](super::Schedule) sorted in topological order
@@ -88,10 +100,6 @@ pub struct SystemSchedule {
     pub(super) system_dependencies: Vec<usize>,
     /// Indexed by system node id.
     /// List of systems that immediately depend on the system.
-    #[cfg_attr(
-        not(feature = "std"),
-        expect(dead_code, reason = "currently only used with the std feature")
-    )]
     pub(super) system_dependents: Vec<Vec<usize>>,
     /// Indexed by system node id.
     /// List of sets containing the system that have conditions
@@ -105,6 +113,8 @@ pub struct SystemSchedule {
     ///
     /// If a set doesn't run because of its conditions, this is used to skip all systems in it.
     pub(super) systems_in_sets_with_conditions: Vec<FixedBitSet>,
+    /// The label of the [`Schedule`](super::Schedule) this was built for, if it has been built.
+    pub(super) label: Option<InternedScheduleLabel>,
 }
 
 impl SystemSchedule {
@@ -120,6 +130,7 @@ impl SystemSchedule {
             system_dependents: Vec::new(),
             sets_with_conditions_of_systems: Vec::new(),
             systems_in_sets_with_conditions: Vec::new(),
+            label: None,
         }
     }
 }
@@ -326,8 +337,11 @@ mod tests {
     #[derive(Component)]
     struct TestComponent;
 
-    const EXECUTORS: [ExecutorKind; 2] =
-        [ExecutorKind::SingleThreaded, ExecutorKind::MultiThreaded];
+    const EXECUTORS: [ExecutorKind; 3] = [
+        ExecutorKind::SingleThreaded,
+        ExecutorKind::MultiThreaded,
+        ExecutorKind::CriticalPath,
+    ];
 
     #[derive(Resource, Default)]
     struct TestState {
@@ -1,9 +1,10 @@
 use alloc::{boxed::Box, vec::Vec};
 use bevy_platform::cell::SyncUnsafeCell;
 use bevy_platform::sync::Arc;
+use bevy_platform::time::Instant;
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
 use concurrent_queue::ConcurrentQueue;
-use core::{any::Any, panic::AssertUnwindSafe};
+use core::{any::Any, cmp::Reverse, panic::AssertUnwindSafe, time::Duration};
 use fixedbitset::FixedBitSet;
 #[cfg(feature = "std")]
 use std::eprintln;
@@ -16,8 +17,8 @@ use crate::{
     error::{ErrorContext, ErrorHandler, Result},
     prelude::Resource,
     schedule::{
-        is_apply_deferred, ConditionWithAccess, ExecutorKind, SystemExecutor, SystemSchedule,
-        SystemWithAccess,
+        is_apply_deferred, ConditionWithAccess, ExecutorKind, InternedScheduleLabel,
+        SystemExecutor, SystemKey, SystemSchedule, SystemTimings, SystemWithAccess,
     },
     system::{RunSystemError, ScheduleSystem},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
@@ -25,7 +26,7 @@ use crate::{
 #[cfg(feature = "hotpatching")]
 use crate::{prelude::DetectChanges, HotPatchChanges};
 
-use super::__rust_begin_short_backtrace;
+use super::{__rust_begin_short_backtrace, system_timings::SystemCost};
 
 /// Borrowed data used by the [`MultiThreadedExecutor`].
 struct Environment<'env, 'sys> {
@@ -83,9 +84,14 @@ struct SystemTaskMetadata {
 /// The result of running a system that is sent across a channel.
 struct SystemResult {
     system_index: usize,
+    /// How long the system took to run, if the executor measures it.
+    elapsed: Option<Duration>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
+///
+/// When created with [`MultiThreadedExecutor::critical_path`], the executor also measures how long
+/// each system runs and starts the ready systems on the longest remaining chain of dependencies first.
 pub struct MultiThreadedExecutor {
     /// The running state, protected by a mutex so that a reference to the executor can be shared across tasks.
     state: Mutex<ExecutorState>,
@@ -96,6 +102,8 @@ pub struct MultiThreadedExecutor {
     /// When set, tells the executor that a thread has panicked.
     panic_payload: Mutex<Option<Box<dyn Any + Send>>>,
     starting_systems: FixedBitSet,
+    /// Whether system run times are measured and used to order ready systems.
+    critical_path: bool,
     /// Cached tracing span
     #[cfg(feature = "trace")]
     executor_span: Span,
@@ -129,6 +137,17 @@ pub struct ExecutorState {
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
+    /// Ready systems in the order they should be started.
+    ready_order: Vec<usize>,
+    /// Indexed by system index. The run-time history of each system, if it is measured.
+    system_costs: Vec<SystemCost>,
+    /// Indexed by system index. The estimated time needed to run the system and all of its dependents,
+    /// used to start the systems on the longest chain of dependencies first.
+    critical_path_costs: Vec<Duration>,
+    /// Indexed by system index. The key of each system, used to publish its run time.
+    system_keys: Vec<SystemKey>,
+    /// The label of the schedule being run, used to publish the run time of its systems.
+    schedule_label: Option<InternedScheduleLabel>,
 }
 
 /// References to data required by the executor.
@@ -150,7 +169,11 @@ impl Default for MultiThreadedExecutor {
 
 impl SystemExecutor for MultiThreadedExecutor {
     fn kind(&self) -> ExecutorKind {
-        ExecutorKind::MultiThreaded
+        if self.critical_path {
+            ExecutorKind::CriticalPath
+        } else {
+            ExecutorKind::MultiThreaded
+        }
     }
 
     fn init(&mut self, schedule: &SystemSchedule) {
@@ -169,6 +192,17 @@ impl SystemExecutor for MultiThreadedExecutor {
         state.skipped_systems = FixedBitSet::with_capacity(sys_count);
         state.unapplied_systems = FixedBitSet::with_capacity(sys_count);
 
+        state.ready_order = Vec::with_capacity(sys_count);
+        state.system_costs.clear();
+        state.critical_path_costs.clear();
+        state.system_keys.clear();
+        state.schedule_label = schedule.label;
+        if self.critical_path {
+            state.system_costs.resize(sys_count, SystemCost::default());
+            state.critical_path_costs.resize(sys_count, Duration::ZERO);
+            state.system_keys.extend_from_slice(&schedule.system_ids);
+        }
+
         state.system_task_metadata = Vec::with_capacity(sys_count);
         for index in 0..sys_count {
             state.system_task_metadata.push(SystemTaskMetadata {
@@ -296,6 +330,33 @@ impl SystemExecutor for MultiThreadedExecutor {
         let systems = environment.systems;
 
         let state = self.state.get_mut().unwrap();
+        if self.critical_path {
+            state.update_critical_path_costs();
+            if let Some(label) = state.schedule_label
+                && let Some(mut timings) = world.get_resource_mut::<SystemTimings>()
+            {
+                // Only record the systems that ran, and not the ones skipped by their run
+                // conditions or by stepping.
+                let mut ran_systems = state.completed_systems.clone();
+                ran_systems.difference_with(&state.skipped_systems);
+                #[cfg(feature = "bevy_debug_stepping")]
+                if let Some(skipped_systems) = _skip_systems {
+                    ran_systems.difference_with(skipped_systems);
+                }
+                for system_index in ran_systems.ones() {
+                    timings.record(
+                        label,
+                        state.system_keys[system_index],
+                        || {
+                            // SAFETY: all systems have completed, no other references exist
+                            unsafe { &*systems[system_index].get() }.system.name()
+                        },
+                        &state.system_costs[system_index],
+                    );
+                }
+            }
+        }
+
         if self.apply_final_deferred {
             // Do one final apply buffers after all systems have completed
             // Commands should be applied while on the scope's thread, not the executor's thread
@@ -331,12 +392,16 @@ impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
         system_index: usize,
         res: Result<(), Box<dyn Any + Send>>,
         system: &ScheduleSystem,
+        start: Option<Instant>,
     ) {
         // tell the executor that the system finished
         self.environment
             .executor
             .system_completion
-            .push(SystemResult { system_index })
+            .push(SystemResult {
+                system_index,
+                elapsed: start.map(|start| start.elapsed()),
+            })
             .unwrap_or_else(|error| unreachable!("{}", error));
         if let Err(payload) = res {
             #[cfg(feature = "std")]
@@ -394,12 +459,24 @@ impl MultiThreadedExecutor {
             state: Mutex::new(ExecutorState::new()),
             system_completion: ConcurrentQueue::unbounded(),
             starting_systems: FixedBitSet::new(),
+            critical_path: false,
             apply_final_deferred: true,
             panic_payload: Mutex::new(None),
             #[cfg(feature = "trace")]
             executor_span: info_span!("multithreaded executor"),
         }
     }
+
+    /// Creates a new `multi_threaded` executor that measures the run time of each system,
+    /// and starts the ready systems with the longest estimated chain of dependents first.
+    ///
+    /// This is the executor used by [`ExecutorKind::CriticalPath`].
+    pub fn critical_path() -> Self {
+        Self {
+            critical_path: true,
+            ..Self::new()
+        }
+    }
 }
 
 impl ExecutorState {
@@ -418,6 +495,11 @@ impl ExecutorState {
             skipped_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
+            ready_order: Vec::new(),
+            system_costs: Vec::new(),
+            critical_path_costs: Vec::new(),
+            system_keys: Vec::new(),
+            schedule_label: None,
         }
     }
 
@@ -466,6 +548,7 @@ impl ExecutorState {
 
         // can't borrow since loop mutably borrows `self`
         let mut ready_systems = core::mem::take(&mut self.ready_systems_copy);
+        let mut ready_order = core::mem::take(&mut self.ready_order);
 
         // Skipping systems may cause their dependents to become ready immediately.
         // If that happens, we need to run again immediately or we may fail to spawn those dependents.
@@ -474,8 +557,15 @@ impl ExecutorState {
             check_for_new_ready_systems = false;
 
             ready_systems.clone_from(&self.ready_systems);
+            ready_order.clear();
+            ready_order.extend(ready_systems.ones());
+            if context.environment.executor.critical_path {
+                // The sort is stable, so systems with the same cost keep their topological order.
+                ready_order
+                    .sort_by_key(|&system_index| Reverse(self.critical_path_costs[system_index]));
+            }
 
-            for system_index in ready_systems.ones() {
+            for &system_index in &ready_order {
                 debug_assert!(!self.running_systems.contains(system_index));
                 // SAFETY: Caller assured that these systems are not running.
                 // Therefore, no other reference to this system exists and there is no aliasing.
@@ -543,6 +633,7 @@ impl ExecutorState {
 
         // give back
         self.ready_systems_copy = ready_systems;
+        self.ready_order = ready_order;
     }
 
     fn can_run(&mut self, system_index: usize, conditions: &mut Conditions) -> bool {
@@ -687,6 +778,11 @@ impl ExecutorState {
         let system_meta = &self.system_task_metadata[system_index];
 
         let task = async move {
+            let start = context
+                .environment
+                .executor
+                .critical_path
+                .then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -709,7 +805,7 @@ impl ExecutorState {
                     }
                 };
             }));
-            context.system_completed(system_index, res, system);
+            context.system_completed(system_index, res, system, start);
         };
 
         if system_meta.is_send {
@@ -736,8 +832,13 @@ impl ExecutorState {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
+                let start = context
+                    .environment
+                    .executor
+                    .critical_path
+                    .then(Instant::now);
                 let res = apply_deferred(&unapplied_systems, context.environment.systems, world);
-                context.system_completed(system_index, res, system);
+                context.system_completed(system_index, res, system, start);
             };
 
             context.scope.spawn_on_scope(task);
@@ -746,6 +847,11 @@ impl ExecutorState {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
+                let start = context
+                    .environment
+                    .executor
+                    .critical_path
+                    .then(Instant::now);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     if let Err(RunSystemError::Failed(err)) =
                         __rust_begin_short_backtrace::run(system, world)
@@ -759,7 +865,7 @@ impl ExecutorState {
                         );
                     }
                 }));
-                context.system_completed(system_index, res, system);
+                context.system_completed(system_index, res, system, start);
             };
 
             context.scope.spawn_on_scope(task);
@@ -770,7 +876,14 @@ impl ExecutorState {
     }
 
     fn finish_system_and_handle_dependents(&mut self, result: SystemResult) {
-        let SystemResult { system_index, .. } = result;
+        let SystemResult {
+            system_index,
+            elapsed,
+        } = result;
+
+        if let Some(elapsed) = elapsed {
+            self.system_costs[system_index].record(elapsed);
+        }
 
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
@@ -794,6 +907,21 @@ impl ExecutorState {
         self.signal_dependents(system_index);
     }
 
+    /// Recomputes the estimated cost of the chain of dependents of every system from their run-time history.
+    fn update_critical_path_costs(&mut self) {
+        // Systems are sorted in topological order, so dependents always come after the systems they depend on.
+        for system_index in (0..self.system_costs.len()).rev() {
+            let dependents_cost = self.system_task_metadata[system_index]
+                .dependents
+                .iter()
+                .map(|&dependent| self.critical_path_costs[dependent])
+                .max()
+                .unwrap_or_default();
+            self.critical_path_costs[system_index] =
+                self.system_costs[system_index].mean() + dependents_cost;
+        }
+    }
+
     fn signal_dependents(&mut self, system_index: usize) {
         for &dep_idx in &self.system_task_metadata[system_index].dependents {
             let remaining = &mut self.num_dependencies_remaining[dep_idx];
@@ -901,10 +1029,11 @@ impl MainThreadExecutor {
 mod tests {
     use crate::{
         prelude::Resource,
-        schedule::{ExecutorKind, IntoScheduleConfigs, Schedule},
+        schedule::{ExecutorKind, IntoScheduleConfigs, Schedule, ScheduleLabel, SystemTimings},
         system::Commands,
         world::World,
     };
+    use core::time::Duration;
 
     #[derive(Resource)]
     struct R;
@@ -939,4 +1068,92 @@ mod tests {
         schedule.add_systems(((|_: Commands| {}), |_: Commands| {}).chain());
         schedule.run(&mut world);
     }
+
+    #[test]
+    fn critical_path_executor_records_system_timings() {
+        let mut world = World::new();
+        world.init_resource::<SystemTimings>();
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::CriticalPath);
+        assert_eq!(schedule.get_executor_kind(), ExecutorKind::CriticalPath);
+        schedule.add_systems(|| std::thread::sleep(Duration::from_millis(2)));
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let timings = world.resource::<SystemTimings>();
+        assert_eq!(timings.len(), 1);
+        let (_, _, timing) = timings.iter().next().unwrap();
+        assert!(timing.last >= Duration::from_millis(2));
+        assert!(timing.mean >= Duration::from_millis(2));
+        assert_eq!(timing.runs, 2);
+    }
+
+    #[test]
+    fn system_timings_are_keyed_by_schedule_and_system() {
+        #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct A;
+        #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct B;
+
+        fn system() {}
+
+        let mut world = World::new();
+        world.init_resource::<SystemTimings>();
+        for label in [A.intern(), B.intern()] {
+            let mut schedule = Schedule::new(label);
+            schedule.set_executor_kind(ExecutorKind::CriticalPath);
+            // The same system twice in a schedule, and once more in another schedule.
+            schedule.add_systems((system, system));
+            schedule.run(&mut world);
+        }
+
+        let timings = world.resource::<SystemTimings>();
+        assert_eq!(timings.len(), 4);
+        assert_eq!(
+            timings
+                .iter()
+                .filter(|(schedule, ..)| *schedule == A.intern())
+                .count(),
+            2
+        );
+        assert!(timings.iter().all(|(.., timing)| timing.runs == 1));
+    }
+
+    #[cfg(feature = "bevy_debug_stepping")]
+    #[test]
+    fn system_timings_skip_systems_skipped_by_stepping() {
+        use crate::{schedule::Stepping, system::RunSystemOnce};
+        use alloc::vec::Vec;
+
+        #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct A;
+
+        fn ran() {}
+        fn skipped() {}
+
+        let mut world = World::new();
+        world.init_resource::<SystemTimings>();
+        let mut schedule = Schedule::new(A);
+        schedule.set_executor_kind(ExecutorKind::CriticalPath);
+        schedule.add_systems((ran, skipped));
+        schedule.run(&mut world);
+
+        let mut stepping = Stepping::new();
+        stepping
+            .add_schedule(A)
+            .enable()
+            .always_run(A, ran)
+            .never_run(A, skipped);
+        world.insert_resource(stepping);
+        world.run_system_once(Stepping::begin_frame).unwrap();
+        schedule.run(&mut world);
+
+        let mut runs: Vec<_> = world
+            .resource::<SystemTimings>()
+            .iter()
+            .map(|(.., timing)| timing.runs)
+            .collect();
+        runs.sort();
+        assert_eq!(runs, [1, 2]);
+    }
 }
@@ -0,0 +1,143 @@
+use bevy_platform::collections::HashMap;
+use bevy_utils::prelude::DebugName;
+use core::time::Duration;
+
+use crate::{
+    resource::Resource,
+    schedule::{InternedScheduleLabel, SystemKey},
+};
+
+/// The number of runs kept in the run-time history of each system.
+const HISTORY_LEN: usize = 16;
+
+/// The run-time history of a single system, used by the [`CriticalPath`](super::ExecutorKind::CriticalPath)
+/// executor to estimate how long it will take to run next time.
+#[derive(Clone, Default)]
+pub(super) struct SystemCost {
+    history: [Duration; HISTORY_LEN],
+    len: usize,
+    next: usize,
+    sum: Duration,
+}
+
+impl SystemCost {
+    /// Adds a measured run time to the history, evicting the oldest one if it is full.
+    pub(super) fn record(&mut self, elapsed: Duration) {
+        if self.len == HISTORY_LEN {
+            self.sum -= self.history[self.next];
+        } else {
+            self.len += 1;
+        }
+        self.history[self.next] = elapsed;
+        self.sum += elapsed;
+        self.next = (self.next + 1) % HISTORY_LEN;
+    }
+
+    /// Returns the last measured run time.
+    pub(super) fn last(&self) -> Duration {
+        self.history[(self.next + HISTORY_LEN - 1) % HISTORY_LEN]
+    }
+
+    /// Returns the mean run time of the history, or zero if the system has never run.
+    pub(super) fn mean(&self) -> Duration {
+        match self.len {
+            0 => Duration::ZERO,
+            len => self.sum / len as u32,
+        }
+    }
+}
+
+/// The measured run time of a system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemTiming {
+    /// The name of the system, for display only.
+    ///
+    /// Without the `debug` feature, this is the same placeholder for every system.
+    pub name: DebugName,
+    /// How long the system took the last time it ran.
+    pub last: Duration,
+    /// The mean run time over the recent runs of the system.
+    pub mean: Duration,
+    /// How many times the system has been measured.
+    ///
+    /// This can be compared between reads to tell whether the system ran in the meantime.
+    pub runs: u64,
+}
+
+/// A [`Resource`] holding the run times of the systems run by [`CriticalPath`](super::ExecutorKind::CriticalPath)
+/// executors, keyed by schedule and system.
+///
+/// Executors only publish their measurements when this resource exists, so it must be initialized
+/// for it to be filled. The same system added to several schedules has one entry per schedule.
+#[derive(Resource, Default, Debug)]
+pub struct SystemTimings {
+    systems: HashMap<(InternedScheduleLabel, SystemKey), SystemTiming>,
+}
+
+impl SystemTimings {
+    /// Returns the timing of the given system in the given schedule, if it has run.
+    pub fn get(&self, schedule: InternedScheduleLabel, system: SystemKey) -> Option<&SystemTiming> {
+        self.systems.get(&(schedule, system))
+    }
+
+    /// Iterates over the schedule, key and timing of every system that has run.
+    pub fn iter(&self) -> impl Iterator<Item = (InternedScheduleLabel, SystemKey, &SystemTiming)> {
+        self.systems
+            .iter()
+            .map(|(&(schedule, system), timing)| (schedule, system, timing))
+    }
+
+    /// Returns the number of systems that have run.
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    /// Returns `true` if no system has run.
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    /// Records a run of a system, only calling `name` the first time the system is recorded.
+    pub(super) fn record(
+        &mut self,
+        schedule: InternedScheduleLabel,
+        system: SystemKey,
+        name: impl FnOnce() -> DebugName,
+        cost: &SystemCost,
+    ) {
+        let timing = self
+            .systems
+            .entry((schedule, system))
+            .or_insert_with(|| SystemTiming {
+                name: name(),
+                last: Duration::ZERO,
+                mean: Duration::ZERO,
+                runs: 0,
+            });
+        timing.last = cost.last();
+        timing.mean = cost.mean();
+        timing.runs += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SystemCost;
+    use core::time::Duration;
+
+    #[test]
+    fn system_cost_history() {
+        let mut cost = SystemCost::default();
+        assert_eq!(cost.mean(), Duration::ZERO);
+
+        cost.record(Duration::from_millis(2));
+        cost.record(Duration::from_millis(4));
+        assert_eq!(cost.last(), Duration::from_millis(4));
+        assert_eq!(cost.mean(), Duration::from_millis(3));
+
+        for _ in 0..super::HISTORY_LEN {
+            cost.record(Duration::from_millis(1));
+        }
+        assert_eq!(cost.mean(), Duration::from_millis(1));
+    }
+}
@@ -0,0 +1,490 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_platform::collections::HashMap;
+use bevy_utils::prelude::DebugName;
+use core::fmt::Write;
+
+use crate::{
+    schedule::{
+        graph::Direction::Outgoing, is_apply_deferred, ConditionWithAccess, NodeId, Schedule,
+        SystemKey, SystemSetKey,
+    },
+    world::World,
+};
+
+/// The format of a schedule graph exported with [`Schedule::export_graph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GraphExportFormat {
+    /// A [Graphviz](https://graphviz.org) DOT graph.
+    ///
+    /// Systems are boxes, system sets are ellipses linked to their members by dashed edges,
+    /// sync points are diamonds and access conflicts are dotted red edges labelled with the conflicting components.
+    Dot,
+    /// A JSON object with the following fields:
+    ///
+    /// - `schedule`: the label of the schedule.
+    /// - `systems`: an array of `{ "id", "name", "conditions", "sync_point" }`, in execution order,
+    ///   where `sync_point` is `null`, `"manual"` or `"auto"` for sync points inserted by
+    ///   [`ScheduleBuildSettings::auto_insert_apply_deferred`](crate::schedule::ScheduleBuildSettings::auto_insert_apply_deferred).
+    /// - `sets`: an array of `{ "id", "name", "conditions", "members" }`.
+    /// - `dependencies`: an array of `{ "before", "after" }` system ids.
+    /// - `conflicts`: an array of `{ "systems", "components" }`, for systems with ambiguous ordering
+    ///   and conflicting access.
+    Json,
+}
+
+/// How a system applies deferred buffers.
+#[derive(Clone, Copy)]
+enum SyncPoint {
+    Manual,
+    Auto,
+}
+
+struct ExportedSystem {
+    name: String,
+    conditions: Vec<String>,
+    sync_point: Option<SyncPoint>,
+}
+
+struct ExportedSet {
+    name: String,
+    conditions: Vec<String>,
+    members: Vec<String>,
+}
+
+struct ExportedConflict {
+    systems: (usize, usize),
+    components: Vec<String>,
+}
+
+/// A flattened view of an initialized [`Schedule`], rendered by each [`GraphExportFormat`].
+struct ExportedGraph {
+    schedule: String,
+    systems: Vec<ExportedSystem>,
+    sets: Vec<ExportedSet>,
+    dependencies: Vec<(usize, usize)>,
+    conflicts: Vec<ExportedConflict>,
+}
+
+fn system_id(index: usize) -> String {
+    format!("system_{index}")
+}
+
+fn set_id(index: usize) -> String {
+    format!("set_{index}")
+}
+
+impl ExportedGraph {
+    fn new(schedule: &Schedule, world: &World) -> Self {
+        let graph = schedule.graph();
+        let executable = schedule.executable();
+        let use_shortnames = schedule.get_build_settings().use_shortnames;
+        let name = |name: DebugName| {
+            if use_shortnames {
+                name.shortname().to_string()
+            } else {
+                name.to_string()
+            }
+        };
+        let condition_names = |conditions: &[ConditionWithAccess]| {
+            conditions
+                .iter()
+                .map(|condition| name(condition.condition.name()))
+                .collect()
+        };
+
+        let system_indices: HashMap<SystemKey, usize> = executable
+            .system_ids
+            .iter()
+            .enumerate()
+            .map(|(index, &key)| (key, index))
+            .collect();
+        let systems = executable
+            .systems
+            .iter()
+            .zip(&executable.system_conditions)
+            .zip(&executable.system_ids)
+            .map(|((system, conditions), &key)| ExportedSystem {
+                name: name(system.system.name()),
+                conditions: condition_names(conditions),
+                sync_point: is_apply_deferred(&*system.system).then(|| {
+                    // Sync points inserted by the build pass are not part of the declared dependency graph.
+                    if graph
+                        .dependency()
+                        .graph()
+                        .contains_node(NodeId::System(key))
+                    {
+                        SyncPoint::Manual
+                    } else {
+                        SyncPoint::Auto
+                    }
+                }),
+            })
+            .collect();
+
+        // System type sets only group the instances of a system, so they are left out.
+        let set_keys: Vec<SystemSetKey> = graph
+            .system_sets
+            .iter()
+            .filter(|(_, set, _)| set.system_type().is_none())
+            .map(|(key, _, _)| key)
+            .collect();
+        let set_indices: HashMap<SystemSetKey, usize> = set_keys
+            .iter()
+            .enumerate()
+            .map(|(index, &key)| (key, index))
+            .collect();
+        let set_conditions: HashMap<SystemSetKey, &[ConditionWithAccess]> = executable
+            .set_ids
+            .iter()
+            .zip(&executable.set_conditions)
+            .map(|(&key, conditions)| (key, conditions.as_slice()))
+            .collect();
+        let sets = set_keys
+            .iter()
+            .map(|&key| {
+                let set = &graph.system_sets[key];
+                ExportedSet {
+                    name: if set.is_anonymous() {
+                        "(anonymous)".into()
+                    } else {
+                        format!("{set:?}")
+                    },
+                    conditions: set_conditions
+                        .get(&key)
+                        .map(|conditions| condition_names(conditions))
+                        .unwrap_or_default(),
+                    members: graph
+                        .hierarchy()
+                        .graph()
+                        .neighbors_directed(NodeId::Set(key), Outgoing)
+                        .filter_map(|member| match member {
+                            NodeId::System(key) => system_indices.get(&key).copied().map(system_id),
+                            NodeId::Set(key) => set_indices.get(&key).copied().map(set_id),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let dependencies = executable
+            .system_dependents
+            .iter()
+            .enumerate()
+            .flat_map(|(before, dependents)| dependents.iter().map(move |&after| (before, after)))
+            .collect();
+
+        let conflicts = graph
+            .conflicting_systems()
+            .iter()
+            .filter_map(|(a, b, components)| {
+                Some(ExportedConflict {
+                    systems: (*system_indices.get(a)?, *system_indices.get(b)?),
+                    components: components
+                        .iter()
+                        .map(|&id| match world.components().get_name(id) {
+                            Some(component) => name(component),
+                            None => format!("{id:?}"),
+                        })
+                        .collect(),
+                })
+            })
+            .collect();
+
+        Self {
+            schedule: format!("{:?}", schedule.label()),
+            systems,
+            sets,
+            dependencies,
+            conflicts,
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        let label = |name: &str, conditions: &[String]| {
+            if conditions.is_empty() {
+                escape_dot(name)
+            } else {
+                escape_dot(&format!("{name}\nrun if: {}", conditions.join(", ")))
+            }
+        };
+
+        writeln!(dot, "digraph \"{}\" {{", escape_dot(&self.schedule)).unwrap();
+        writeln!(dot, "\tnode [shape=box];").unwrap();
+        for (index, system) in self.systems.iter().enumerate() {
+            let style = match system.sync_point {
+                None => "",
+                Some(SyncPoint::Manual) => ", shape=diamond",
+                Some(SyncPoint::Auto) => ", shape=diamond, style=dashed",
+            };
+            writeln!(
+                dot,
+                "\t\"{}\" [label=\"{}\"{style}];",
+                system_id(index),
+                label(&system.name, &system.conditions)
+            )
+            .unwrap();
+        }
+        for (index, set) in self.sets.iter().enumerate() {
+            writeln!(
+                dot,
+                "\t\"{}\" [label=\"{}\", shape=ellipse];",
+                set_id(index),
+                label(&set.name, &set.conditions)
+            )
+            .unwrap();
+            for member in &set.members {
+                writeln!(
+                    dot,
+                    "\t\"{}\" -> \"{member}\" [style=dashed, arrowhead=none];",
+                    set_id(index)
+                )
+                .unwrap();
+            }
+        }
+        for &(before, after) in &self.dependencies {
+            writeln!(
+                dot,
+                "\t\"{}\" -> \"{}\";",
+                system_id(before),
+                system_id(after)
+            )
+            .unwrap();
+        }
+        for conflict in &self.conflicts {
+            writeln!(
+                dot,
+                "\t\"{}\" -> \"{}\" [dir=none, style=dotted, color=red, label=\"{}\"];",
+                system_id(conflict.systems.0),
+                system_id(conflict.systems.1),
+                escape_dot(&conflict.components.join(", "))
+            )
+            .unwrap();
+        }
+        dot.push('}');
+        dot
+    }
+
+    fn to_json(&self) -> String {
+        let strings = |values: &[String]| {
+            let values: Vec<_> = values.iter().map(|value| escape_json(value)).collect();
+            format!("[{}]", values.join(", "))
+        };
+
+        let mut json = String::new();
+        writeln!(json, "{{").unwrap();
+        writeln!(json, "  \"schedule\": {},", escape_json(&self.schedule)).unwrap();
+
+        writeln!(json, "  \"systems\": [").unwrap();
+        for (index, system) in self.systems.iter().enumerate() {
+            let sync_point = match system.sync_point {
+                None => "null",
+                Some(SyncPoint::Manual) => "\"manual\"",
+                Some(SyncPoint::Auto) => "\"auto\"",
+            };
+            let separator = if index + 1 < self.systems.len() {
+                ","
+            } else {
+                ""
+            };
+            writeln!(
+                json,
+                "    {{ \"id\": \"{}\", \"name\": {}, \"conditions\": {}, \"sync_point\": {sync_point} }}{separator}",
+                system_id(index),
+                escape_json(&system.name),
+                strings(&system.conditions),
+            )
+            .unwrap();
+        }
+        writeln!(json, "  ],").unwrap();
+
+        writeln!(json, "  \"sets\": [").unwrap();
+        for (index, set) in self.sets.iter().enumerate() {
+            let separator = if index + 1 < self.sets.len() { "," } else { "" };
+            writeln!(
+                json,
+                "    {{ \"id\": \"{}\", \"name\": {}, \"conditions\": {}, \"members\": {} }}{separator}",
+                set_id(index),
+                escape_json(&set.name),
+                strings(&set.conditions),
+                strings(&set.members),
+            )
+            .unwrap();
+        }
+        writeln!(json, "  ],").unwrap();
+
+        writeln!(json, "  \"dependencies\": [").unwrap();
+        for (index, &(before, after)) in self.dependencies.iter().enumerate() {
+            let separator = if index + 1 < self.dependencies.len() {
+                ","
+            } else {
+                ""
+            };
+            writeln!(
+                json,
+                "    {{ \"before\": \"{}\", \"after\": \"{}\" }}{separator}",
+                system_id(before),
+                system_id(after),
+            )
+            .unwrap();
+        }
+        writeln!(json, "  ],").unwrap();
+
+        writeln!(json, "  \"conflicts\": [").unwrap();
+        for (index, conflict) in self.conflicts.iter().enumerate() {
+            let separator = if index + 1 < self.conflicts.len() {
+                ","
+            } else {
+                ""
+            };
+            writeln!(
+                json,
+                "    {{ \"systems\": [\"{}\", \"{}\"], \"components\": {} }}{separator}",
+                system_id(conflict.systems.0),
+                system_id(conflict.systems.1),
+                strings(&conflict.components),
+            )
+            .unwrap();
+        }
+        writeln!(json, "  ]").unwrap();
+        json.push('}');
+        json
+    }
+}
+
+/// Escapes a string to be used inside a quoted DOT identifier.
+fn escape_dot(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quotes and escapes a string as a JSON string.
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Renders the graph of an initialized schedule, see [`Schedule::export_graph`].
+pub(super) fn export_graph(
+    schedule: &Schedule,
+    world: &World,
+    format: GraphExportFormat,
+) -> String {
+    let graph = ExportedGraph::new(schedule, world);
+    match format {
+        GraphExportFormat::Dot => graph.to_dot(),
+        GraphExportFormat::Json => graph.to_json(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_json, GraphExportFormat};
+    use crate::{
+        prelude::*,
+        schedule::{ScheduleBuildSettings, ScheduleLabel},
+    };
+
+    #[derive(ScheduleLabel, Hash, PartialEq, Eq, Debug, Clone)]
+    struct TestSchedule;
+
+    #[derive(SystemSet, Hash, PartialEq, Eq, Debug, Clone)]
+    struct TestSet;
+
+    #[derive(Resource)]
+    struct R;
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty();
+    }
+
+    fn read(_: Query<Entity>) {}
+
+    fn write_a(_: ResMut<R>) {}
+
+    fn write_b(_: ResMut<R>) {}
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.insert_resource(R);
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: crate::schedule::LogLevel::Ignore,
+            ..Default::default()
+        });
+        schedule
+            .configure_sets(TestSet.run_if(|| true))
+            .add_systems(((spawn, read).chain(), write_a, write_b).in_set(TestSet));
+        schedule.initialize(&mut world).unwrap();
+        (world, schedule)
+    }
+
+    #[test]
+    fn export_requires_initialization() {
+        let world = World::new();
+        let schedule = Schedule::new(TestSchedule);
+        assert!(schedule
+            .export_graph(&world, GraphExportFormat::Dot)
+            .is_err());
+    }
+
+    #[test]
+    fn export_dot() {
+        let (world, schedule) = setup();
+        let dot = schedule
+            .export_graph(&world, GraphExportFormat::Dot)
+            .unwrap();
+
+        assert!(dot.starts_with("digraph \"TestSchedule\" {"));
+        assert!(dot.ends_with('}'));
+        // The auto-inserted sync point between `spawn` and `read`.
+        assert_eq!(dot.matches("shape=diamond, style=dashed").count(), 1);
+        assert!(dot.contains("[label=\"TestSet\\nrun if: "));
+        assert_eq!(dot.matches("[style=dashed, arrowhead=none]").count(), 4);
+        assert_eq!(dot.matches("color=red").count(), 1);
+    }
+
+    #[test]
+    fn export_json() {
+        let (world, schedule) = setup();
+        let json = schedule
+            .export_graph(&world, GraphExportFormat::Json)
+            .unwrap();
+
+        assert!(json.contains("\"schedule\": \"TestSchedule\""));
+        assert_eq!(json.matches("\"sync_point\": \"auto\"").count(), 1);
+        assert_eq!(json.matches("\"sync_point\": null").count(), 4);
+        assert_eq!(json.matches("\"before\": ").count(), 2);
+        assert_eq!(json.matches("\"components\": [").count(), 1);
+    }
+
+    #[test]
+    fn json_escaping() {
+        assert_eq!(escape_json("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+}
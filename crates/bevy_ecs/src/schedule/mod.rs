@@ -5,6 +5,7 @@ mod condition;
 mod config;
 mod error;
 mod executor;
+mod export;
 mod node;
 mod pass;
 mod schedule;
@@ -12,7 +13,10 @@ mod set;
 mod stepping;
 
 pub use self::graph::GraphInfo;
-pub use self::{condition::*, config::*, error::*, executor::*, node::*, schedule::*, set::*};
+pub use self::{
+    condition::*, config::*, error::*, executor::*, export::GraphExportFormat, node::*,
+    schedule::*, set::*,
+};
 pub use pass::ScheduleBuildPass;
 
 /// An implementation of a graph data structure.
@@ -1302,5 +1306,11 @@ mod tests {
         fn multi_threaded_executor() {
             assert_executor_supports_stepping!(ExecutorKind::MultiThreaded);
         }
+
+        /// verify the [`MultiThreadedExecutor`] supports stepping when ordering by critical path
+        #[test]
+        fn critical_path_executor() {
+            assert_executor_supports_stepping!(ExecutorKind::CriticalPath);
+        }
     }
 }
@@ -240,6 +240,8 @@ fn make_executor(kind: ExecutorKind) -> Box<dyn SystemExecutor> {
         ExecutorKind::SingleThreaded => Box::new(SingleThreadedExecutor::new()),
         #[cfg(feature = "std")]
         ExecutorKind::MultiThreaded => Box::new(MultiThreadedExecutor::new()),
+        #[cfg(feature = "std")]
+        ExecutorKind::CriticalPath => Box::new(MultiThreadedExecutor::critical_path()),
     }
 }
 
@@ -674,6 +676,28 @@ impl Schedule {
         }
     }
 
+    /// Exports the graph of this schedule in the given [`GraphExportFormat`], for example to review
+    /// changes to the schedule or to visualize it.
+    ///
+    /// The export includes the systems in execution order with their run conditions, the system sets
+    /// with their run conditions and members, the ordering dependencies between systems once sets are
+    /// flattened, the sync points applying deferred buffers, and the systems with ambiguous ordering
+    /// and conflicting access, along with the components they conflict on.
+    ///
+    /// Note: this method will return [`ScheduleNotInitialized`] if the
+    /// schedule has never been initialized or run.
+    pub fn export_graph(
+        &self,
+        world: &World,
+        format: GraphExportFormat,
+    ) -> Result<String, ScheduleNotInitialized> {
+        if !self.executor_initialized {
+            return Err(ScheduleNotInitialized);
+        }
+
+        Ok(export::export_graph(self, world, format))
+    }
+
     /// Returns warnings that were generated during the last call to
     /// [`Schedule::initialize`].
     pub fn warnings(&self) -> &[ScheduleBuildWarning] {
@@ -1314,6 +1338,7 @@ impl ScheduleGraph {
             system_dependents,
             sets_with_conditions_of_systems,
             systems_in_sets_with_conditions,
+            label: None,
         }
     }
 
@@ -1357,6 +1382,7 @@ impl ScheduleGraph {
 
         let (new_schedule, warnings) = self.build_schedule(world, ignored_ambiguities)?;
         *schedule = new_schedule;
+        schedule.label = Some(schedule_label);
 
         for warning in &warnings {
             warn!(
@@ -5,7 +5,10 @@ use crate::{
     bundle::{Bundle, DynamicBundle, InsertMode, NoBundleEffect},
     change_detection::MaybeLocation,
     entity::Entity,
-    relationship::{RelatedSpawner, Relationship, RelationshipHookMode, RelationshipTarget},
+    relationship::{
+        ManyRelationship, ManyRelationshipTarget, RelatedSpawner, Relationship,
+        RelationshipHookMode, RelationshipSourceCollection, RelationshipTarget,
+    },
     world::{EntityWorldMut, World},
 };
 use alloc::vec::Vec;
@@ -251,7 +254,7 @@ impl<R: Relationship> SpawnableList<R> for WithOneRelated {
 macro_rules! spawnable_list_impl {
     ($(#[$meta:meta])* $(($index:tt, $list: ident, $alias: ident)),*) => {
         $(#[$meta])*
-        impl<R: Relationship, $($list: SpawnableList<R>),*> SpawnableList<R> for ($($list,)*) {
+        impl<R, $($list: SpawnableList<R>),*> SpawnableList<R> for ($($list,)*) {
             #[expect(
                 clippy::allow_attributes,
                 reason = "This is a tuple-related macro; as such, the lints below may not always apply."
@@ -449,6 +452,185 @@ impl<T: RelationshipTarget> SpawnRelated for T {
     }
 }
 
+/// The relationship parameter of the [`SpawnableList`]s spawning and linking the "source" entities of a [`ManyRelationship`].
+///
+/// [`Spawn`], [`SpawnIter`], [`WithRelated`], [`WithOneRelated`] and tuples of them implement
+/// [`SpawnableList<ManyRelated<R>>`], which [`SpawnManyRelated`] takes.
+pub struct ManyRelated<R: ManyRelationship>(PhantomData<R>);
+
+/// Creates the `R` [`ManyRelationship`] of a source entity relating to the single `target` entity.
+fn many_relationship<R: ManyRelationship>(target: Entity) -> R {
+    let mut collection = R::Collection::with_capacity(1);
+    collection.add(target);
+    R::from_collection(collection)
+}
+
+impl<R: ManyRelationship, B: Bundle> SpawnableList<ManyRelated<R>> for Spawn<B> {
+    fn spawn(this: MovingPtr<'_, Self>, world: &mut World, entity: Entity) {
+        world.spawn((many_relationship::<R>(entity), this.read().0));
+    }
+
+    fn size_hint(&self) -> usize {
+        1
+    }
+}
+
+impl<R: ManyRelationship, I: Iterator<Item = B> + Send + Sync + 'static, B: Bundle>
+    SpawnableList<ManyRelated<R>> for SpawnIter<I>
+{
+    fn spawn(mut this: MovingPtr<'_, Self>, world: &mut World, entity: Entity) {
+        for bundle in &mut this.0 {
+            world.spawn((many_relationship::<R>(entity), bundle));
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.size_hint().0
+    }
+}
+
+impl<R: ManyRelationship, I: Iterator<Item = Entity>> SpawnableList<ManyRelated<R>>
+    for WithRelated<I>
+{
+    fn spawn(mut this: MovingPtr<'_, Self>, world: &mut World, entity: Entity) {
+        let related = (&mut this.0).collect::<Vec<_>>();
+        world.entity_mut(entity).add_many_related::<R>(&related);
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.size_hint().0
+    }
+}
+
+impl<R: ManyRelationship> SpawnableList<ManyRelated<R>> for WithOneRelated {
+    fn spawn(this: MovingPtr<'_, Self>, world: &mut World, entity: Entity) {
+        world
+            .entity_mut(entity)
+            .add_many_related::<R>(&[this.read().0]);
+    }
+
+    fn size_hint(&self) -> usize {
+        1
+    }
+}
+
+/// A [`Bundle`] that:
+/// 1. Contains a [`ManyRelationshipTarget`] component (associated with the given [`ManyRelationship`]). This reserves space for the [`SpawnableList`].
+/// 2. Spawns (or links) a [`SpawnableList`] of source entities relating to the bundle's entity with the given [`ManyRelationship`].
+///
+/// This is intended to be created using [`SpawnManyRelated`].
+pub struct SpawnManyRelatedBundle<R: ManyRelationship, L: SpawnableList<ManyRelated<R>>> {
+    list: L,
+    marker: PhantomData<R>,
+}
+
+// SAFETY: This internally relies on the ManyRelationshipTarget's Bundle implementation, which is sound.
+unsafe impl<R: ManyRelationship, L: SpawnableList<ManyRelated<R>> + Send + Sync + 'static> Bundle
+    for SpawnManyRelatedBundle<R, L>
+{
+    fn component_ids(
+        components: &mut crate::component::ComponentsRegistrator,
+    ) -> impl Iterator<Item = crate::component::ComponentId> + use<R, L> {
+        <R::RelationshipTarget as Bundle>::component_ids(components)
+    }
+
+    fn get_component_ids(
+        components: &crate::component::Components,
+    ) -> impl Iterator<Item = Option<crate::component::ComponentId>> {
+        <R::RelationshipTarget as Bundle>::get_component_ids(components)
+    }
+}
+
+impl<R: ManyRelationship, L: SpawnableList<ManyRelated<R>>> DynamicBundle
+    for SpawnManyRelatedBundle<R, L>
+{
+    type Effect = Self;
+
+    unsafe fn get_components(
+        ptr: MovingPtr<'_, Self>,
+        func: &mut impl FnMut(crate::component::StorageType, bevy_ptr::OwningPtr<'_>),
+    ) {
+        let target =
+            <R::RelationshipTarget as ManyRelationshipTarget>::with_capacity(ptr.list.size_hint());
+        move_as_ptr!(target);
+        // SAFETY:
+        // - The caller must ensure that this is called exactly once before `apply_effect`.
+        // - Assuming `DynamicBundle` is implemented correctly for `R::Relationship` target, `func` should be
+        //   called exactly once for each component being fetched with the correct `StorageType`
+        // - `Effect: !NoBundleEffect`, which means the caller is responsible for calling this type's `apply_effect`
+        //   at least once before returning to safe code.
+        unsafe { <R::RelationshipTarget as DynamicBundle>::get_components(target, func) };
+        // Forget the pointer so that the value is available in `apply_effect`.
+        mem::forget(ptr);
+    }
+
+    unsafe fn apply_effect(ptr: MovingPtr<'_, MaybeUninit<Self>>, entity: &mut EntityWorldMut) {
+        // SAFETY: The value was not moved out in `get_components`, only borrowed, and thus should still
+        // be valid and initialized.
+        let effect = unsafe { ptr.assume_init() };
+        let id = entity.id();
+
+        entity.world_scope(|world: &mut World| {
+            bevy_ptr::deconstruct_moving_ptr!({
+                let Self { list, marker: _ } = effect;
+            });
+            L::spawn(list, world, id);
+        });
+    }
+}
+
+/// [`ManyRelationshipTarget`] methods that create a [`Bundle`] with a [`DynamicBundle::Effect`] that:
+///
+/// 1. Contains the [`ManyRelationshipTarget`] component, pre-allocated with the necessary space for the source entities.
+/// 2. Spawns (or links) a list of source entities that relate to the entity the [`Bundle`] is added to via the
+///    [`ManyRelationshipTarget::Relationship`].
+///
+/// This is the many-to-many counterpart of [`SpawnRelated`]. Spawned source entities relate to a single target,
+/// and can be linked to more targets later on.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::spawn::SpawnManyRelated;
+/// #[derive(Component)]
+/// #[relationship(relationship_target = Members, many)]
+/// pub struct MemberOf(pub Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = MemberOf, many)]
+/// pub struct Members(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// let alice = world.spawn(Name::new("Alice")).id();
+/// world.spawn((
+///     Name::new("Guild"),
+///     Members::spawn_many((
+///         Spawn(Name::new("Bob")),
+///         // This adds the already existing entity to the guild.
+///         WithOneRelated(alice),
+///     )),
+/// ));
+/// ```
+pub trait SpawnManyRelated: ManyRelationshipTarget {
+    /// Returns a [`Bundle`] containing this [`ManyRelationshipTarget`] component. It also spawns (or links) a
+    /// [`SpawnableList`] of source entities, each related to the bundle's entity via [`ManyRelationshipTarget::Relationship`].
+    ///
+    /// See [`Spawn`], [`SpawnIter`], [`WithRelated`] and [`WithOneRelated`] for the supported lists.
+    fn spawn_many<L: SpawnableList<ManyRelated<Self::Relationship>>>(
+        list: L,
+    ) -> SpawnManyRelatedBundle<Self::Relationship, L>;
+}
+
+impl<T: ManyRelationshipTarget> SpawnManyRelated for T {
+    fn spawn_many<L: SpawnableList<ManyRelated<Self::Relationship>>>(
+        list: L,
+    ) -> SpawnManyRelatedBundle<Self::Relationship, L> {
+        SpawnManyRelatedBundle {
+            list,
+            marker: PhantomData,
+        }
+    }
+}
+
 /// Returns a [`SpawnRelatedBundle`] that will insert the given [`RelationshipTarget`], spawn a [`SpawnableList`] of entities with given bundles that
 /// relate to the [`RelationshipTarget`] entity via the [`RelationshipTarget::Relationship`] component, and reserve space in the [`RelationshipTarget`] for each spawned entity.
 ///
@@ -482,6 +664,37 @@ macro_rules! related {
     };
 }
 
+/// Returns a [`SpawnManyRelatedBundle`] that will insert the given [`ManyRelationshipTarget`], and spawn a [`SpawnableList`]
+/// of source entities with the given bundles that relate to the [`ManyRelationshipTarget`] entity via the
+/// [`ManyRelationshipTarget::Relationship`] component.
+///
+/// This is the many-to-many counterpart of [`related`](crate::related): the first argument is the [`ManyRelationshipTarget`]
+/// type, and any additional arguments will be interpreted as bundles to be spawned.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::many_related;
+/// #[derive(Component)]
+/// #[relationship(relationship_target = Members, many)]
+/// pub struct MemberOf(pub Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = MemberOf, many)]
+/// pub struct Members(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// world.spawn((
+///     Name::new("Guild"),
+///     many_related!(Members[Name::new("Alice"), Name::new("Bob")]),
+/// ));
+/// ```
+#[macro_export]
+macro_rules! many_related {
+    ($relationship_target:ty [$($child:expr),*$(,)?]) => {
+       <$relationship_target as $crate::spawn::SpawnManyRelated>::spawn_many($crate::recursive_spawn!($($child),*))
+    };
+}
+
 // A tail-recursive spawn utility.
 //
 // Since `SpawnableList` is only implemented for tuples
@@ -3,12 +3,14 @@ use bevy_utils::prelude::DebugName;
 use crate::{
     batching::BatchingStrategy,
     change_detection::Tick,
-    entity::{Entity, EntityEquivalent, EntitySet, UniqueEntityArray},
+    entity::{index_set, Entity, EntityEquivalent, EntitySet, UniqueEntityArray},
     query::{
         DebugCheckedUnwrap, NopWorldQuery, QueryCombinationIter, QueryData, QueryEntityError,
         QueryFilter, QueryIter, QueryManyIter, QueryManyUniqueIter, QueryParIter, QueryParManyIter,
-        QueryParManyUniqueIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyQueryData,
+        QueryParManyUniqueIter, QuerySingleError, QueryState, QueryStats, ROQueryItem,
+        ReadOnlyQueryData,
     },
+    relationship::{Relationship, RelationshipIndex},
     world::unsafe_world_cell::UnsafeWorldCell,
 };
 use core::{
@@ -1090,6 +1092,68 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the read-only query items of the entities whose `R` [`Relationship`]
+    /// targets `target`.
+    ///
+    /// This is backed by the [`RelationshipIndex<R>`], so only the matching entities are visited.
+    /// Entities that don't match the query are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query filter does not include [`RelatedTo<R>`](crate::query::RelatedTo).
+    ///
+    /// # See also
+    ///
+    /// - [`iter_related_to_mut`](Self::iter_related_to_mut) to get mutable query items.
+    #[inline]
+    pub fn iter_related_to<R: Relationship>(
+        &self,
+        target: Entity,
+    ) -> QueryManyUniqueIter<'_, 's, D::ReadOnly, F, index_set::Iter<'w>> {
+        let sources = self.relationship_index::<R>().sources(target);
+        self.iter_many_unique(sources)
+    }
+
+    /// Returns an iterator over the query items of the entities whose `R` [`Relationship`]
+    /// targets `target`.
+    ///
+    /// This is backed by the [`RelationshipIndex<R>`], so only the matching entities are visited.
+    /// Entities that don't match the query are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query filter does not include [`RelatedTo<R>`](crate::query::RelatedTo).
+    ///
+    /// # See also
+    ///
+    /// - [`iter_related_to`](Self::iter_related_to) to get read-only query items.
+    #[inline]
+    pub fn iter_related_to_mut<R: Relationship>(
+        &mut self,
+        target: Entity,
+    ) -> QueryManyUniqueIter<'_, 's, D, F, index_set::Iter<'w>> {
+        let sources = self.relationship_index::<R>().sources(target);
+        self.iter_many_unique_mut(sources)
+    }
+
+    fn relationship_index<R: Relationship>(&self) -> &'w RelationshipIndex<R> {
+        let has_access = self
+            .world
+            .components()
+            .resource_id::<RelationshipIndex<R>>()
+            .is_some_and(|id| self.state.component_access().access().has_resource_read(id));
+        assert!(
+            has_access,
+            "Query<{}, {}> must include the RelatedTo<{}> filter to look up entities by relationship target",
+            DebugName::type_name::<D>(),
+            DebugName::type_name::<F>(),
+            DebugName::type_name::<R>(),
+        );
+        // SAFETY: We checked above that the query has read access to the index resource.
+        unsafe { self.world.get_resource::<RelationshipIndex<R>>() }
+            .expect("RelationshipIndex is initialized by the RelatedTo filter")
+    }
+
     /// Returns an [`Iterator`] over the query items.
     ///
     /// This iterator is always guaranteed to return results from each matching entity once and only once.
@@ -2006,6 +2070,31 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns how this query matches the archetypes and tables of the world,
+    /// and how much time was spent keeping its cache up to date.
+    ///
+    /// See [`QueryState::stats`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// #
+    /// fn report_fragmentation(query: Query<&Enemy>) {
+    ///     let stats = query.stats();
+    ///     if stats.matched_archetypes > 100 {
+    ///         println!("`Enemy` is spread over {} archetypes", stats.matched_archetypes);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(report_fragmentation);
+    /// ```
+    pub fn stats(&self) -> QueryStats {
+        self.state.stats()
+    }
+
     /// Returns `true` if the given [`Entity`] matches the query.
     ///
     /// This is always guaranteed to run in `O(1)` time.
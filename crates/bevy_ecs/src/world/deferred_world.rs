@@ -778,7 +778,7 @@ impl<'w> DeferredWorld<'w> {
             // SAFETY: The only outstanding reference to world is `observers`
             (world.into_deferred(), observers)
         };
-        let context = TriggerContext { event_key, caller };
+        let context = TriggerContext::new(event_key, caller);
 
         // SAFETY:
         // - `observers` comes from `world`, and corresponds to the `event_key`, as it was looked up above
@@ -0,0 +1,451 @@
+//! Recording of structured change sets between two points in time of a [`World`].
+//!
+//! See [`WorldDiffRecorder`] and [`WorldDiff`].
+
+#[cfg(feature = "serialize")]
+mod serde;
+
+#[cfg(feature = "serialize")]
+pub use self::serde::{WorldDiffDeserializer, WorldDiffSerializer};
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{PartialReflect, ReflectFromReflect, TypeRegistration};
+use thiserror::Error;
+
+use crate::{
+    change_detection::Tick,
+    component::ComponentId,
+    entity::{Entity, EntityHashMap, EntityHashSet, SceneEntityMapper},
+    lifecycle::RemovedComponentEntity,
+    message::MessageCursor,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    relationship::RelationshipHookMode,
+    world::World,
+};
+
+/// Records the changes made to a [`World`] as [`WorldDiff`]s.
+///
+/// Each call to [`record`](Self::record) returns everything that changed since the previous call
+/// (or since the recorder was created):
+/// - entities spawned and despawned,
+/// - components inserted and removed,
+/// - components mutated, captured as reflected values.
+///
+/// Changes are detected with the [`Tick`]s stored alongside every component, so only components
+/// registered in the [`AppTypeRegistry`] with [`ReflectComponent`] are recorded.
+/// Removals are read from the world's [`RemovedComponents`](crate::lifecycle::RemovedComponents) buffers,
+/// which are cleared by [`World::clear_trackers`]: [`record`](Self::record) must be called at least once
+/// between two calls to it to observe every removal. Despawns are deduced from those removals, so despawning
+/// an entity without any components is not recorded.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::reflect::AppTypeRegistry;
+/// # use bevy_ecs::world::WorldDiffRecorder;
+/// # use bevy_ecs::entity::EntityHashMap;
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// let mut recorder = WorldDiffRecorder::new(&mut world);
+///
+/// world.spawn(Health(10));
+/// let diff = recorder.record(&mut world);
+///
+/// let mut other = World::new();
+/// other.insert_resource(world.resource::<AppTypeRegistry>().clone());
+/// let mut entity_map = EntityHashMap::default();
+/// diff.apply(&mut other, &mut entity_map).unwrap();
+/// assert_eq!(other.query::<&Health>().single(&other).unwrap().0, 10);
+/// ```
+pub struct WorldDiffRecorder {
+    last_run: Tick,
+    removed: HashMap<ComponentId, MessageCursor<RemovedComponentEntity>>,
+}
+
+impl WorldDiffRecorder {
+    /// Creates a recorder that tracks the changes made to `world` from now on.
+    pub fn new(world: &mut World) -> Self {
+        let removed = world
+            .removed_components()
+            .iter()
+            .map(|(id, messages)| (*id, messages.get_cursor_current()))
+            .collect();
+        Self {
+            last_run: world.increment_change_tick(),
+            removed,
+        }
+    }
+
+    /// Returns the [`Tick`] up to which changes have been recorded.
+    pub fn last_run(&self) -> Tick {
+        self.last_run
+    }
+
+    /// Returns every change made to `world` since the previous recording.
+    pub fn record(&mut self, world: &mut World) -> WorldDiff {
+        let this_run = world.increment_change_tick();
+        let last_run = core::mem::replace(&mut self.last_run, this_run);
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let mut diff = WorldDiff::default();
+        let mut entities = EntityHashMap::<EntityDiff>::default();
+
+        for archetype in world.archetypes().iter() {
+            for archetype_entity in archetype.entities() {
+                let entity = archetype_entity.id();
+                let entity_ref = world.entity(entity);
+                let spawned = world
+                    .entities()
+                    .entity_get_spawn_or_despawn_tick(entity)
+                    .is_some_and(|tick| tick.is_newer_than(last_run, this_run));
+                if spawned {
+                    diff.spawned.push(entity);
+                }
+
+                for component_id in archetype.iter_components() {
+                    let Some(ticks) = entity_ref.get_change_ticks_by_id(component_id) else {
+                        continue;
+                    };
+                    let inserted = spawned || ticks.is_added(last_run, this_run);
+                    if !inserted && !ticks.is_changed(last_run, this_run) {
+                        continue;
+                    }
+                    let Some((registration, reflect_component)) =
+                        reflect_component(world, &registry, component_id)
+                    else {
+                        continue;
+                    };
+                    let Some(value) = reflect_component.reflect(entity_ref) else {
+                        continue;
+                    };
+
+                    let value = clone_reflect_value(value.as_partial_reflect(), registration);
+                    let entity_diff = entities
+                        .entry(entity)
+                        .or_insert_with(|| EntityDiff::new(entity));
+                    if inserted {
+                        entity_diff.inserted.push(value);
+                    } else {
+                        entity_diff.mutated.push(value);
+                    }
+                }
+            }
+        }
+
+        let mut despawned = EntityHashSet::default();
+        for (&component_id, messages) in world.removed_components().iter() {
+            let cursor = self.removed.entry(component_id).or_default();
+            for removed in cursor.read(messages) {
+                let entity: Entity = removed.clone().into();
+                let Ok(entity_ref) = world.get_entity(entity) else {
+                    despawned.insert(entity);
+                    continue;
+                };
+                if entity_ref.contains_id(component_id) || diff.spawned.contains(&entity) {
+                    continue;
+                }
+                let Some((registration, _)) = reflect_component(world, &registry, component_id)
+                else {
+                    continue;
+                };
+                let type_path = registration.type_info().type_path();
+                let entity_diff = entities
+                    .entry(entity)
+                    .or_insert_with(|| EntityDiff::new(entity));
+                if !entity_diff
+                    .removed
+                    .iter()
+                    .any(|removed| removed == type_path)
+                {
+                    entity_diff.removed.push(type_path.into());
+                }
+            }
+        }
+
+        diff.despawned = despawned.into_iter().collect();
+        diff.despawned.sort_unstable();
+        diff.spawned.sort_unstable();
+        diff.entities = entities.into_inner().into_values().collect();
+        diff.entities
+            .sort_unstable_by_key(|entity_diff| entity_diff.entity);
+        diff
+    }
+}
+
+fn reflect_component<'r>(
+    world: &World,
+    registry: &'r bevy_reflect::TypeRegistry,
+    component_id: ComponentId,
+) -> Option<(&'r TypeRegistration, &'r ReflectComponent)> {
+    let type_id = world.components().get_info(component_id)?.type_id()?;
+    let registration = registry.get(type_id)?;
+    Some((registration, registration.data::<ReflectComponent>()?))
+}
+
+pub(super) fn clone_reflect_value(
+    value: &dyn PartialReflect,
+    registration: &TypeRegistration,
+) -> Box<dyn PartialReflect> {
+    value
+        .reflect_clone()
+        .map(PartialReflect::into_partial_reflect)
+        .unwrap_or_else(|_| {
+            registration
+                .data::<ReflectFromReflect>()
+                .and_then(|fr| fr.from_reflect(value))
+                .map(PartialReflect::into_partial_reflect)
+                .unwrap_or_else(|| value.to_dynamic())
+        })
+}
+
+/// A structured change set of a [`World`], produced by a [`WorldDiffRecorder`].
+///
+/// Component values are stored as reflected values, so a diff can be serialized with
+/// [`WorldDiffSerializer`](crate::world::WorldDiffSerializer) (requires the `serialize` feature)
+/// and re-applied to another [`World`] with [`WorldDiff::apply`].
+#[derive(Default)]
+pub struct WorldDiff {
+    /// Entities spawned since the previous recording.
+    pub spawned: Vec<Entity>,
+    /// Entities despawned since the previous recording.
+    pub despawned: Vec<Entity>,
+    /// Component changes of the entities that still exist, sorted by entity.
+    pub entities: Vec<EntityDiff>,
+}
+
+/// The component changes of a single entity in a [`WorldDiff`].
+pub struct EntityDiff {
+    /// The entity in the recorded [`World`].
+    pub entity: Entity,
+    /// Components inserted since the previous recording, with their current value.
+    pub inserted: Vec<Box<dyn PartialReflect>>,
+    /// Components mutated since the previous recording, with their current value.
+    pub mutated: Vec<Box<dyn PartialReflect>>,
+    /// Type paths of the components removed since the previous recording.
+    pub removed: Vec<String>,
+}
+
+impl EntityDiff {
+    /// Creates an empty diff for `entity`.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            inserted: Vec::new(),
+            mutated: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+}
+
+/// An error that occurs when applying a [`WorldDiff`] to a [`World`].
+#[derive(Error, Debug)]
+pub enum WorldDiffError {
+    /// A component type is not registered in the [`AppTypeRegistry`] with [`ReflectComponent`].
+    #[error(
+        "the type `{type_path}` is not registered as a component in the type registry: consider reflecting it with `#[reflect(Component)]`"
+    )]
+    UnregisteredComponent {
+        /// The type path of the component.
+        type_path: String,
+    },
+    /// A reflected value does not represent any type.
+    #[error("a diff value of the dynamic type `{type_path}` does not represent a concrete type")]
+    NoRepresentedType {
+        /// The type path of the dynamic value.
+        type_path: String,
+    },
+    /// An entity of the diff was neither spawned by it nor present in the entity map.
+    #[error("the diff refers to entity {entity} which has no counterpart in the entity map")]
+    UnmappedEntity {
+        /// The entity in the recorded world.
+        entity: Entity,
+    },
+}
+
+impl WorldDiff {
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.spawned.is_empty() && self.despawned.is_empty() && self.entities.is_empty()
+    }
+
+    /// Applies this diff to `world`, using the [`AppTypeRegistry`] resource of `world`.
+    ///
+    /// `entity_map` maps entities of the recorded world to entities of `world`. Spawned entities are added to it,
+    /// and despawned ones are removed from it, so the same map should be kept across successive diffs.
+    /// Entity references inside component values are mapped with it as well.
+    pub fn apply(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) -> Result<(), WorldDiffError> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        for &entity in &self.spawned {
+            entity_map
+                .entry(entity)
+                .or_insert_with(|| world.spawn_empty().id());
+        }
+
+        for entity_diff in &self.entities {
+            let &target =
+                entity_map
+                    .get(&entity_diff.entity)
+                    .ok_or(WorldDiffError::UnmappedEntity {
+                        entity: entity_diff.entity,
+                    })?;
+            if world.get_entity(target).is_err() {
+                // The entity may already have been despawned along with a related entity.
+                continue;
+            }
+
+            for type_path in &entity_diff.removed {
+                let reflect_component = registry
+                    .get_with_type_path(type_path)
+                    .and_then(TypeRegistration::data::<ReflectComponent>)
+                    .ok_or_else(|| WorldDiffError::UnregisteredComponent {
+                        type_path: type_path.clone(),
+                    })?;
+                reflect_component.remove(&mut world.entity_mut(target));
+            }
+
+            for value in entity_diff.inserted.iter().chain(&entity_diff.mutated) {
+                let type_info = value.get_represented_type_info().ok_or_else(|| {
+                    WorldDiffError::NoRepresentedType {
+                        type_path: value.reflect_type_path().into(),
+                    }
+                })?;
+                let reflect_component = registry
+                    .get(type_info.type_id())
+                    .and_then(TypeRegistration::data::<ReflectComponent>)
+                    .ok_or_else(|| WorldDiffError::UnregisteredComponent {
+                        type_path: type_info.type_path().into(),
+                    })?;
+
+                SceneEntityMapper::world_scope(entity_map, world, |world, mapper| {
+                    reflect_component.apply_or_insert_mapped(
+                        &mut world.entity_mut(target),
+                        value.as_partial_reflect(),
+                        &registry,
+                        mapper,
+                        RelationshipHookMode::Skip,
+                    );
+                });
+            }
+        }
+
+        for entity in &self.despawned {
+            if let Some(target) = entity_map.remove(entity) {
+                world.try_despawn(target).ok();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WorldDiffRecorder;
+    use crate::{
+        component::Component,
+        entity::{Entity, EntityHashMap},
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    };
+    use alloc::{string::ToString, vec};
+    use bevy_reflect::{Reflect, TypePath};
+
+    #[derive(Component, Reflect, Clone, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct A(u32);
+
+    #[derive(Component, Reflect, Clone, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct B(#[entities] Entity);
+
+    fn world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<A>();
+            registry.register::<B>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn records_changes_between_ticks() {
+        let mut world = world();
+        let kept = world.spawn(A(0)).id();
+        let mutated = world.spawn(A(1)).id();
+        let stripped = world.spawn((A(2), B(kept))).id();
+        let despawned = world.spawn(A(3)).id();
+        let mut recorder = WorldDiffRecorder::new(&mut world);
+
+        world.get_mut::<A>(mutated).unwrap().0 = 10;
+        world.entity_mut(stripped).remove::<B>();
+        world.despawn(despawned);
+        let spawned = world.spawn(A(4)).id();
+        world.entity_mut(kept).insert(B(spawned));
+
+        let diff = recorder.record(&mut world);
+        assert_eq!(diff.spawned, vec![spawned]);
+        assert_eq!(diff.despawned, vec![despawned]);
+        let changes = |entity| {
+            diff.entities
+                .iter()
+                .find(|entity_diff| entity_diff.entity == entity)
+                .unwrap()
+        };
+        assert_eq!(diff.entities.len(), 4);
+        assert_eq!(changes(kept).inserted.len(), 1);
+        assert_eq!(changes(mutated).mutated.len(), 1);
+        assert_eq!(changes(stripped).removed, vec![B::type_path().to_string()]);
+        assert_eq!(changes(spawned).inserted.len(), 1);
+
+        assert!(recorder.record(&mut world).is_empty());
+    }
+
+    #[test]
+    fn apply_diff_to_other_world() {
+        let mut world = world();
+        let mut other = World::new();
+        other.insert_resource(world.resource::<AppTypeRegistry>().clone());
+        let mut entity_map = EntityHashMap::default();
+        let mut recorder = WorldDiffRecorder::new(&mut world);
+
+        let a = world.spawn(A(1)).id();
+        let b = world.spawn((A(2), B(a))).id();
+        recorder
+            .record(&mut world)
+            .apply(&mut other, &mut entity_map)
+            .unwrap();
+        let (other_a, other_b) = (entity_map[&a], entity_map[&b]);
+        assert_eq!(other.get::<A>(other_a), Some(&A(1)));
+        assert_eq!(other.get::<B>(other_b), Some(&B(other_a)));
+
+        world.get_mut::<A>(a).unwrap().0 = 5;
+        world.entity_mut(b).remove::<B>();
+        world.despawn(a);
+        let c = world.spawn(A(3)).id();
+        recorder
+            .record(&mut world)
+            .apply(&mut other, &mut entity_map)
+            .unwrap();
+        assert!(other.get_entity(other_a).is_err());
+        assert!(!other.entity(other_b).contains::<B>());
+        assert_eq!(other.get::<A>(entity_map[&c]), Some(&A(3)));
+        assert!(!entity_map.contains_key(&a));
+    }
+}
@@ -0,0 +1,526 @@
+//! `serde` serialization and deserialization implementation for [`WorldDiff`].
+
+use super::{EntityDiff, WorldDiff};
+use crate::entity::Entity;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use bevy_platform::collections::HashSet;
+use bevy_reflect::{
+    serde::{TypeRegistrationDeserializer, TypedReflectDeserializer, TypedReflectSerializer},
+    PartialReflect, ReflectFromReflect, TypeInfo, TypeRegistry,
+};
+use core::fmt::Formatter;
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+const DIFF_STRUCT: &str = "WorldDiff";
+const DIFF_SPAWNED: &str = "spawned";
+const DIFF_DESPAWNED: &str = "despawned";
+const DIFF_ENTITIES: &str = "entities";
+
+const ENTITY_STRUCT: &str = "EntityDiff";
+const ENTITY_INSERTED: &str = "inserted";
+const ENTITY_MUTATED: &str = "mutated";
+const ENTITY_REMOVED: &str = "removed";
+
+/// Serializer for a [`WorldDiff`].
+///
+/// Component values are serialized as maps of type path to value, like scenes are,
+/// so every component type of the diff must be registered in `registry`.
+pub struct WorldDiffSerializer<'a> {
+    /// The diff to serialize.
+    pub diff: &'a WorldDiff,
+    /// Type registry in which the component types of the diff are registered.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> WorldDiffSerializer<'a> {
+    /// Creates a new serializer from a [`WorldDiff`] and an associated [`TypeRegistry`].
+    pub fn new(diff: &'a WorldDiff, registry: &'a TypeRegistry) -> Self {
+        Self { diff, registry }
+    }
+}
+
+impl<'a> Serialize for WorldDiffSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(DIFF_STRUCT, 3)?;
+        state.serialize_field(DIFF_SPAWNED, &self.diff.spawned)?;
+        state.serialize_field(DIFF_DESPAWNED, &self.diff.despawned)?;
+        state.serialize_field(
+            DIFF_ENTITIES,
+            &EntityDiffsSerializer {
+                entities: &self.diff.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct EntityDiffsSerializer<'a> {
+    entities: &'a [EntityDiff],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EntityDiffsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(self.entities.len()))?;
+        for entity in self.entities {
+            state.serialize_entry(
+                &entity.entity,
+                &EntityDiffSerializer {
+                    entity,
+                    registry: self.registry,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+struct EntityDiffSerializer<'a> {
+    entity: &'a EntityDiff,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EntityDiffSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(ENTITY_STRUCT, 3)?;
+        state.serialize_field(
+            ENTITY_INSERTED,
+            &ComponentsSerializer {
+                entries: &self.entity.inserted,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            ENTITY_MUTATED,
+            &ComponentsSerializer {
+                entries: &self.entity.mutated,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(ENTITY_REMOVED, &self.entity.removed)?;
+        state.end()
+    }
+}
+
+/// Serializes reflected values as a map of type path to value, sorted by type path.
+struct ComponentsSerializer<'a> {
+    entries: &'a [Box<dyn PartialReflect>],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for ComponentsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let type_path = entry
+                    .get_represented_type_info()
+                    .map(TypeInfo::type_path)
+                    .ok_or_else(|| {
+                        serde::ser::Error::custom(format_args!(
+                            "diff value of type `{}` does not represent a concrete type",
+                            entry.reflect_type_path()
+                        ))
+                    })?;
+                Ok((type_path, entry.as_partial_reflect()))
+            })
+            .collect::<Result<Vec<_>, S::Error>>()?;
+        entries.sort_by_key(|(type_path, _)| *type_path);
+
+        let mut state = serializer.serialize_map(Some(entries.len()))?;
+        for (type_path, value) in entries {
+            state.serialize_entry(
+                type_path,
+                &TypedReflectSerializer::new(value, self.registry),
+            )?;
+        }
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum DiffField {
+    Spawned,
+    Despawned,
+    Entities,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum EntityField {
+    Inserted,
+    Mutated,
+    Removed,
+}
+
+/// Deserializer for a [`WorldDiff`] serialized with [`WorldDiffSerializer`].
+pub struct WorldDiffDeserializer<'a> {
+    /// Type registry in which the component types of the diff are registered.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for WorldDiffDeserializer<'a> {
+    type Value = WorldDiff;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            DIFF_STRUCT,
+            &[DIFF_SPAWNED, DIFF_DESPAWNED, DIFF_ENTITIES],
+            WorldDiffVisitor {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct WorldDiffVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for WorldDiffVisitor<'a> {
+    type Value = WorldDiff;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("world diff struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let spawned = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(DIFF_SPAWNED))?;
+        let despawned = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(DIFF_DESPAWNED))?;
+        let entities = seq
+            .next_element_seed(EntityDiffsDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(DIFF_ENTITIES))?;
+
+        Ok(WorldDiff {
+            spawned,
+            despawned,
+            entities,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut spawned = None;
+        let mut despawned = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                DiffField::Spawned => {
+                    if spawned.is_some() {
+                        return Err(Error::duplicate_field(DIFF_SPAWNED));
+                    }
+                    spawned = Some(map.next_value()?);
+                }
+                DiffField::Despawned => {
+                    if despawned.is_some() {
+                        return Err(Error::duplicate_field(DIFF_DESPAWNED));
+                    }
+                    despawned = Some(map.next_value()?);
+                }
+                DiffField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(DIFF_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(EntityDiffsDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        Ok(WorldDiff {
+            spawned: spawned.ok_or_else(|| Error::missing_field(DIFF_SPAWNED))?,
+            despawned: despawned.ok_or_else(|| Error::missing_field(DIFF_DESPAWNED))?,
+            entities: entities.ok_or_else(|| Error::missing_field(DIFF_ENTITIES))?,
+        })
+    }
+}
+
+struct EntityDiffsDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntityDiffsDeserializer<'a> {
+    type Value = Vec<EntityDiff>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(EntityDiffsVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct EntityDiffsVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EntityDiffsVisitor<'a> {
+    type Value = Vec<EntityDiff>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("map of entity diffs")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = map.next_key::<Entity>()? {
+            entities.push(map.next_value_seed(EntityDiffDeserializer {
+                entity,
+                registry: self.registry,
+            })?);
+        }
+        Ok(entities)
+    }
+}
+
+struct EntityDiffDeserializer<'a> {
+    entity: Entity,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntityDiffDeserializer<'a> {
+    type Value = EntityDiff;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            ENTITY_STRUCT,
+            &[ENTITY_INSERTED, ENTITY_MUTATED, ENTITY_REMOVED],
+            EntityDiffVisitor {
+                entity: self.entity,
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct EntityDiffVisitor<'a> {
+    entity: Entity,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EntityDiffVisitor<'a> {
+    type Value = EntityDiff;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("entity diff struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let inserted = seq
+            .next_element_seed(ComponentsDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(ENTITY_INSERTED))?;
+        let mutated = seq
+            .next_element_seed(ComponentsDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(ENTITY_MUTATED))?;
+        let removed = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(ENTITY_REMOVED))?;
+
+        Ok(EntityDiff {
+            entity: self.entity,
+            inserted,
+            mutated,
+            removed,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut inserted = None;
+        let mut mutated = None;
+        let mut removed: Option<Vec<String>> = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                EntityField::Inserted => {
+                    if inserted.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_INSERTED));
+                    }
+                    inserted = Some(map.next_value_seed(ComponentsDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                EntityField::Mutated => {
+                    if mutated.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_MUTATED));
+                    }
+                    mutated = Some(map.next_value_seed(ComponentsDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                EntityField::Removed => {
+                    if removed.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_REMOVED));
+                    }
+                    removed = Some(map.next_value()?);
+                }
+            }
+        }
+
+        Ok(EntityDiff {
+            entity: self.entity,
+            inserted: inserted.ok_or_else(|| Error::missing_field(ENTITY_INSERTED))?,
+            mutated: mutated.ok_or_else(|| Error::missing_field(ENTITY_MUTATED))?,
+            removed: removed.ok_or_else(|| Error::missing_field(ENTITY_REMOVED))?,
+        })
+    }
+}
+
+struct ComponentsDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ComponentsDeserializer<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ComponentsVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct ComponentsVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for ComponentsVisitor<'a> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("map of reflect types")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut added = <HashSet<_>>::default();
+        let mut entries = Vec::new();
+        while let Some(registration) =
+            map.next_key_seed(TypeRegistrationDeserializer::new(self.registry))?
+        {
+            if !added.insert(registration.type_id()) {
+                return Err(Error::custom(format_args!(
+                    "duplicate reflect type: `{}`",
+                    registration.type_info().type_path(),
+                )));
+            }
+
+            let value =
+                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?;
+
+            // Attempt to convert using FromReflect.
+            let value = registration
+                .data::<ReflectFromReflect>()
+                .and_then(|fr| fr.from_reflect(value.as_partial_reflect()))
+                .map(PartialReflect::into_partial_reflect)
+                .unwrap_or(value);
+
+            entries.push(value);
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WorldDiffDeserializer, WorldDiffSerializer};
+    use crate::{
+        component::Component,
+        entity::EntityHashMap,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::{World, WorldDiffRecorder},
+    };
+    use bevy_reflect::Reflect;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[test]
+    fn world_diff_roundtrip() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Health>();
+        let mut recorder = WorldDiffRecorder::new(&mut world);
+        let entity = world.spawn(Health(3)).id();
+        let diff = recorder.record(&mut world);
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let serialized = ron::ser::to_string(&WorldDiffSerializer::new(&diff, &registry)).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = WorldDiffDeserializer {
+            registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        assert_eq!(deserialized.spawned, diff.spawned);
+        assert_eq!(deserialized.entities[0].inserted.len(), 1);
+
+        let mut other = World::new();
+        other.insert_resource(world.resource::<AppTypeRegistry>().clone());
+        let mut entity_map = EntityHashMap::default();
+        deserialized.apply(&mut other, &mut entity_map).unwrap();
+        assert_eq!(other.get::<Health>(entity_map[&entity]), Some(&Health(3)));
+    }
+}
@@ -7,10 +7,15 @@
 
 pub(crate) mod command_queue;
 mod deferred_world;
+#[cfg(feature = "bevy_reflect")]
+mod diff;
 mod entity_access;
 mod entity_fetch;
 mod filtered_resource;
 mod identifier;
+#[cfg(feature = "bevy_reflect")]
+mod paging;
+mod snapshot;
 mod spawn_batch;
 
 pub mod error;
@@ -24,6 +29,8 @@ pub use crate::{
 };
 pub use bevy_ecs_macros::FromWorld;
 pub use deferred_world::DeferredWorld;
+#[cfg(feature = "bevy_reflect")]
+pub use diff::*;
 pub use entity_access::{
     ComponentEntry, DynamicComponentFetch, EntityMut, EntityMutExcept, EntityRef, EntityRefExcept,
     EntityWorldMut, FilteredEntityMut, FilteredEntityRef, OccupiedComponentEntry,
@@ -32,6 +39,9 @@ pub use entity_access::{
 pub use entity_fetch::{EntityFetcher, WorldEntityFetch};
 pub use filtered_resource::*;
 pub use identifier::WorldId;
+#[cfg(feature = "bevy_reflect")]
+pub use paging::*;
+pub use snapshot::{SnapshotFilter, WorldSnapshot};
 pub use spawn_batch::*;
 
 use crate::{
@@ -109,6 +119,8 @@ pub struct World {
     pub(crate) last_change_tick: Tick,
     pub(crate) last_check_tick: Tick,
     pub(crate) last_trigger_id: u32,
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) last_table_page_id: u32,
     pub(crate) command_queue: RawCommandQueue,
 }
 
@@ -130,6 +142,8 @@ impl Default for World {
             last_change_tick: Tick::new(0),
             last_check_tick: Tick::new(0),
             last_trigger_id: 0,
+            #[cfg(feature = "bevy_reflect")]
+            last_table_page_id: 0,
             command_queue: RawCommandQueue::new(),
             component_ids: ComponentIds::default(),
         };
@@ -0,0 +1,613 @@
+//! Moving the data of whole tables out of a [`World`] and back, to keep huge worlds within memory bounds.
+
+use crate::{
+    bundle::{BundleRemover, InsertMode},
+    change_detection::MaybeLocation,
+    component::{Component, ComponentId, ComponentInfo},
+    entity::Entity,
+    entity_disabling::DefaultQueryFilters,
+    error::BevyError,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    relationship::RelationshipHookMode,
+    storage::TableId,
+    world::{diff::clone_reflect_value, World},
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_platform::collections::HashMap;
+use bevy_ptr::OwningPtr;
+use bevy_reflect::{FromType, PartialReflect, Reflect, TypeRegistration};
+use bumpalo::Bump;
+use thiserror::Error;
+
+/// Identifies a [`TablePage`] written by [`World::page_out_table`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Hash)]
+pub struct TablePageId(u32);
+
+impl TablePageId {
+    /// Returns the raw value of this id, for example to name the file a page is written to.
+    pub fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Creates an id from the value returned by [`TablePageId::to_bits`].
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+}
+
+/// Marks an entity whose table components have been paged out with [`World::page_out_table`].
+///
+/// [`World::enable_table_paging`] registers this component as a disabling component,
+/// so paged-out entities are skipped by queries unless they explicitly request it,
+/// for example with [`Allow<PagedOut>`](crate::query::Allow) or [`With<PagedOut>`](crate::query::With).
+/// See [`entity_disabling`](crate::entity_disabling) for more info.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[component(immutable)]
+#[reflect(Component, Debug, Clone, PartialEq)]
+// This component is registered as a disabling component by World::enable_table_paging
+pub struct PagedOut {
+    page: TablePageId,
+}
+
+impl PagedOut {
+    /// Returns the page holding the components of this entity.
+    pub fn page(&self) -> TablePageId {
+        self.page
+    }
+}
+
+/// A component whose values can be paged out of a [`World`] as raw bytes, rather than through reflection.
+///
+/// Register [`ReflectPodComponent`] for the type, with `#[reflect(PodComponent)]`, to opt into it.
+///
+/// # Safety
+///
+/// The type must not contain any padding bytes, and every bit pattern of its size must be a valid value.
+pub unsafe trait PodComponent: Component + Copy {}
+
+/// Type data marking a [`PodComponent`], which [`World::page_out_table`] stores as raw bytes.
+#[derive(Clone)]
+pub struct ReflectPodComponent(());
+
+impl<T: PodComponent> FromType<T> for ReflectPodComponent {
+    fn from_type() -> Self {
+        Self(())
+    }
+}
+
+/// The values of the table components of some entities, written by [`World::page_out_table`].
+///
+/// Reflected values can be serialized with the serializers of `bevy_reflect`, and raw bytes as they are.
+pub struct TablePage {
+    /// The id of this page.
+    pub id: TablePageId,
+    /// The entities whose components are stored in this page.
+    pub entities: Vec<Entity>,
+    /// The values of each component, in the same order as [`TablePage::entities`].
+    pub columns: Vec<PagedColumn>,
+}
+
+/// The values of a single component of a [`TablePage`].
+pub struct PagedColumn {
+    /// The id of the component.
+    pub component: ComponentId,
+    /// The values of the component.
+    pub data: PagedColumnData,
+}
+
+/// The values of a [`PagedColumn`].
+pub enum PagedColumnData {
+    /// One reflected value per entity.
+    Reflect(Vec<Box<dyn PartialReflect>>),
+    /// The bytes of the values of a [`PodComponent`], one after the other.
+    Bytes(Vec<u8>),
+}
+
+/// A user-provided storage for the pages written by [`World::page_out_table`], for example on disk.
+pub trait TablePageStore {
+    /// Stores `page`, so it can be returned by [`TablePageStore::load`] later.
+    fn store(&mut self, page: TablePage) -> Result<(), BevyError>;
+
+    /// Takes back the page with the given id.
+    fn load(&mut self, id: TablePageId) -> Result<TablePage, BevyError>;
+}
+
+impl TablePageStore for HashMap<TablePageId, TablePage> {
+    fn store(&mut self, page: TablePage) -> Result<(), BevyError> {
+        self.insert(page.id, page);
+        Ok(())
+    }
+
+    fn load(&mut self, id: TablePageId) -> Result<TablePage, BevyError> {
+        self.remove(&id)
+            .ok_or_else(|| TablePagingError::MissingPage(id).into())
+    }
+}
+
+/// An error that occurs when paging a table in or out of a [`World`].
+#[derive(Error, Debug)]
+pub enum TablePagingError {
+    /// The table does not exist.
+    #[error("the table {0:?} does not exist")]
+    InvalidTable(TableId),
+    /// Paging has not been enabled with [`World::enable_table_paging`].
+    #[error("table paging is not enabled: call `World::enable_table_paging` first")]
+    Disabled,
+    /// The table holds entities that are already paged out.
+    #[error("the table {0:?} holds entities that are already paged out")]
+    AlreadyPagedOut(TableId),
+    /// The table holds a [`Relationship`](crate::relationship::Relationship) or a
+    /// [`RelationshipTarget`](crate::relationship::RelationshipTarget) component.
+    #[error("the component `{0}` can not be paged out: it is part of a relationship")]
+    Relationship(String),
+    /// A component can neither be reflected nor stored as bytes.
+    #[error(
+        "the component `{0}` can not be paged out: consider reflecting it with `#[reflect(Component)]`"
+    )]
+    Unpageable(String),
+    /// A column of a page does not match its component.
+    #[error("the values of the component {0:?} do not match the entities of the page")]
+    InvalidColumn(ComponentId),
+    /// The [`TablePageStore`] does not hold the page.
+    #[error("the page {0:?} could not be found")]
+    MissingPage(TablePageId),
+    /// The [`TablePageStore`] failed.
+    #[error("the page store failed: {0}")]
+    Store(BevyError),
+}
+
+/// How the values of a component are paged.
+enum PagingMode<'r> {
+    Bytes(usize),
+    Reflect(&'r TypeRegistration, &'r ReflectComponent),
+}
+
+fn paging_mode<'r>(
+    world: &World,
+    registry: &'r bevy_reflect::TypeRegistry,
+    component_id: ComponentId,
+) -> Result<PagingMode<'r>, TablePagingError> {
+    let info = world.components().get_info(component_id);
+    if let Some(info) = info
+        && info.relationship_accessor().is_some()
+    {
+        return Err(TablePagingError::Relationship(info.name().to_string()));
+    }
+    let registration = info
+        .and_then(ComponentInfo::type_id)
+        .and_then(|type_id| registry.get(type_id));
+    match registration {
+        Some(registration) if registration.contains::<ReflectPodComponent>() => {
+            // `info` exists since the component has a registration.
+            Ok(PagingMode::Bytes(info.unwrap().layout().size()))
+        }
+        Some(registration) => match registration.data::<ReflectComponent>() {
+            Some(reflect_component) => Ok(PagingMode::Reflect(registration, reflect_component)),
+            None => Err(TablePagingError::Unpageable(
+                registration.type_info().type_path().into(),
+            )),
+        },
+        None => Err(TablePagingError::Unpageable(
+            info.map(|info| info.name().to_string())
+                .unwrap_or_else(|| alloc::format!("{component_id:?}")),
+        )),
+    }
+}
+
+impl World {
+    /// Enables [`World::page_out_table`], registering [`PagedOut`] as a disabling component
+    /// so that queries skip paged-out entities.
+    ///
+    /// Like other [default query filters](crate::entity_disabling::DefaultQueryFilters), this only affects
+    /// queries created after it is called, so it should be called before the app starts.
+    /// Paging is opt-in so that worlds which don't use it don't pay for the extra filter.
+    pub fn enable_table_paging(&mut self) {
+        self.register_disabling_component::<PagedOut>();
+    }
+
+    /// Moves the components stored in the table `table_id` out of this world and into `store`,
+    /// returning the id of the written [`TablePage`].
+    ///
+    /// The entities of the table stay alive, with their sparse set components,
+    /// and gain a [`PagedOut`] component which excludes them from queries that don't request it.
+    /// Use [`World::page_in_table`] to bring the components back.
+    ///
+    /// Components are stored as raw bytes if they are a [`PodComponent`] registered in the [`AppTypeRegistry`]
+    /// with [`ReflectPodComponent`], and as reflected values if they are registered with [`ReflectComponent`].
+    /// The world is left untouched if a component can't be paged out, or if `store` fails.
+    ///
+    /// Paging out removes the components like [`EntityWorldMut::remove`](crate::world::EntityWorldMut::remove),
+    /// triggering their hooks and observers. Tables holding [`Relationship`](crate::relationship::Relationship)
+    /// or [`RelationshipTarget`](crate::relationship::RelationshipTarget) components can't be paged out,
+    /// since the other side of the relationship would point to an entity without its half.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::world::{TablePage, TablePageId};
+    /// # use bevy_platform::collections::HashMap;
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Tree(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.enable_table_paging();
+    /// world.init_resource::<AppTypeRegistry>();
+    /// world.resource::<AppTypeRegistry>().write().register::<Tree>();
+    /// let tree = world.spawn(Tree(3)).id();
+    /// let table = world.entity(tree).location().table_id;
+    ///
+    /// let mut store = HashMap::<TablePageId, TablePage>::default();
+    /// let page = world.page_out_table(table, &mut store).unwrap();
+    /// assert_eq!(world.query::<&Tree>().iter(&world).count(), 0);
+    ///
+    /// world.page_in_table(page, &mut store).unwrap();
+    /// assert_eq!(world.get::<Tree>(tree).unwrap().0, 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`AppTypeRegistry`] resource does not exist.
+    #[track_caller]
+    pub fn page_out_table(
+        &mut self,
+        table_id: TableId,
+        store: &mut dyn TablePageStore,
+    ) -> Result<TablePageId, TablePagingError> {
+        let caller = MaybeLocation::caller();
+        let paged_out_id = self.register_component::<PagedOut>();
+        if !self
+            .resource::<DefaultQueryFilters>()
+            .disabling_ids()
+            .any(|id| id == paged_out_id)
+        {
+            return Err(TablePagingError::Disabled);
+        }
+        let table = self
+            .storages
+            .tables
+            .get(table_id)
+            .ok_or(TablePagingError::InvalidTable(table_id))?;
+        if table.has_column(paged_out_id) {
+            return Err(TablePagingError::AlreadyPagedOut(table_id));
+        }
+        let entities = table.entities().to_vec();
+        let Some(&first) = entities.first() else {
+            return self.allocate_table_page_id(store, TablePage::default());
+        };
+        let components: Vec<ComponentId> =
+            self.entity(first).archetype().table_components().collect();
+
+        let registry = self.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let mut columns = Vec::with_capacity(components.len());
+        for &component in &components {
+            let data = match paging_mode(self, &registry, component)? {
+                PagingMode::Bytes(size) => {
+                    let mut bytes = Vec::with_capacity(size * entities.len());
+                    for &entity in &entities {
+                        let value = self.entity(entity).get_by_id(component).unwrap();
+                        // SAFETY: `value` points to a `PodComponent` of `size` bytes, which has no padding.
+                        bytes.extend_from_slice(unsafe {
+                            core::slice::from_raw_parts(value.as_ptr(), size)
+                        });
+                    }
+                    PagedColumnData::Bytes(bytes)
+                }
+                PagingMode::Reflect(registration, reflect_component) => PagedColumnData::Reflect(
+                    entities
+                        .iter()
+                        .map(|&entity| {
+                            let value = reflect_component.reflect(self.entity(entity)).unwrap();
+                            clone_reflect_value(value.as_partial_reflect(), registration)
+                        })
+                        .collect(),
+                ),
+            };
+            columns.push(PagedColumn { component, data });
+        }
+        drop(registry);
+
+        let page = TablePage {
+            id: TablePageId(0),
+            entities,
+            columns,
+        };
+        let entities = page.entities.clone();
+        let id = self.allocate_table_page_id(store, page)?;
+        for entity in entities {
+            let mut entity = self.entity_mut(entity);
+            entity.insert(PagedOut { page: id });
+            entity.remove_by_ids_with_caller(
+                &components,
+                caller,
+                RelationshipHookMode::Run,
+                BundleRemover::empty_pre_remove,
+            );
+        }
+        Ok(id)
+    }
+
+    fn allocate_table_page_id(
+        &mut self,
+        store: &mut dyn TablePageStore,
+        mut page: TablePage,
+    ) -> Result<TablePageId, TablePagingError> {
+        let id = TablePageId(self.last_table_page_id.wrapping_add(1));
+        page.id = id;
+        store.store(page).map_err(TablePagingError::Store)?;
+        self.last_table_page_id = id.0;
+        Ok(id)
+    }
+
+    /// Loads the page `id` from `store` and moves its components back into the entities it was paged out from,
+    /// removing their [`PagedOut`] component.
+    ///
+    /// Entities which have been despawned or paged out into another page since are skipped.
+    /// Paging in inserts the components like [`EntityWorldMut::insert`](crate::world::EntityWorldMut::insert),
+    /// triggering their hooks and observers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`AppTypeRegistry`] resource does not exist.
+    #[track_caller]
+    pub fn page_in_table(
+        &mut self,
+        id: TablePageId,
+        store: &mut dyn TablePageStore,
+    ) -> Result<(), TablePagingError> {
+        let caller = MaybeLocation::caller();
+        let page = store.load(id).map_err(TablePagingError::Store)?;
+        let registry = self.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let mut columns = Vec::with_capacity(page.columns.len());
+        for column in &page.columns {
+            let mode = paging_mode(self, &registry, column.component)?;
+            let valid = match (&mode, &column.data) {
+                (PagingMode::Bytes(size), PagedColumnData::Bytes(bytes)) => {
+                    bytes.len() == size * page.entities.len()
+                }
+                (PagingMode::Reflect(registration, _), PagedColumnData::Reflect(values)) => {
+                    values.len() == page.entities.len()
+                        && values.iter().all(|value| {
+                            value
+                                .get_represented_type_info()
+                                .map(bevy_reflect::TypeInfo::type_id)
+                                == Some(registration.type_id())
+                        })
+                }
+                _ => false,
+            };
+            if !valid {
+                return Err(TablePagingError::InvalidColumn(column.component));
+            }
+            columns.push((column, mode));
+        }
+
+        let scratch = Bump::new();
+        for (row, &entity) in page.entities.iter().enumerate() {
+            let Ok(mut entity) = self.get_entity_mut(entity) else {
+                continue;
+            };
+            if entity.get::<PagedOut>().map(PagedOut::page) != Some(id) {
+                continue;
+            }
+            for (column, mode) in &columns {
+                match (mode, &column.data) {
+                    (PagingMode::Bytes(size), PagedColumnData::Bytes(bytes)) => {
+                        // `paging_mode` found a registration, so the component info exists.
+                        let layout = entity
+                            .world()
+                            .components()
+                            .get_info(column.component)
+                            .unwrap()
+                            .layout();
+                        let value = scratch.alloc_layout(layout);
+                        // SAFETY: `value` is valid for `size` bytes, and `bytes` holds `size` bytes for each entity.
+                        unsafe {
+                            core::ptr::copy_nonoverlapping(
+                                bytes[row * size..].as_ptr(),
+                                value.as_ptr(),
+                                *size,
+                            );
+                        }
+                        // SAFETY:
+                        // - `value` is aligned for the component and holds a valid value of it,
+                        //   since every bit pattern is valid for a `PodComponent`.
+                        // - `PodComponent`s are `Copy`, so `value` can be read any number of times.
+                        unsafe {
+                            entity.insert_by_id_with_caller(
+                                column.component,
+                                OwningPtr::new(value),
+                                InsertMode::Replace,
+                                caller,
+                                RelationshipHookMode::Run,
+                            );
+                        }
+                    }
+                    (
+                        PagingMode::Reflect(_, reflect_component),
+                        PagedColumnData::Reflect(values),
+                    ) => {
+                        reflect_component.apply_or_insert_mapped(
+                            &mut entity,
+                            &*values[row],
+                            &registry,
+                            &mut (),
+                            RelationshipHookMode::Run,
+                        );
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            entity.remove::<PagedOut>();
+        }
+        Ok(())
+    }
+}
+
+impl Default for TablePage {
+    fn default() -> Self {
+        Self {
+            id: TablePageId(0),
+            entities: Vec::new(),
+            columns: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        PagedOut, PodComponent, ReflectPodComponent, TablePage, TablePageId, TablePagingError,
+    };
+    use crate::{
+        component::Component,
+        hierarchy::{ChildOf, Children},
+        query::{Allow, With},
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    };
+    use bevy_platform::collections::HashMap;
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Clone, Copy, PartialEq, Debug)]
+    #[reflect(Component, PodComponent)]
+    struct Position(u32, u32);
+
+    // SAFETY: `Position` has no padding and every bit pattern is valid.
+    unsafe impl PodComponent for Position {}
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Name(alloc::string::String);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Marker;
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.enable_table_paging();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Position>();
+            registry.register::<Name>();
+            registry.register::<ChildOf>();
+            registry.register::<Children>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn page_out_and_in() {
+        let mut world = setup();
+        let a = world.spawn((Position(1, 2), Name("a".into()), Marker)).id();
+        let b = world.spawn((Position(3, 4), Name("b".into()))).id();
+        let other = world.spawn(Position(5, 6)).id();
+        let table = world.entity(a).location().table_id;
+
+        let mut store = HashMap::<TablePageId, TablePage>::default();
+        let page = world.page_out_table(table, &mut store).unwrap();
+        assert!(world.get::<Position>(a).is_none());
+        assert!(world.get::<Marker>(a).is_some());
+        assert_eq!(world.get::<PagedOut>(b).map(PagedOut::page), Some(page));
+        assert_eq!(world.storages.tables[table].entity_count(), 0);
+        assert_eq!(
+            world
+                .query::<&Position>()
+                .iter(&world)
+                .copied()
+                .collect::<alloc::vec::Vec<_>>(),
+            [Position(5, 6)]
+        );
+        assert_eq!(world.query::<&Marker>().iter(&world).count(), 0);
+        assert_eq!(
+            world
+                .query_filtered::<&Marker, Allow<PagedOut>>()
+                .iter(&world)
+                .count(),
+            1
+        );
+        assert_eq!(
+            world
+                .query_filtered::<(), With<PagedOut>>()
+                .iter(&world)
+                .count(),
+            2
+        );
+
+        world.despawn(b);
+        world.page_in_table(page, &mut store).unwrap();
+        assert!(store.is_empty());
+        assert_eq!(world.get::<Position>(a), Some(&Position(1, 2)));
+        assert_eq!(world.get::<Name>(a), Some(&Name("a".into())));
+        assert!(world.get::<PagedOut>(a).is_none());
+        assert_eq!(world.get::<Position>(other), Some(&Position(5, 6)));
+        assert_eq!(world.query::<&Position>().iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn paging_requires_opt_in() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        let entity = world.spawn_empty().id();
+        let table = world.entity(entity).location().table_id;
+
+        let mut store = HashMap::<TablePageId, TablePage>::default();
+        assert!(matches!(
+            world.page_out_table(table, &mut store),
+            Err(TablePagingError::Disabled)
+        ));
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn relationships_can_not_be_paged_out() {
+        let mut world = setup();
+        let parent = world.spawn(Position(0, 0)).id();
+        let child = world.spawn((Position(1, 1), ChildOf(parent))).id();
+
+        let mut store = HashMap::<TablePageId, TablePage>::default();
+        for entity in [parent, child] {
+            let table = world.entity(entity).location().table_id;
+            assert!(matches!(
+                world.page_out_table(table, &mut store),
+                Err(TablePagingError::Relationship(_))
+            ));
+        }
+        assert!(store.is_empty());
+        assert_eq!(world.get::<ChildOf>(child), Some(&ChildOf(parent)));
+    }
+
+    #[test]
+    fn unpageable_components_leave_the_world_untouched() {
+        #[derive(Component)]
+        struct Opaque;
+
+        let mut world = setup();
+        let entity = world.spawn((Position(0, 0), Opaque)).id();
+        let table = world.entity(entity).location().table_id;
+
+        let mut store = HashMap::<TablePageId, TablePage>::default();
+        assert!(world.page_out_table(table, &mut store).is_err());
+        assert!(store.is_empty());
+        assert!(world.get::<Position>(entity).is_some());
+
+        let paged_out_table = {
+            let other = world.spawn(Position(1, 1)).id();
+            let table = world.entity(other).location().table_id;
+            world.page_out_table(table, &mut store).unwrap();
+            world.entity(other).location().table_id
+        };
+        assert!(world.page_out_table(paged_out_table, &mut store).is_err());
+    }
+}
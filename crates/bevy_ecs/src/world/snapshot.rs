@@ -0,0 +1,674 @@
+//! Capturing and restoring parts of a [`World`], for example to roll back a simulation.
+
+use crate::{
+    archetype::ArchetypeEntity,
+    change_detection::{MaybeLocation, Tick},
+    component::{Component, ComponentCloneBehavior, ComponentId},
+    entity::{
+        BundleScratch, ComponentCloneCtx, Entity, EntityCloner, EntityGeneration, EntityHashSet,
+        EntityIndex, SourceComponent,
+    },
+    relationship::RelationshipHookMode,
+    resource::Resource,
+    storage::ResourceData,
+    world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
+};
+use alloc::vec::Vec;
+use bevy_platform::collections::HashSet;
+use bevy_ptr::{OwningPtr, Ptr};
+use bumpalo::Bump;
+use core::{mem::ManuallyDrop, ptr::NonNull};
+
+/// Selects the components and resources captured by [`World::snapshot`].
+///
+/// Every entity that has at least one of the allowed components is part of the snapshot.
+/// Component values are cloned with the same [clone behaviors](ComponentCloneBehavior) used by [`EntityCloner`],
+/// so components need to implement [`Clone`] or be reflected with the `AppTypeRegistry` to be captured.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::SnapshotFilter;
+/// #[derive(Component, Clone)]
+/// struct Position(f32);
+///
+/// #[derive(Resource, Clone)]
+/// struct Score(u32);
+///
+/// let filter = SnapshotFilter::new()
+///     .allow::<Position>()
+///     .allow_resource::<Score>();
+/// ```
+#[derive(Clone, Default)]
+pub struct SnapshotFilter {
+    components: Vec<SnapshotId>,
+    resources: Vec<(SnapshotId, ComponentCloneBehavior)>,
+}
+
+/// A component or resource that has been allowed by a [`SnapshotFilter`].
+#[derive(Clone, Copy)]
+enum SnapshotId {
+    Type(fn(&mut World) -> ComponentId),
+    Id(ComponentId),
+}
+
+impl SnapshotFilter {
+    /// Creates a filter that does not capture anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captures the component `C` and the entities that have it.
+    pub fn allow<C: Component>(mut self) -> Self {
+        self.components
+            .push(SnapshotId::Type(World::register_component::<C>));
+        self
+    }
+
+    /// Captures the component with the given [`ComponentId`] and the entities that have it.
+    pub fn allow_by_id(mut self, id: ComponentId) -> Self {
+        self.components.push(SnapshotId::Id(id));
+        self
+    }
+
+    /// Captures the resource `R`, cloning it with its [`Clone`] implementation.
+    pub fn allow_resource<R: Resource + Clone>(mut self) -> Self {
+        self.resources.push((
+            SnapshotId::Type(World::register_resource::<R>),
+            ComponentCloneBehavior::Custom(resource_clone_via_clone::<R>),
+        ));
+        self
+    }
+
+    /// Captures the resource with the given [`ComponentId`].
+    ///
+    /// The resource is cloned with the default clone behavior, which uses reflection when the `bevy_reflect` feature is enabled.
+    pub fn allow_resource_by_id(mut self, id: ComponentId) -> Self {
+        self.resources
+            .push((SnapshotId::Id(id), ComponentCloneBehavior::Default));
+        self
+    }
+
+    fn component_ids(&self, world: &mut World) -> Vec<ComponentId> {
+        let mut ids: Vec<ComponentId> = self
+            .components
+            .iter()
+            .filter_map(|id| id.resolve(world))
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    fn resource_ids(&self, world: &mut World) -> Vec<(ComponentId, ComponentCloneBehavior)> {
+        let mut ids: Vec<_> = self
+            .resources
+            .iter()
+            .filter_map(|(id, behavior)| Some((id.resolve(world)?, behavior.clone())))
+            .collect();
+        ids.sort_unstable_by_key(|(id, _)| *id);
+        ids.dedup_by_key(|(id, _)| *id);
+        ids
+    }
+}
+
+impl SnapshotId {
+    fn resolve(self, world: &mut World) -> Option<ComponentId> {
+        match self {
+            SnapshotId::Type(register) => Some(register(world)),
+            SnapshotId::Id(id) => world.components().get_info(id).map(|_| id),
+        }
+    }
+}
+
+/// Clones a resource through its [`Clone`] implementation.
+fn resource_clone_via_clone<R: Resource + Clone>(
+    source: &SourceComponent,
+    ctx: &mut ComponentCloneCtx,
+) {
+    // SAFETY: This handler is only registered for the `ComponentId` of `R`.
+    let value = ManuallyDrop::new(unsafe { source.ptr().deref::<R>() }.clone());
+    // SAFETY: `value` is an owned `R` that is never dropped here, so the written copy takes ownership of it.
+    unsafe { ctx.write_target_component_ptr(Ptr::from(&*value)) };
+}
+
+/// A cloned component or resource value owned by a [`WorldSnapshot`].
+struct SnapshotValue {
+    ptr: NonNull<u8>,
+    drop: Option<unsafe fn(OwningPtr<'_>)>,
+}
+
+struct EntitySnapshot {
+    entity: Entity,
+    /// The allowed components the entity had, along with their value if it could be cloned.
+    components: Vec<(ComponentId, Option<SnapshotValue>)>,
+}
+
+/// A copy of the entities, components, resources and entity allocator state of a [`World`],
+/// captured by [`World::snapshot`] and applied again with [`World::restore`].
+///
+/// A snapshot can be restored any number of times, which makes it suitable for rollback networking:
+/// capture a snapshot every frame, restore an old one when a late input arrives and re-simulate from there.
+pub struct WorldSnapshot {
+    world_id: WorldId,
+    tick: Tick,
+    component_ids: Vec<ComponentId>,
+    resource_ids: Vec<(ComponentId, ComponentCloneBehavior)>,
+    entities: Vec<EntitySnapshot>,
+    resources: Vec<(ComponentId, Option<SnapshotValue>)>,
+    free_entities: Vec<Entity>,
+    next_entity_index: u32,
+    /// Owns the memory of every [`SnapshotValue`].
+    values: Bump,
+}
+
+// SAFETY: All captured values are components and resources, which are `Send`.
+unsafe impl Send for WorldSnapshot {}
+
+// SAFETY: `&WorldSnapshot` only gives out shared access to the captured values, which are `Sync`,
+// and never allocates in `values`.
+unsafe impl Sync for WorldSnapshot {}
+
+impl WorldSnapshot {
+    /// Returns the change tick of the [`World`] when this snapshot was captured.
+    pub fn tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Returns the entities captured by this snapshot, sorted by their id.
+    pub fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + '_ {
+        self.entities.iter().map(|snapshot| snapshot.entity)
+    }
+
+    /// Returns `true` if `entity` was captured by this snapshot.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.find(entity).is_some()
+    }
+
+    /// Returns the number of entities captured by this snapshot.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if this snapshot did not capture any entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn find(&self, entity: Entity) -> Option<&EntitySnapshot> {
+        self.entities
+            .binary_search_by_key(&entity, |snapshot| snapshot.entity)
+            .ok()
+            .map(|index| &self.entities[index])
+    }
+}
+
+impl Drop for WorldSnapshot {
+    fn drop(&mut self) {
+        let entity_values = self
+            .entities
+            .iter_mut()
+            .flat_map(|snapshot| snapshot.components.iter_mut());
+        for (_, value) in entity_values.chain(self.resources.iter_mut()) {
+            if let Some(SnapshotValue {
+                ptr,
+                drop: Some(drop),
+            }) = value.take()
+            {
+                // SAFETY: `ptr` points to an owned value matching `drop`, which is only dropped here.
+                unsafe { drop(OwningPtr::new(ptr)) };
+            }
+        }
+        self.values.reset();
+    }
+}
+
+/// Clones `source` into memory owned by `values`.
+///
+/// # Safety
+/// - `component` must be registered in `world`.
+/// - `source` must point to a valid value of the type represented by `component`.
+unsafe fn capture_value(
+    cloner: &mut EntityCloner,
+    world: UnsafeWorldCell<'_>,
+    entity: Entity,
+    component: ComponentId,
+    source: Ptr<'_>,
+    values: &Bump,
+) -> Option<SnapshotValue> {
+    let mut scratch = BundleScratch::with_capacity(1);
+    // SAFETY: Ensured by the caller.
+    let written = unsafe {
+        cloner.clone_component_value(world, entity, component, source, values, &mut scratch)
+    };
+    let ptr = scratch.component_ptrs.pop().filter(|_| written)?;
+    Some(SnapshotValue {
+        ptr: ptr.into(),
+        // SAFETY: Ensured by the caller.
+        drop: unsafe { world.components().get_info_unchecked(component) }.drop(),
+    })
+}
+
+impl World {
+    /// Captures the components and resources allowed by `filter`, along with the state of the entity allocator.
+    ///
+    /// Use [`World::restore`] to bring this world back to the captured state.
+    /// Components and resources that can not be cloned are left untouched by restoring.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::world::SnapshotFilter;
+    /// #[derive(Component, Clone, PartialEq, Debug)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn(Health(10)).id();
+    ///
+    /// let snapshot = world.snapshot(&SnapshotFilter::new().allow::<Health>());
+    ///
+    /// world.despawn(player);
+    /// world.spawn(Health(5));
+    ///
+    /// world.restore(&snapshot);
+    /// assert_eq!(world.get::<Health>(player), Some(&Health(10)));
+    /// assert_eq!(world.query::<&Health>().iter(&world).count(), 1);
+    /// ```
+    pub fn snapshot(&mut self, filter: &SnapshotFilter) -> WorldSnapshot {
+        self.flush();
+        let component_ids = filter.component_ids(self);
+        let resource_ids = filter.resource_ids(self);
+        let (free_entities, next_entity_index) = self.entity_allocator.parts();
+        let mut cloner = snapshot_cloner(self, &resource_ids);
+
+        let mut tracked: Vec<Entity> = self
+            .archetypes
+            .iter()
+            .filter(|archetype| component_ids.iter().any(|&id| archetype.contains(id)))
+            .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+            .collect();
+        tracked.sort_unstable();
+
+        let values = Bump::new();
+        let world = self.as_unsafe_world_cell_readonly();
+        let entities = tracked
+            .into_iter()
+            .map(|entity| {
+                let cell = world
+                    .get_entity(entity)
+                    .expect("Entities in archetypes are spawned.");
+                let components = component_ids
+                    .iter()
+                    .filter_map(|&id| {
+                        // SAFETY: We have exclusive access to the world and only read from it.
+                        let source = unsafe { cell.get_by_id(id) }?;
+                        // SAFETY: `id` is registered and `source` is the entity's value of it.
+                        let value = unsafe {
+                            capture_value(&mut cloner, world, entity, id, source, &values)
+                        };
+                        Some((id, value))
+                    })
+                    .collect();
+                EntitySnapshot { entity, components }
+            })
+            .collect();
+
+        let resources = resource_ids
+            .iter()
+            .map(|&(id, _)| {
+                // SAFETY: We have exclusive access to the world and only read from it.
+                let value = unsafe { world.storages() }
+                    .resources
+                    .get(id)
+                    .and_then(ResourceData::get_data)
+                    .map(|source| {
+                        // SAFETY: `id` is registered and `source` is the resource's value.
+                        unsafe {
+                            capture_value(
+                                &mut cloner,
+                                world,
+                                Entity::PLACEHOLDER,
+                                id,
+                                source,
+                                &values,
+                            )
+                        }
+                    });
+                (id, value)
+            })
+            .filter_map(|(id, value)| match value {
+                // The resource is missing.
+                None => Some((id, None)),
+                // The resource can not be cloned.
+                Some(None) => None,
+                Some(value) => Some((id, value)),
+            })
+            .collect();
+        // Deferred clones write into the world and can not be captured.
+        cloner.discard_deferred_clones();
+
+        WorldSnapshot {
+            world_id: self.id(),
+            tick: self.read_change_tick(),
+            component_ids,
+            resource_ids,
+            entities,
+            resources,
+            free_entities,
+            next_entity_index,
+            values,
+        }
+    }
+
+    /// Restores the components, resources and entity allocator state captured by [`World::snapshot`].
+    ///
+    /// - Entities that have an allowed component but are not part of the snapshot are despawned.
+    /// - Entities of the snapshot that have been despawned are spawned again with the same [`Entity`] id.
+    ///   Any entity occupying the [`EntityIndex`](crate::entity::EntityIndex) of such an entity is despawned.
+    /// - Allowed components and resources are inserted again, or removed if they were missing when the snapshot was taken.
+    /// - Entities are allocated in the same order as they would have been after the snapshot was taken,
+    ///   as long as entities outside of the snapshot don't hold on to the same ids.
+    ///
+    /// Restored values are inserted like any other value, so they trigger hooks and observers and
+    /// show up as added and changed to systems.
+    ///
+    /// All [`RemoteAllocator`](crate::entity::RemoteAllocator)s built before restoring are disconnected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `snapshot` was captured from a different [`World`].
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        assert_eq!(
+            self.id(),
+            snapshot.world_id,
+            "A `WorldSnapshot` can only be restored to the world it was captured from."
+        );
+        self.flush();
+
+        // Despawn entities that did not exist when the snapshot was taken.
+        let snapshot_entities: EntityHashSet = snapshot.entities().collect();
+        let mut to_despawn: Vec<Entity> = self
+            .archetypes
+            .iter()
+            .filter(|archetype| {
+                snapshot
+                    .component_ids
+                    .iter()
+                    .any(|&id| archetype.contains(id))
+            })
+            .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+            .filter(|entity| !snapshot_entities.contains(entity))
+            .collect();
+        to_despawn.extend(snapshot.entities().filter_map(|entity| {
+            let current = self.entities.resolve_from_index(entity.index());
+            (current != entity && self.entities.is_index_spawned(entity.index())).then_some(current)
+        }));
+        for entity in to_despawn {
+            // The entity may already have been despawned along with a related entity.
+            let _ = self.try_despawn(entity);
+        }
+
+        // Spawn the entities that have been despawned since the snapshot was taken.
+        for entity in snapshot.entities() {
+            if self.entities.contains_spawned(entity) {
+                continue;
+            }
+            let current = self.entities.resolve_from_index(entity.index());
+            if self.entities.is_index_spawned(entity.index()) {
+                // Something spawned in this index while despawning.
+                let _ = self.try_despawn(current);
+            }
+            // SAFETY: The index is not spawned.
+            unsafe {
+                self.entities
+                    .set_generation(entity.index(), entity.generation());
+            }
+            self.spawn_empty_at(entity)
+                .expect("The entity index has just been despawned.");
+        }
+
+        self.restore_entity_allocator(snapshot);
+
+        let mut cloner = snapshot_cloner(self, &snapshot.resource_ids);
+        for entity_snapshot in &snapshot.entities {
+            let entity = entity_snapshot.entity;
+            let Ok(mut entity_mut) = self.get_entity_mut(entity) else {
+                // Despawned by a hook or observer of a previously restored entity.
+                continue;
+            };
+            let missing: Vec<ComponentId> = snapshot
+                .component_ids
+                .iter()
+                .copied()
+                .filter(|&id| {
+                    entity_mut.contains_id(id)
+                        && !entity_snapshot
+                            .components
+                            .iter()
+                            .any(|(captured, _)| *captured == id)
+                })
+                .collect();
+            if !missing.is_empty() {
+                entity_mut.remove_by_ids(&missing);
+            }
+
+            let scratch_allocator = Bump::new();
+            let mut scratch = BundleScratch::with_capacity(entity_snapshot.components.len());
+            let world = self.as_unsafe_world_cell();
+            for (id, value) in &entity_snapshot.components {
+                let Some(value) = value else {
+                    continue;
+                };
+                // SAFETY:
+                // - The snapshot was captured from this world, so `id` is registered.
+                // - `value` holds a valid value of `id`.
+                unsafe {
+                    cloner.clone_component_value(
+                        world,
+                        entity,
+                        *id,
+                        Ptr::new(value.ptr),
+                        &scratch_allocator,
+                        &mut scratch,
+                    );
+                }
+            }
+            if self.entities.contains_spawned(entity) {
+                // SAFETY: All component ids in the scratch come from this world.
+                unsafe { scratch.write(self, entity, RelationshipHookMode::Run) };
+            }
+            cloner.apply_deferred_clones(self);
+        }
+
+        for (id, value) in &snapshot.resources {
+            let Some(value) = value else {
+                self.remove_resource_by_id(*id);
+                continue;
+            };
+            let scratch_allocator = Bump::new();
+            let mut scratch = BundleScratch::with_capacity(1);
+            // SAFETY:
+            // - The snapshot was captured from this world, so `id` is registered.
+            // - `value` holds a valid value of `id`.
+            let written = unsafe {
+                cloner.clone_component_value(
+                    self.as_unsafe_world_cell(),
+                    Entity::PLACEHOLDER,
+                    *id,
+                    Ptr::new(value.ptr),
+                    &scratch_allocator,
+                    &mut scratch,
+                )
+            };
+            if let Some(ptr) = scratch.component_ptrs.pop().filter(|_| written) {
+                // SAFETY: The handler wrote a valid, owned value of `id`.
+                unsafe { self.insert_resource_by_id(*id, ptr.promote(), MaybeLocation::caller()) };
+            }
+        }
+        cloner.discard_deferred_clones();
+    }
+
+    /// Rebuilds the entity allocator so it hands out entities in the same order as it would have after the snapshot was taken.
+    fn restore_entity_allocator(&mut self, snapshot: &WorldSnapshot) {
+        let (_, current_next_index) = self.entity_allocator.parts();
+        let indices = |range: core::ops::Range<u32>| range.filter_map(EntityIndex::from_raw_u32);
+        // Fresh indices handed out since the snapshot can only be reused if they are not spawned.
+        let next_index = indices(snapshot.next_entity_index..current_next_index)
+            .rev()
+            .find(|&index| self.entities.is_index_spawned(index))
+            .map_or(snapshot.next_entity_index, |index| index.index() + 1);
+        // Indices that will be handed out fresh again start over at the first generation,
+        // just like they did after the snapshot was taken.
+        for index in indices(next_index..current_next_index) {
+            // SAFETY: No index after `next_index` is spawned.
+            unsafe {
+                self.entities.set_generation(index, EntityGeneration::FIRST);
+            }
+        }
+
+        // Unspawned indices the snapshot doesn't know about go to the bottom of the free list,
+        // so they are only reused once the snapshot's free list is exhausted.
+        let snapshot_free: HashSet<EntityIndex> = snapshot
+            .free_entities
+            .iter()
+            .map(|entity| entity.index())
+            .collect();
+        let mut free: Vec<Entity> = indices(0..next_index)
+            .filter(|index| {
+                !self.entities.is_index_spawned(*index) && !snapshot_free.contains(index)
+            })
+            .map(|index| self.entities.resolve_from_index(index))
+            .collect();
+        for &entity in &snapshot.free_entities {
+            if self.entities.is_index_spawned(entity.index()) {
+                continue;
+            }
+            // SAFETY: The index is not spawned.
+            free.push(unsafe {
+                self.entities
+                    .set_generation(entity.index(), entity.generation())
+            });
+        }
+        self.entity_allocator.restore_from_parts(free, next_index);
+    }
+}
+
+/// Creates the [`EntityCloner`] used to capture and restore values for a snapshot.
+fn snapshot_cloner(
+    world: &mut World,
+    resource_ids: &[(ComponentId, ComponentCloneBehavior)],
+) -> EntityCloner {
+    let mut builder = EntityCloner::build_opt_out(world);
+    for (id, behavior) in resource_ids {
+        builder.override_clone_behavior_with_id(*id, behavior.clone());
+    }
+    builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        world::{SnapshotFilter, World},
+    };
+    use alloc::{string::String, vec, vec::Vec};
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Position(i32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Name(String);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Untracked;
+
+    #[derive(Resource, Clone, PartialEq, Debug)]
+    struct Frame(u32);
+
+    fn filter() -> SnapshotFilter {
+        SnapshotFilter::new()
+            .allow::<Position>()
+            .allow::<Name>()
+            .allow_resource::<Frame>()
+    }
+
+    #[test]
+    fn restore_component_values() {
+        let mut world = World::new();
+        let a = world.spawn((Position(1), Name("a".into()))).id();
+        let b = world.spawn(Position(2)).id();
+        let untracked = world.spawn(Untracked).id();
+
+        let snapshot = world.snapshot(&filter());
+        let mut expected = vec![a, b];
+        expected.sort();
+        assert_eq!(snapshot.entities().collect::<Vec<_>>(), expected);
+
+        world.get_mut::<Position>(a).unwrap().0 = 10;
+        world.entity_mut(a).remove::<Name>();
+        world.entity_mut(b).insert(Name("b".into()));
+        world.clear_trackers();
+
+        world.restore(&snapshot);
+        assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+        assert_eq!(world.get::<Name>(a), Some(&Name("a".into())));
+        assert_eq!(world.get::<Name>(b), None);
+        assert!(world.entity(untracked).contains::<Untracked>());
+
+        // Restored data is reported as changed.
+        let mut query = world.query_filtered::<Entity, Changed<Position>>();
+        assert_eq!(query.iter(&world).count(), 2);
+
+        // The same snapshot can be restored again.
+        world.get_mut::<Position>(b).unwrap().0 = 20;
+        world.restore(&snapshot);
+        assert_eq!(world.get::<Position>(b), Some(&Position(2)));
+    }
+
+    #[test]
+    fn restore_entities_with_same_ids() {
+        let mut world = World::new();
+        let a = world.spawn(Position(1)).id();
+        let b = world.spawn(Position(2)).id();
+        let freed = world.spawn_empty().id();
+        world.despawn(freed);
+
+        let snapshot = world.snapshot(&filter());
+        let spawned_after_snapshot: Vec<Entity> =
+            (0..3).map(|i| world.spawn(Position(i)).id()).collect();
+        world.despawn(a);
+
+        world.restore(&snapshot);
+        assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+        assert_eq!(world.get::<Position>(b), Some(&Position(2)));
+        for entity in &spawned_after_snapshot {
+            assert!(!world.entities().contains_spawned(*entity));
+        }
+
+        // The allocator hands out the same entities as it did after the snapshot was taken.
+        let spawned_after_restore: Vec<Entity> =
+            (0..3).map(|i| world.spawn(Position(i)).id()).collect();
+        assert_eq!(spawned_after_snapshot, spawned_after_restore);
+    }
+
+    #[test]
+    fn restore_resources() {
+        let mut world = World::new();
+        let snapshot_without_frame = world.snapshot(&filter());
+        world.insert_resource(Frame(1));
+        let snapshot = world.snapshot(&filter());
+
+        world.resource_mut::<Frame>().0 = 2;
+        world.restore(&snapshot);
+        assert_eq!(world.resource::<Frame>(), &Frame(1));
+
+        world.restore(&snapshot_without_frame);
+        assert!(!world.contains_resource::<Frame>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn restore_to_other_world() {
+        let mut world = World::new();
+        let snapshot = world.snapshot(&filter());
+        World::new().restore(&snapshot);
+    }
+}
@@ -0,0 +1,867 @@
+//! Rebindable input actions, mapping user-defined actions to keys, mouse buttons and gamepad inputs.
+//!
+//! An [`InputMap`] binds the variants of an [`InputAction`] type to [`Binding`]s, grouped in a stack of
+//! [`ActionContext`]s which can be pushed and popped as the game changes modes.
+//! Every frame, [`ActionPlugin`] evaluates the bindings and stores the result in the [`ActionState`]
+//! living on the same entity. Spawn one entity per local player, each reading its own gamepad.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::{action::*, keyboard::KeyCode};
+//! #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+//! enum PlayerAction {
+//!     Jump,
+//!     Move,
+//! }
+//!
+//! fn spawn_player(mut commands: Commands) {
+//!     let gameplay = ActionContext::new("gameplay")
+//!         .with(PlayerAction::Jump, KeyCode::Space)
+//!         .with(PlayerAction::Move, DualAxisBinding::wasd());
+//!     commands.spawn(InputMap::new(gameplay));
+//! }
+//!
+//! fn jump(players: Query<&ActionState<PlayerAction>>) {
+//!     for actions in &players {
+//!         if actions.just_pressed(PlayerAction::Jump) {
+//!             // Jump!
+//!         }
+//!         let movement = actions.axis_pair(PlayerAction::Move);
+//!     }
+//! }
+//! ```
+
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt::Debug, hash::Hash, marker::PhantomData};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::{ops, Vec2};
+use bevy_platform::collections::HashMap;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, GetTypeRegistration, Reflect, Typed};
+
+use crate::{
+    gamepad::{AxisSettings, Gamepad, GamepadAxis, GamepadButton},
+    keyboard::KeyCode,
+    mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButton},
+    ButtonInput, InputSystems,
+};
+
+/// A user-defined action, usually an enum, which can be bound to inputs in an [`InputMap`].
+///
+/// This is implemented for every type meeting its bounds.
+pub trait InputAction: Copy + Eq + Hash + Debug + Send + Sync + 'static {}
+
+impl<A: Copy + Eq + Hash + Debug + Send + Sync + 'static> InputAction for A {}
+
+/// Updates the [`ActionState`] of every entity with an [`InputMap`] for the action type `A`.
+///
+/// With the `bevy_reflect` feature, this registers the binding types. Call
+/// [`AppExtActions::register_type_action`] to also register `A`, its [`ActionContext`]s,
+/// [`InputMap`] and [`ActionState`].
+pub struct ActionPlugin<A: InputAction>(PhantomData<fn() -> A>);
+
+impl<A: InputAction> Default for ActionPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: InputAction> Plugin for ActionPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            update_action_states::<A>
+                .in_set(ActionSystems)
+                .after(InputSystems),
+        );
+
+        #[cfg(feature = "bevy_reflect")]
+        app.register_type::<InputBinding>()
+            .register_type::<AxisBinding>()
+            .register_type::<DualAxisBinding>()
+            .register_type::<Binding>()
+            .register_type::<ActionData>();
+    }
+}
+
+/// Extension trait for [`App`] to register the reflected types of an [`InputAction`].
+#[cfg(feature = "bevy_reflect")]
+pub trait AppExtActions {
+    /// Registers the action type `A`, along with [`ActionContext<A>`], [`InputMap<A>`] and [`ActionState<A>`],
+    /// using [`App::register_type`].
+    ///
+    /// This enables reflection code, such as inspectors or the serialization of binding profiles,
+    /// to access the actions of entities.
+    fn register_type_action<A>(&mut self) -> &mut Self
+    where
+        A: InputAction + FromReflect + GetTypeRegistration + Typed;
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl AppExtActions for App {
+    fn register_type_action<A>(&mut self) -> &mut Self
+    where
+        A: InputAction + FromReflect + GetTypeRegistration + Typed,
+    {
+        self.register_type::<A>()
+            .register_type::<ActionContext<A>>()
+            .register_type::<InputMap<A>>()
+            .register_type::<ActionState<A>>()
+    }
+}
+
+/// Label for the systems updating the [`ActionState`]s, which run in [`PreUpdate`] after [`InputSystems`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct ActionSystems;
+
+/// A digital input: a key, a mouse button, a gamepad button, or a chord of them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub enum InputBinding {
+    /// A keyboard key.
+    Key(KeyCode),
+    /// A mouse button.
+    Mouse(MouseButton),
+    /// A gamepad button.
+    Gamepad(GamepadButton),
+    /// Several inputs which must all be pressed at the same time, such as `Ctrl + S`.
+    Chord(Vec<InputBinding>),
+}
+
+impl InputBinding {
+    /// Creates a chord of `inputs`, which must all be pressed at the same time.
+    pub fn chord(inputs: impl IntoIterator<Item = impl Into<InputBinding>>) -> Self {
+        Self::Chord(inputs.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+impl From<GamepadButton> for InputBinding {
+    fn from(button: GamepadButton) -> Self {
+        Self::Gamepad(button)
+    }
+}
+
+/// An analog input, producing a single value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub enum AxisBinding {
+    /// A gamepad axis, such as a stick axis.
+    Gamepad(GamepadAxis),
+    /// The analog value of a gamepad button, such as a trigger.
+    GamepadButton(GamepadButton),
+    /// The horizontal [`AccumulatedMouseMotion`].
+    MouseMotionX,
+    /// The vertical [`AccumulatedMouseMotion`].
+    MouseMotionY,
+    /// The horizontal [`AccumulatedMouseScroll`].
+    MouseScrollX,
+    /// The vertical [`AccumulatedMouseScroll`].
+    MouseScrollY,
+    /// Two digital inputs, producing `-1.0` when `negative` is pressed and `1.0` when `positive` is pressed.
+    Buttons {
+        /// The input producing `-1.0`.
+        negative: InputBinding,
+        /// The input producing `1.0`.
+        positive: InputBinding,
+    },
+}
+
+impl AxisBinding {
+    /// Creates an axis from two digital inputs.
+    pub fn buttons(negative: impl Into<InputBinding>, positive: impl Into<InputBinding>) -> Self {
+        Self::Buttons {
+            negative: negative.into(),
+            positive: positive.into(),
+        }
+    }
+}
+
+impl From<GamepadAxis> for AxisBinding {
+    fn from(axis: GamepadAxis) -> Self {
+        Self::Gamepad(axis)
+    }
+}
+
+/// Two [`AxisBinding`]s composed into a [`Vec2`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct DualAxisBinding {
+    /// The horizontal axis.
+    pub x: AxisBinding,
+    /// The vertical axis.
+    pub y: AxisBinding,
+}
+
+impl DualAxisBinding {
+    /// Creates a dual axis from two [`AxisBinding`]s.
+    pub fn new(x: impl Into<AxisBinding>, y: impl Into<AxisBinding>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    /// Creates a dual axis from four digital inputs.
+    pub fn buttons(
+        left: impl Into<InputBinding>,
+        right: impl Into<InputBinding>,
+        down: impl Into<InputBinding>,
+        up: impl Into<InputBinding>,
+    ) -> Self {
+        Self::new(
+            AxisBinding::buttons(left, right),
+            AxisBinding::buttons(down, up),
+        )
+    }
+
+    /// The `W`, `A`, `S` and `D` keys.
+    pub fn wasd() -> Self {
+        Self::buttons(KeyCode::KeyA, KeyCode::KeyD, KeyCode::KeyS, KeyCode::KeyW)
+    }
+
+    /// The arrow keys.
+    pub fn arrow_keys() -> Self {
+        Self::buttons(
+            KeyCode::ArrowLeft,
+            KeyCode::ArrowRight,
+            KeyCode::ArrowDown,
+            KeyCode::ArrowUp,
+        )
+    }
+
+    /// The left stick of a gamepad.
+    pub fn left_stick() -> Self {
+        Self::new(GamepadAxis::LeftStickX, GamepadAxis::LeftStickY)
+    }
+
+    /// The right stick of a gamepad.
+    pub fn right_stick() -> Self {
+        Self::new(GamepadAxis::RightStickX, GamepadAxis::RightStickY)
+    }
+
+    /// The D-pad of a gamepad.
+    pub fn dpad() -> Self {
+        Self::buttons(
+            GamepadButton::DPadLeft,
+            GamepadButton::DPadRight,
+            GamepadButton::DPadDown,
+            GamepadButton::DPadUp,
+        )
+    }
+
+    /// The [`AccumulatedMouseMotion`].
+    pub fn mouse_motion() -> Self {
+        Self::new(AxisBinding::MouseMotionX, AxisBinding::MouseMotionY)
+    }
+}
+
+/// The inputs an action is bound to.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub enum Binding {
+    /// A digital input. The action value is `1.0` while it is pressed.
+    Button(InputBinding),
+    /// An analog input. The action is pressed while its value is not zero.
+    Axis {
+        /// The bound input.
+        axis: AxisBinding,
+        /// The deadzone applied to the value, with [`AxisSettings::clamp`].
+        deadzone: Option<AxisSettings>,
+    },
+    /// Two analog inputs composed into a [`Vec2`]. The action is pressed while its value is not zero.
+    DualAxis {
+        /// The bound inputs.
+        axes: DualAxisBinding,
+        /// The deadzone applied to each axis, with [`AxisSettings::clamp`].
+        deadzone: Option<AxisSettings>,
+    },
+}
+
+impl Binding {
+    /// Applies `deadzone` to this analog binding. Does nothing for [`Binding::Button`].
+    pub fn with_deadzone(mut self, settings: AxisSettings) -> Self {
+        match &mut self {
+            Self::Button(_) => {}
+            Self::Axis { deadzone, .. } | Self::DualAxis { deadzone, .. } => {
+                *deadzone = Some(settings);
+            }
+        }
+        self
+    }
+}
+
+impl<T: Into<InputBinding>> From<T> for Binding {
+    fn from(input: T) -> Self {
+        Self::Button(input.into())
+    }
+}
+
+impl From<AxisBinding> for Binding {
+    fn from(axis: AxisBinding) -> Self {
+        Self::Axis {
+            axis,
+            deadzone: None,
+        }
+    }
+}
+
+impl From<DualAxisBinding> for Binding {
+    fn from(axes: DualAxisBinding) -> Self {
+        Self::DualAxis {
+            axes,
+            deadzone: None,
+        }
+    }
+}
+
+/// A named set of bindings, such as the bindings of a game mode or a binding profile edited in a settings menu.
+///
+/// Contexts are stacked in an [`InputMap`]. With the `bevy_reflect` feature, they can be serialized
+/// through reflection to save the bindings chosen by the player.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct ActionContext<A: InputAction> {
+    /// The name of the context.
+    pub name: String,
+    /// The actions and the inputs they are bound to. An action can be bound several times.
+    pub bindings: Vec<(A, Binding)>,
+    /// Whether the contexts below this one in the [`InputMap`] are ignored while it is active.
+    pub blocking: bool,
+}
+
+impl<A: InputAction> ActionContext<A> {
+    /// Creates an empty, non-blocking context.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            bindings: Vec::new(),
+            blocking: false,
+        }
+    }
+
+    /// Binds `action` to `binding`, in addition to its existing bindings.
+    pub fn with(mut self, action: A, binding: impl Into<Binding>) -> Self {
+        self.bind(action, binding);
+        self
+    }
+
+    /// Sets whether the contexts below this one are ignored while it is active.
+    pub fn with_blocking(mut self, blocking: bool) -> Self {
+        self.blocking = blocking;
+        self
+    }
+
+    /// Binds `action` to `binding`, in addition to its existing bindings.
+    pub fn bind(&mut self, action: A, binding: impl Into<Binding>) {
+        self.bindings.push((action, binding.into()));
+    }
+
+    /// Replaces every binding of `action` with `binding`.
+    pub fn rebind(&mut self, action: A, binding: impl Into<Binding>) {
+        self.unbind(action);
+        self.bind(action, binding);
+    }
+
+    /// Removes every binding of `action`.
+    pub fn unbind(&mut self, action: A) {
+        self.bindings.retain(|(bound, _)| *bound != action);
+    }
+
+    /// Returns the bindings of `action`.
+    pub fn bindings_of(&self, action: A) -> impl Iterator<Item = &Binding> {
+        self.bindings
+            .iter()
+            .filter(move |(bound, _)| *bound == action)
+            .map(|(_, binding)| binding)
+    }
+}
+
+/// Maps the actions `A` to inputs for an entity, usually a local player.
+///
+/// The bindings are grouped in a stack of [`ActionContext`]s: the contexts are all read, from the top of the
+/// stack down to the first [blocking](ActionContext::blocking) one. The result is stored in the [`ActionState`]
+/// of the entity by [`ActionPlugin`].
+#[derive(Component, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, PartialEq, Clone)
+)]
+#[require(ActionState<A>)]
+pub struct InputMap<A: InputAction> {
+    /// The stack of contexts, the last one being on top.
+    pub contexts: Vec<ActionContext<A>>,
+    /// The gamepad entity read by the bindings. When `None`, every gamepad is read.
+    pub gamepad: Option<Entity>,
+}
+
+impl<A: InputAction> InputMap<A> {
+    /// Creates an input map with a single `context`, reading every gamepad.
+    pub fn new(context: ActionContext<A>) -> Self {
+        Self {
+            contexts: vec![context],
+            gamepad: None,
+        }
+    }
+
+    /// Only reads the given `gamepad` entity.
+    pub fn with_gamepad(mut self, gamepad: Entity) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Pushes `context` on top of the stack.
+    pub fn push_context(&mut self, context: ActionContext<A>) {
+        self.contexts.push(context);
+    }
+
+    /// Pops the context on top of the stack.
+    pub fn pop_context(&mut self) -> Option<ActionContext<A>> {
+        self.contexts.pop()
+    }
+
+    /// Returns the context named `name`, if it is in the stack.
+    pub fn context_mut(&mut self, name: &str) -> Option<&mut ActionContext<A>> {
+        self.contexts
+            .iter_mut()
+            .rev()
+            .find(|context| context.name == name)
+    }
+
+    /// Iterates over the contexts which are read, from the top of the stack.
+    pub fn active_contexts(&self) -> impl Iterator<Item = &ActionContext<A>> {
+        let blocking = self
+            .contexts
+            .iter()
+            .rposition(|context| context.blocking)
+            .unwrap_or(0);
+        self.contexts[blocking..].iter().rev()
+    }
+}
+
+/// The state of an action in an [`ActionState`].
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+pub struct ActionData {
+    /// Whether any binding of the action is pressed.
+    pub pressed: bool,
+    /// Whether the action started being pressed this frame.
+    pub just_pressed: bool,
+    /// Whether the action stopped being pressed this frame.
+    pub just_released: bool,
+    /// The value of the binding with the largest magnitude.
+    ///
+    /// This is `1.0` for pressed buttons, and the length of the [`axis_pair`](Self::axis_pair) for dual axes.
+    pub value: f32,
+    /// The value of the dual axis binding with the largest magnitude.
+    pub axis_pair: Vec2,
+}
+
+/// The state of the actions `A` of an entity, computed from its [`InputMap`].
+#[derive(Component, Debug, Clone)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, Default, Clone)
+)]
+pub struct ActionState<A: InputAction> {
+    actions: HashMap<A, ActionData>,
+}
+
+impl<A: InputAction> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            actions: HashMap::default(),
+        }
+    }
+}
+
+impl<A: InputAction> ActionState<A> {
+    /// Returns the state of `action`.
+    pub fn get(&self, action: A) -> ActionData {
+        self.actions.get(&action).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` if `action` is pressed.
+    pub fn pressed(&self, action: A) -> bool {
+        self.get(action).pressed
+    }
+
+    /// Returns `true` if `action` started being pressed this frame.
+    pub fn just_pressed(&self, action: A) -> bool {
+        self.get(action).just_pressed
+    }
+
+    /// Returns `true` if `action` stopped being pressed this frame.
+    pub fn just_released(&self, action: A) -> bool {
+        self.get(action).just_released
+    }
+
+    /// Returns the value of `action`. See [`ActionData::value`].
+    pub fn value(&self, action: A) -> f32 {
+        self.get(action).value
+    }
+
+    /// Returns the dual axis value of `action`. See [`ActionData::axis_pair`].
+    pub fn axis_pair(&self, action: A) -> Vec2 {
+        self.get(action).axis_pair
+    }
+
+    /// Iterates over the actions which are pressed.
+    pub fn get_pressed(&self) -> impl Iterator<Item = A> + '_ {
+        self.actions
+            .iter()
+            .filter(|(_, data)| data.pressed)
+            .map(|(action, _)| *action)
+    }
+}
+
+/// The input resources read by the bindings of an [`InputMap`].
+struct InputSources<'a> {
+    keys: Option<&'a ButtonInput<KeyCode>>,
+    mouse_buttons: Option<&'a ButtonInput<MouseButton>>,
+    mouse_motion: Vec2,
+    mouse_scroll: Vec2,
+    gamepads: Vec<&'a Gamepad>,
+}
+
+impl InputSources<'_> {
+    fn pressed(&self, input: &InputBinding) -> bool {
+        match input {
+            InputBinding::Key(key) => self.keys.is_some_and(|keys| keys.pressed(*key)),
+            InputBinding::Mouse(button) => self
+                .mouse_buttons
+                .is_some_and(|buttons| buttons.pressed(*button)),
+            InputBinding::Gamepad(button) => {
+                self.gamepads.iter().any(|gamepad| gamepad.pressed(*button))
+            }
+            InputBinding::Chord(inputs) => {
+                !inputs.is_empty() && inputs.iter().all(|input| self.pressed(input))
+            }
+        }
+    }
+
+    fn axis(&self, axis: &AxisBinding) -> f32 {
+        let gamepad_value = |input| {
+            self.gamepads
+                .iter()
+                .filter_map(|gamepad: &&Gamepad| gamepad.get(input))
+                .fold(0.0, max_magnitude)
+        };
+        match axis {
+            AxisBinding::Gamepad(axis) => gamepad_value(crate::gamepad::GamepadInput::Axis(*axis)),
+            AxisBinding::GamepadButton(button) => {
+                gamepad_value(crate::gamepad::GamepadInput::Button(*button))
+            }
+            AxisBinding::MouseMotionX => self.mouse_motion.x,
+            AxisBinding::MouseMotionY => self.mouse_motion.y,
+            AxisBinding::MouseScrollX => self.mouse_scroll.x,
+            AxisBinding::MouseScrollY => self.mouse_scroll.y,
+            AxisBinding::Buttons { negative, positive } => {
+                f32::from(u8::from(self.pressed(positive)))
+                    - f32::from(u8::from(self.pressed(negative)))
+            }
+        }
+    }
+
+    /// Returns whether `binding` is pressed, its value and its dual axis value.
+    fn evaluate(&self, binding: &Binding) -> (bool, f32, Vec2) {
+        let clamp = |deadzone: &Option<AxisSettings>, value: f32| match deadzone {
+            Some(settings) => settings.clamp(value),
+            None => value,
+        };
+        match binding {
+            Binding::Button(input) => {
+                let pressed = self.pressed(input);
+                (pressed, if pressed { 1.0 } else { 0.0 }, Vec2::ZERO)
+            }
+            Binding::Axis { axis, deadzone } => {
+                let value = clamp(deadzone, self.axis(axis));
+                (value != 0.0, value, Vec2::ZERO)
+            }
+            Binding::DualAxis { axes, deadzone } => {
+                let pair = Vec2::new(
+                    clamp(deadzone, self.axis(&axes.x)),
+                    clamp(deadzone, self.axis(&axes.y)),
+                );
+                (pair != Vec2::ZERO, pair.length(), pair)
+            }
+        }
+    }
+}
+
+fn max_magnitude(a: f32, b: f32) -> f32 {
+    if ops::abs(b) > ops::abs(a) {
+        b
+    } else {
+        a
+    }
+}
+
+/// Updates the [`ActionState`] of every entity with an [`InputMap`] from the current input.
+pub fn update_action_states<A: InputAction>(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mouse_buttons: Option<Res<ButtonInput<MouseButton>>>,
+    mouse_motion: Option<Res<AccumulatedMouseMotion>>,
+    mouse_scroll: Option<Res<AccumulatedMouseScroll>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut maps: Query<(&InputMap<A>, &mut ActionState<A>)>,
+) {
+    for (map, mut state) in &mut maps {
+        let sources = InputSources {
+            keys: keys.as_deref(),
+            mouse_buttons: mouse_buttons.as_deref(),
+            mouse_motion: mouse_motion
+                .as_ref()
+                .map_or(Vec2::ZERO, |motion| motion.delta),
+            mouse_scroll: mouse_scroll
+                .as_ref()
+                .map_or(Vec2::ZERO, |scroll| scroll.delta),
+            gamepads: gamepads
+                .iter()
+                .filter(|(entity, _)| map.gamepad.is_none_or(|gamepad| gamepad == *entity))
+                .map(|(_, gamepad)| gamepad)
+                .collect(),
+        };
+
+        let state = &mut state.actions;
+        for data in state.values_mut() {
+            let was_pressed = data.pressed;
+            *data = ActionData {
+                // Temporarily holds the previous state.
+                just_released: was_pressed,
+                ..Default::default()
+            };
+        }
+
+        for context in map.active_contexts() {
+            for (action, binding) in &context.bindings {
+                let (pressed, value, pair) = sources.evaluate(binding);
+                let data = state.entry(*action).or_default();
+                data.pressed |= pressed;
+                data.value = max_magnitude(data.value, value);
+                if pair.length_squared() > data.axis_pair.length_squared() {
+                    data.axis_pair = pair;
+                }
+            }
+        }
+
+        for data in state.values_mut() {
+            let was_pressed = data.just_released;
+            data.just_pressed = data.pressed && !was_pressed;
+            data.just_released = !data.pressed && was_pressed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    enum Action {
+        Jump,
+        Save,
+        Move,
+        Throttle,
+    }
+
+    fn setup(map: InputMap<Action>) -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<ButtonInput<MouseButton>>();
+        let player = world.spawn(map).id();
+        (world, player)
+    }
+
+    fn update(world: &mut World, player: Entity) -> ActionState<Action> {
+        world
+            .run_system_cached(update_action_states::<Action>)
+            .unwrap();
+        world.get::<ActionState<Action>>(player).unwrap().clone()
+    }
+
+    #[test]
+    fn button_transitions() {
+        let (mut world, player) = setup(InputMap::new(
+            ActionContext::new("gameplay").with(Action::Jump, KeyCode::Space),
+        ));
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        let state = update(&mut world, player);
+        assert!(state.pressed(Action::Jump));
+        assert!(state.just_pressed(Action::Jump));
+        assert_eq!(state.value(Action::Jump), 1.0);
+
+        let state = update(&mut world, player);
+        assert!(state.pressed(Action::Jump));
+        assert!(!state.just_pressed(Action::Jump));
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::Space);
+        let state = update(&mut world, player);
+        assert!(!state.pressed(Action::Jump));
+        assert!(state.just_released(Action::Jump));
+    }
+
+    #[test]
+    fn chords() {
+        let (mut world, player) = setup(InputMap::new(ActionContext::new("editor").with(
+            Action::Save,
+            InputBinding::chord([KeyCode::ControlLeft, KeyCode::KeyS]),
+        )));
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyS);
+        assert!(!update(&mut world, player).pressed(Action::Save));
+
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ControlLeft);
+        assert!(update(&mut world, player).just_pressed(Action::Save));
+    }
+
+    #[test]
+    fn composed_axes() {
+        let (mut world, player) = setup(InputMap::new(
+            ActionContext::new("gameplay").with(Action::Move, DualAxisBinding::wasd()),
+        ));
+
+        let mut keys = world.resource_mut::<ButtonInput<KeyCode>>();
+        keys.press(KeyCode::KeyW);
+        keys.press(KeyCode::KeyA);
+        let state = update(&mut world, player);
+        assert_eq!(state.axis_pair(Action::Move), Vec2::new(-1.0, 1.0));
+        assert!(state.pressed(Action::Move));
+    }
+
+    #[test]
+    fn contexts() {
+        let (mut world, player) = setup(InputMap::new(
+            ActionContext::new("gameplay").with(Action::Jump, KeyCode::Space),
+        ));
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        assert!(update(&mut world, player).pressed(Action::Jump));
+
+        let mut map = world.get_mut::<InputMap<Action>>(player).unwrap();
+        map.push_context(ActionContext::new("menu").with_blocking(true));
+        let state = update(&mut world, player);
+        assert!(!state.pressed(Action::Jump));
+        assert!(state.just_released(Action::Jump));
+
+        let mut map = world.get_mut::<InputMap<Action>>(player).unwrap();
+        assert_eq!(map.pop_context().unwrap().name, "menu");
+        assert!(update(&mut world, player).just_pressed(Action::Jump));
+    }
+
+    #[test]
+    fn per_player_gamepads() {
+        let mut world = World::new();
+        let mut first = Gamepad::default();
+        first.analog.set(GamepadAxis::LeftStickY, 0.05);
+        first.analog.set(GamepadButton::RightTrigger2, 0.05);
+        let first = world.spawn(first).id();
+        let mut second = Gamepad::default();
+        second.analog.set(GamepadButton::RightTrigger2, 0.8);
+        let second = world.spawn(second).id();
+
+        let context = ActionContext::new("driving").with(
+            Action::Throttle,
+            Binding::from(AxisBinding::GamepadButton(GamepadButton::RightTrigger2))
+                .with_deadzone(AxisSettings::default()),
+        );
+        let player_one = world
+            .spawn(InputMap::new(context.clone()).with_gamepad(first))
+            .id();
+        let player_two = world
+            .spawn(InputMap::new(context).with_gamepad(second))
+            .id();
+
+        assert!(!update(&mut world, player_one).pressed(Action::Throttle));
+        assert_eq!(update(&mut world, player_two).value(Action::Throttle), 0.8);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn reflection_round_trip() {
+        use bevy_ecs::reflect::AppTypeRegistry;
+        use bevy_reflect::serde::{ReflectDeserializer, ReflectSerializer};
+        use serde::de::DeserializeSeed;
+
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
+        enum ReflectedAction {
+            Jump,
+            Move,
+        }
+
+        let mut app = App::new();
+        app.add_plugins(ActionPlugin::<ReflectedAction>::default())
+            .register_type_action::<ReflectedAction>();
+
+        let map = InputMap::new(
+            ActionContext::new("gameplay")
+                .with(
+                    ReflectedAction::Jump,
+                    InputBinding::chord([KeyCode::ControlLeft, KeyCode::Space]),
+                )
+                .with(ReflectedAction::Jump, MouseButton::Left)
+                .with(ReflectedAction::Move, DualAxisBinding::wasd())
+                .with_blocking(true),
+        );
+
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let serialized = ron::to_string(&ReflectSerializer::new(&map, &registry)).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(
+            InputMap::<ReflectedAction>::from_reflect(deserialized.as_partial_reflect()),
+            Some(map)
+        );
+    }
+}
@@ -88,3 +88,61 @@ pub struct DoubleTapGesture;
     reflect(Serialize, Deserialize)
 )]
 pub struct PanGesture(pub Vec2);
+
+/// Single tap gesture, containing the position of the tap.
+///
+/// ## Platform-specific
+///
+/// - Only emitted by the touch gesture recognizer of the `touch_gestures` feature.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct TapGesture(pub Vec2);
+
+/// Long press gesture, containing the position of the press.
+///
+/// Emitted once, when a finger has been held still for long enough.
+///
+/// ## Platform-specific
+///
+/// - Only emitted by the touch gesture recognizer of the `touch_gestures` feature.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct LongPressGesture(pub Vec2);
+
+/// Swipe gesture, containing the velocity of the swipe in logical pixels per second.
+///
+/// Emitted when a finger is lifted after a quick movement.
+///
+/// ## Platform-specific
+///
+/// - Only emitted by the touch gesture recognizer of the `touch_gestures` feature.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SwipeGesture(pub Vec2);
@@ -17,6 +17,8 @@ extern crate std;
 
 extern crate alloc;
 
+#[cfg(feature = "action")]
+pub mod action;
 mod axis;
 mod button_input;
 /// Common run conditions
@@ -37,6 +39,12 @@ pub mod mouse;
 #[cfg(feature = "touch")]
 pub mod touch;
 
+#[cfg(feature = "touch_gestures")]
+pub mod touch_gestures;
+
+#[cfg(feature = "replay")]
+pub mod replay;
+
 pub use axis::*;
 pub use button_input::*;
 
@@ -136,7 +144,10 @@ impl Plugin for InputPlugin {
         app.add_message::<PinchGesture>()
             .add_message::<RotationGesture>()
             .add_message::<DoubleTapGesture>()
-            .add_message::<PanGesture>();
+            .add_message::<PanGesture>()
+            .add_message::<TapGesture>()
+            .add_message::<LongPressGesture>()
+            .add_message::<SwipeGesture>();
 
         #[cfg(feature = "gamepad")]
         app.add_message::<GamepadEvent>()
@@ -0,0 +1,665 @@
+//! Recording and playback of input messages and frame times, to reproduce a session deterministically.
+//!
+//! [`ReplayRecordingPlugin`] records the [`Time<Real>`] delta of every frame, along with the input
+//! messages written by the windowing and gamepad backends, and saves them as a [`Replay`] file when the app exits.
+//! [`ReplayPlaybackPlugin`] feeds a [`Replay`] back into the app, using [`TimeUpdateStrategy::ManualDuration`]
+//! and the message queues, so that the session can be reproduced headlessly.
+//!
+//! During playback, recorded windows and gamepads are mapped to live entities, so that replayed messages target them:
+//! - entities mapped with [`ReplayPlayer::map_entity`] are mapped to the given live entity,
+//! - other windows are mapped to the entity with [`ReplayWindow`], which `bevy_window` requires on its
+//!   `PrimaryWindow` when its `replay` feature is enabled,
+//! - other gamepads are mapped to the live gamepads not targeted yet.
+//!
+//! Recorded entities without a live counterpart are mapped to new entities. Recorded gamepads become live
+//! ones this way if the replay contains their connection messages.
+//! Only input messages are replayed: any other source of non-determinism, such as random number generators
+//! or assets loaded asynchronously, must be controlled separately.
+
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy_app::{App, AppExit, First, Last, Plugin};
+use bevy_ecs::{
+    entity::{hash_map::EntityHashMap, Entity, EntityMapper},
+    message::{Message, MessageReader, MessageUpdateSystems},
+    prelude::*,
+};
+use bevy_time::{Real, Time, TimeSystems, TimeUpdateStrategy};
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[cfg(feature = "gamepad")]
+use crate::gamepad::{
+    Gamepad, GamepadConnectionEvent, RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent,
+    RawGamepadEvent,
+};
+#[cfg(feature = "gestures")]
+use crate::gestures::{DoubleTapGesture, PanGesture, PinchGesture, RotationGesture};
+#[cfg(feature = "keyboard")]
+use crate::keyboard::{KeyboardFocusLost, KeyboardInput};
+#[cfg(feature = "mouse")]
+use crate::mouse::{MouseButtonInput, MouseMotion, MouseWheel};
+#[cfg(feature = "touch")]
+use crate::touch::TouchInput;
+
+/// A recording of the frame times and input messages of a session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    /// The recorded frames, in order.
+    pub frames: Vec<ReplayFrame>,
+}
+
+/// A single frame of a [`Replay`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    /// The [`Time<Real>`] delta of the frame.
+    pub delta: Duration,
+    /// The input messages written during the frame.
+    pub messages: Vec<ReplayMessage>,
+}
+
+/// An error that occurs when saving or loading a [`Replay`].
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    /// The replay file could not be read or written.
+    #[error("failed to access the replay file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The replay could not be serialized.
+    #[error("failed to serialize the replay: {0}")]
+    Serialize(#[from] ron::Error),
+    /// The replay file could not be parsed.
+    #[error("failed to parse the replay file: {0}")]
+    Deserialize(#[from] ron::error::SpannedError),
+}
+
+impl Replay {
+    /// Loads a replay from a RON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let contents = fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    /// Saves the replay to a RON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        let contents = ron::to_string(self)?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+macro_rules! replay_messages {
+    ($($(#[$meta:meta])* $variant:ident($message:ty),)*) => {
+        /// An input message recorded in a [`Replay`].
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        pub enum ReplayMessage {
+            $(
+                $(#[$meta])*
+                #[doc = concat!("A [`", stringify!($message), "`] message.")]
+                $variant($message),
+            )*
+        }
+
+        $(
+            $(#[$meta])*
+            impl From<$message> for ReplayMessage {
+                fn from(message: $message) -> Self {
+                    Self::$variant(message)
+                }
+            }
+        )*
+
+        impl ReplayMessage {
+            /// Writes the message to its [`Messages`] queue in `world`.
+            #[expect(clippy::allow_attributes, reason = "this is only sometimes unused")]
+            #[allow(unused, reason = "all features could be disabled")]
+            pub fn write(self, world: &mut World) {
+                match self {
+                    $(
+                        $(#[$meta])*
+                        Self::$variant(message) => {
+                            world.write_message(message);
+                        }
+                    )*
+                }
+            }
+        }
+
+        #[expect(clippy::allow_attributes, reason = "this is only sometimes unused")]
+        #[allow(unused, reason = "all features could be disabled")]
+        fn add_recording_systems(app: &mut App) {
+            $(
+                $(#[$meta])*
+                app.add_systems(Last, record_messages::<$message>.in_set(ReplaySystems));
+            )*
+        }
+    };
+}
+
+replay_messages! {
+    #[cfg(feature = "keyboard")]
+    Keyboard(KeyboardInput),
+    #[cfg(feature = "keyboard")]
+    KeyboardFocusLost(KeyboardFocusLost),
+    #[cfg(feature = "mouse")]
+    MouseButton(MouseButtonInput),
+    #[cfg(feature = "mouse")]
+    MouseMotion(MouseMotion),
+    #[cfg(feature = "mouse")]
+    MouseWheel(MouseWheel),
+    #[cfg(feature = "touch")]
+    Touch(TouchInput),
+    #[cfg(feature = "gestures")]
+    PinchGesture(PinchGesture),
+    #[cfg(feature = "gestures")]
+    RotationGesture(RotationGesture),
+    #[cfg(feature = "gestures")]
+    DoubleTapGesture(DoubleTapGesture),
+    #[cfg(feature = "gestures")]
+    PanGesture(PanGesture),
+    #[cfg(feature = "gamepad")]
+    RawGamepad(RawGamepadEvent),
+    #[cfg(feature = "gamepad")]
+    GamepadConnection(GamepadConnectionEvent),
+    #[cfg(feature = "gamepad")]
+    RawGamepadButtonChanged(RawGamepadButtonChangedEvent),
+    #[cfg(feature = "gamepad")]
+    RawGamepadAxisChanged(RawGamepadAxisChangedEvent),
+}
+
+/// The kind of entity referenced by a [`ReplayMessage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReplayEntity {
+    #[cfg_attr(
+        not(any(feature = "keyboard", feature = "mouse", feature = "touch")),
+        expect(dead_code, reason = "only window messages construct this")
+    )]
+    Window,
+    #[cfg_attr(
+        not(feature = "gamepad"),
+        expect(dead_code, reason = "only gamepad messages construct this")
+    )]
+    Gamepad,
+}
+
+impl ReplayMessage {
+    /// Maps the entities referenced by the message, such as windows and gamepads, using `mapper`.
+    pub fn map_entities<E: EntityMapper>(&mut self, mapper: &mut E) {
+        self.for_each_entity(|_, entity| *entity = mapper.get_mapped(*entity));
+    }
+
+    #[expect(clippy::allow_attributes, reason = "this is only sometimes unused")]
+    #[allow(unused, reason = "all features could be disabled")]
+    fn for_each_entity(&mut self, mut f: impl FnMut(ReplayEntity, &mut Entity)) {
+        match self {
+            #[cfg(feature = "keyboard")]
+            Self::Keyboard(message) => f(ReplayEntity::Window, &mut message.window),
+            #[cfg(feature = "mouse")]
+            Self::MouseButton(message) => f(ReplayEntity::Window, &mut message.window),
+            #[cfg(feature = "mouse")]
+            Self::MouseWheel(message) => f(ReplayEntity::Window, &mut message.window),
+            #[cfg(feature = "touch")]
+            Self::Touch(message) => f(ReplayEntity::Window, &mut message.window),
+            #[cfg(feature = "gamepad")]
+            Self::RawGamepad(message) => match message {
+                RawGamepadEvent::Connection(event) => f(ReplayEntity::Gamepad, &mut event.gamepad),
+                RawGamepadEvent::Button(event) => f(ReplayEntity::Gamepad, &mut event.gamepad),
+                RawGamepadEvent::Axis(event) => f(ReplayEntity::Gamepad, &mut event.gamepad),
+            },
+            #[cfg(feature = "gamepad")]
+            Self::GamepadConnection(message) => f(ReplayEntity::Gamepad, &mut message.gamepad),
+            #[cfg(feature = "gamepad")]
+            Self::RawGamepadButtonChanged(message) => {
+                f(ReplayEntity::Gamepad, &mut message.gamepad);
+            }
+            #[cfg(feature = "gamepad")]
+            Self::RawGamepadAxisChanged(message) => f(ReplayEntity::Gamepad, &mut message.gamepad),
+            _ => {}
+        }
+    }
+}
+
+/// System set for the systems recording and playing back a [`Replay`].
+///
+/// Frames are started in [`First`], after [`TimeSystems`] when recording and before it when playing back.
+/// Input messages are recorded in [`Last`], once every input backend has written them.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct ReplaySystems;
+
+/// Records a [`Replay`] of the app, saved to [`path`](Self::path) when the app exits.
+///
+/// The recording is stored in the [`ReplayRecorder`] resource, and can also be saved manually.
+pub struct ReplayRecordingPlugin {
+    /// The path of the RON file the replay is saved to when an [`AppExit`] message is written, if any.
+    pub path: Option<PathBuf>,
+}
+
+impl ReplayRecordingPlugin {
+    /// Creates a plugin recording a replay to the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+        }
+    }
+}
+
+impl Plugin for ReplayRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ReplayRecorder {
+            replay: Replay::default(),
+            path: self.path.clone(),
+            recording: true,
+        })
+        .configure_sets(First, ReplaySystems.after(TimeSystems))
+        .add_systems(First, start_recording_frame.in_set(ReplaySystems))
+        .add_systems(Last, save_replay_on_exit);
+        add_recording_systems(app);
+    }
+}
+
+/// The resource recording a [`Replay`], added by [`ReplayRecordingPlugin`].
+#[derive(Resource, Debug)]
+pub struct ReplayRecorder {
+    replay: Replay,
+    path: Option<PathBuf>,
+    recording: bool,
+}
+
+impl ReplayRecorder {
+    /// Returns the replay recorded so far.
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Returns `true` if frames are currently being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Pauses or resumes the recording.
+    pub fn set_recording(&mut self, recording: bool) {
+        self.recording = recording;
+    }
+
+    /// Clears the recorded frames, returning the replay recorded so far.
+    pub fn take(&mut self) -> Replay {
+        core::mem::take(&mut self.replay)
+    }
+}
+
+fn start_recording_frame(mut recorder: ResMut<ReplayRecorder>, time: Res<Time<Real>>) {
+    if recorder.recording {
+        recorder.replay.frames.push(ReplayFrame {
+            delta: time.delta(),
+            messages: Vec::new(),
+        });
+    }
+}
+
+#[expect(clippy::allow_attributes, reason = "this is only sometimes unused")]
+#[allow(unused, reason = "all features could be disabled")]
+fn record_messages<M: Message + Clone + Into<ReplayMessage>>(
+    mut reader: MessageReader<M>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    if !recorder.recording {
+        reader.clear();
+        return;
+    }
+    let Some(frame) = recorder.replay.frames.last_mut() else {
+        return;
+    };
+    frame
+        .messages
+        .extend(reader.read().cloned().map(Into::into));
+}
+
+fn save_replay_on_exit(mut exits: MessageReader<AppExit>, recorder: Res<ReplayRecorder>) {
+    if exits.read().last().is_none() {
+        return;
+    }
+    let Some(path) = &recorder.path else {
+        return;
+    };
+    if let Err(err) = recorder.replay.save(path) {
+        error!("Failed to save the replay to {}: {err}", path.display());
+    }
+}
+
+/// Plays back a [`Replay`], overriding [`TimeUpdateStrategy`] and writing the recorded input messages.
+///
+/// Each update of the app plays one frame of the replay.
+/// Once all frames have been played, [`TimeUpdateStrategy`] is left unchanged,
+/// and an [`AppExit::Success`] message is written if [`exit_when_finished`](Self::exit_when_finished) is set.
+pub struct ReplayPlaybackPlugin {
+    /// The replay to play back.
+    pub replay: Replay,
+    /// Whether to exit the app once all frames have been played.
+    pub exit_when_finished: bool,
+}
+
+impl ReplayPlaybackPlugin {
+    /// Creates a plugin playing back `replay`, and exiting the app once it is finished.
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            exit_when_finished: true,
+        }
+    }
+}
+
+impl Plugin for ReplayPlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimeUpdateStrategy>()
+            .insert_resource(ReplayPlayer {
+                replay: self.replay.clone(),
+                frame: 0,
+                entities: EntityHashMap::default(),
+                exit_when_finished: self.exit_when_finished,
+                finished: false,
+            })
+            .configure_sets(
+                First,
+                ReplaySystems
+                    .before(TimeSystems)
+                    .after(MessageUpdateSystems),
+            )
+            .add_systems(First, play_replay_frame.in_set(ReplaySystems));
+    }
+}
+
+/// Marks the window that replayed input messages target, unless their recorded window was mapped
+/// with [`ReplayPlayer::map_entity`].
+///
+/// `bevy_window` requires this component on its `PrimaryWindow` when its `replay` feature is enabled.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayWindow;
+
+/// The resource playing back a [`Replay`], added by [`ReplayPlaybackPlugin`].
+#[derive(Resource, Debug)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    frame: usize,
+    entities: EntityHashMap<Entity>,
+    exit_when_finished: bool,
+    finished: bool,
+}
+
+impl ReplayPlayer {
+    /// Returns the replay being played back.
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Returns the index of the next frame to play.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns `true` once all frames have been played.
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.replay.frames.len()
+    }
+
+    /// Maps the `recorded` entity, such as a window or a gamepad, to the `live` one for the rest of the playback.
+    ///
+    /// Entities that are not mapped explicitly are mapped as described in the [module docs](self).
+    pub fn map_entity(&mut self, recorded: Entity, live: Entity) {
+        self.entities.insert(recorded, live);
+    }
+}
+
+/// Maps a recorded entity that was not mapped yet to a live one, spawning a new entity if there is none.
+#[expect(clippy::allow_attributes, reason = "this is only sometimes unused")]
+#[allow(unused, reason = "all features could be disabled")]
+fn map_new_entity(
+    entities: &EntityHashMap<Entity>,
+    world: &mut World,
+    kind: ReplayEntity,
+) -> Entity {
+    let live = match kind {
+        ReplayEntity::Window => world
+            .query_filtered::<Entity, With<ReplayWindow>>()
+            .iter(world)
+            .next(),
+        #[cfg(feature = "gamepad")]
+        ReplayEntity::Gamepad => world
+            .query_filtered::<Entity, With<Gamepad>>()
+            .iter(world)
+            .find(|gamepad| !entities.values().any(|mapped| mapped == gamepad)),
+        #[cfg(not(feature = "gamepad"))]
+        ReplayEntity::Gamepad => None,
+    };
+    live.unwrap_or_else(|| world.spawn_empty().id())
+}
+
+fn play_replay_frame(world: &mut World) {
+    world.resource_scope(|world, mut player: Mut<ReplayPlayer>| {
+        let player = &mut *player;
+        let Some(frame) = player.replay.frames.get(player.frame) else {
+            if !player.finished {
+                player.finished = true;
+                info!("Finished playing back the replay.");
+                if player.exit_when_finished {
+                    world.write_message(AppExit::Success);
+                }
+            }
+            return;
+        };
+        player.frame += 1;
+
+        *world.resource_mut::<TimeUpdateStrategy>() =
+            TimeUpdateStrategy::ManualDuration(frame.delta);
+        let mut messages = frame.messages.clone();
+        for message in &mut messages {
+            message.for_each_entity(|kind, entity| {
+                *entity = match player.entities.get(entity) {
+                    Some(&mapped) => mapped,
+                    None => {
+                        let mapped = map_new_entity(&player.entities, world, kind);
+                        player.entities.insert(*entity, mapped);
+                        mapped
+                    }
+                };
+            });
+        }
+        for message in messages {
+            message.write(world);
+        }
+    });
+}
+
+#[cfg(all(test, feature = "keyboard"))]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use core::time::Duration;
+
+    use bevy_app::{App, PreUpdate, Update};
+    use bevy_ecs::prelude::*;
+    use bevy_time::{Real, Time, TimePlugin, TimeUpdateStrategy};
+
+    use super::*;
+    use crate::{
+        keyboard::{Key, KeyCode, KeyboardInput},
+        ButtonInput, ButtonState, InputPlugin,
+    };
+
+    fn key_input(window: Entity) -> KeyboardInput {
+        KeyboardInput {
+            key_code: KeyCode::Space,
+            logical_key: Key::Space,
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window,
+        }
+    }
+
+    fn record() -> Replay {
+        let mut app = App::new();
+        app.add_plugins((
+            TimePlugin,
+            InputPlugin,
+            ReplayRecordingPlugin { path: None },
+        ));
+        let window = app.world_mut().spawn_empty().id();
+        for millis in [10, 20, 15] {
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                millis,
+            )));
+            if millis == 20 {
+                app.world_mut().write_message(key_input(window));
+            }
+            app.update();
+        }
+        app.world_mut().resource_mut::<ReplayRecorder>().take()
+    }
+
+    #[test]
+    fn recording_captures_deltas_and_messages() {
+        let replay = record();
+        assert_eq!(replay.frames.len(), 3);
+        assert_eq!(replay.frames[0].delta, Duration::ZERO);
+        assert_eq!(replay.frames[1].delta, Duration::from_millis(20));
+        assert_eq!(replay.frames[2].delta, Duration::from_millis(15));
+        assert_eq!(replay.frames[1].messages.len(), 1);
+        assert!(replay.frames[2].messages.is_empty());
+
+        let serialized = ron::to_string(&replay).unwrap();
+        assert_eq!(ron::from_str::<Replay>(&serialized).unwrap(), replay);
+    }
+
+    #[test]
+    fn messages_written_by_backends_during_the_frame_are_recorded() {
+        let mut app = App::new();
+        app.add_plugins((
+            TimePlugin,
+            InputPlugin,
+            ReplayRecordingPlugin { path: None },
+        ));
+        let window = app.world_mut().spawn_empty().id();
+        app.add_systems(
+            PreUpdate,
+            move |mut writer: MessageWriter<KeyboardInput>| {
+                writer.write(key_input(window));
+            },
+        );
+        app.update();
+
+        let replay = app.world_mut().resource_mut::<ReplayRecorder>().take();
+        assert_eq!(replay.frames.len(), 1);
+        assert_eq!(replay.frames[0].messages.len(), 1);
+    }
+
+    #[test]
+    fn playback_reproduces_deltas_and_messages() {
+        #[derive(Resource, Default)]
+        struct Observed(Vec<(Duration, bool)>);
+
+        let replay = record();
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin, ReplayPlaybackPlugin::new(replay)))
+            .init_resource::<Observed>()
+            .add_systems(
+                Update,
+                |time: Res<Time<Real>>,
+                 keys: Res<ButtonInput<KeyCode>>,
+                 mut observed: ResMut<Observed>| {
+                    observed
+                        .0
+                        .push((time.delta(), keys.just_pressed(KeyCode::Space)));
+                },
+            );
+        for _ in 0..3 {
+            app.update();
+            assert!(app.should_exit().is_none());
+        }
+        assert_eq!(
+            app.world().resource::<Observed>().0,
+            [
+                (Duration::ZERO, false),
+                (Duration::from_millis(20), true),
+                (Duration::from_millis(15), false),
+            ]
+        );
+
+        app.update();
+        assert!(app.world().resource::<ReplayPlayer>().is_finished());
+        assert_eq!(app.should_exit(), Some(AppExit::Success));
+    }
+
+    /// Plays back a single frame writing `message`, returning the `M` messages read by the app.
+    fn play_one<M: Message + Clone>(
+        message: impl Into<ReplayMessage>,
+        setup: impl FnOnce(&mut World),
+    ) -> Vec<M> {
+        #[derive(Resource)]
+        struct Observed<M>(Vec<M>);
+
+        let replay = Replay {
+            frames: vec![ReplayFrame {
+                delta: Duration::from_millis(10),
+                messages: vec![message.into()],
+            }],
+        };
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin, ReplayPlaybackPlugin::new(replay)))
+            .insert_resource(Observed::<M>(Vec::new()))
+            .add_systems(
+                Update,
+                |mut reader: MessageReader<M>, mut observed: ResMut<Observed<M>>| {
+                    observed.0.extend(reader.read().cloned());
+                },
+            );
+        setup(app.world_mut());
+        app.update();
+        app.world_mut().remove_resource::<Observed<M>>().unwrap().0
+    }
+
+    #[test]
+    fn playback_targets_the_replay_window() {
+        let recorded = Entity::from_raw_u32(1000).unwrap();
+        let mut window = None;
+        let messages = play_one::<KeyboardInput>(key_input(recorded), |world| {
+            window = Some(world.spawn(ReplayWindow).id());
+        });
+        assert_eq!(messages.len(), 1);
+        assert_eq!(Some(messages[0].window), window);
+    }
+
+    #[test]
+    fn playback_targets_explicitly_mapped_entities() {
+        let recorded = Entity::from_raw_u32(1000).unwrap();
+        let mut live = None;
+        let messages = play_one::<KeyboardInput>(key_input(recorded), |world| {
+            world.spawn(ReplayWindow);
+            let entity = world.spawn_empty().id();
+            world
+                .resource_mut::<ReplayPlayer>()
+                .map_entity(recorded, entity);
+            live = Some(entity);
+        });
+        assert_eq!(Some(messages[0].window), live);
+    }
+
+    #[cfg(feature = "gamepad")]
+    #[test]
+    fn playback_targets_live_gamepads() {
+        use crate::gamepad::{Gamepad, GamepadButton, RawGamepadButtonChangedEvent};
+
+        let recorded = Entity::from_raw_u32(1000).unwrap();
+        let mut gamepad = None;
+        let messages = play_one::<RawGamepadButtonChangedEvent>(
+            RawGamepadButtonChangedEvent::new(recorded, GamepadButton::South, 1.0),
+            |world| {
+                gamepad = Some(world.spawn(Gamepad::default()).id());
+            },
+        );
+        assert_eq!(Some(messages[0].gamepad), gamepad);
+    }
+}
@@ -0,0 +1,381 @@
+//! Cross-platform gesture recognition from touch input.
+//!
+//! [`TouchGesturePlugin`] synthesizes gestures from the [`Touches`] resource, and writes the same messages
+//! as the platform gestures of the [`gestures`](crate::gestures) module, so that gameplay code works
+//! the same on every platform. The thresholds are configured with the [`TouchGestureSettings`] resource.
+//!
+//! On platforms emitting their own gestures, such as macOS and iOS, adding this plugin may produce
+//! duplicate gesture messages.
+
+use core::time::Duration;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_platform::collections::HashMap;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::{Real, Time};
+
+use crate::{
+    gestures::{
+        DoubleTapGesture, LongPressGesture, PanGesture, PinchGesture, RotationGesture,
+        SwipeGesture, TapGesture,
+    },
+    touch::Touches,
+    InputSystems,
+};
+
+/// Recognizes tap, double tap, long press, swipe, pan, pinch and rotation gestures from touch input.
+///
+/// The gestures are written as [`TapGesture`], [`DoubleTapGesture`], [`LongPressGesture`], [`SwipeGesture`],
+/// [`PanGesture`], [`PinchGesture`] and [`RotationGesture`] messages.
+#[derive(Default)]
+pub struct TouchGesturePlugin;
+
+impl Plugin for TouchGesturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchGestureSettings>()
+            .add_systems(PreUpdate, recognize_touch_gestures.after(InputSystems));
+    }
+}
+
+/// The thresholds used to recognize touch gestures. Distances are in logical pixels.
+#[derive(Resource, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, Default, PartialEq, Clone)
+)]
+pub struct TouchGestureSettings {
+    /// How far a finger can move before it no longer counts as a tap or a long press, and starts panning.
+    pub tap_slop: f32,
+    /// The longest duration of a tap.
+    pub tap_max_duration: Duration,
+    /// The longest duration between the two taps of a double tap.
+    pub double_tap_interval: Duration,
+    /// The largest distance between the two taps of a double tap.
+    pub double_tap_distance: f32,
+    /// How long a finger must be held still to produce a long press.
+    pub long_press_duration: Duration,
+    /// The shortest distance a finger must travel to produce a swipe.
+    pub swipe_min_distance: f32,
+    /// The lowest average speed, in logical pixels per second, a finger must travel at to produce a swipe.
+    pub swipe_min_velocity: f32,
+}
+
+impl Default for TouchGestureSettings {
+    fn default() -> Self {
+        Self {
+            tap_slop: 10.0,
+            tap_max_duration: Duration::from_millis(300),
+            double_tap_interval: Duration::from_millis(300),
+            double_tap_distance: 40.0,
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_min_velocity: 300.0,
+        }
+    }
+}
+
+/// A touch followed by [`recognize_touch_gestures`].
+#[derive(Debug, Clone, Copy)]
+struct TrackedTouch {
+    /// When the touch started.
+    start: Duration,
+    /// The position of the touch the last time it was seen.
+    last_position: Vec2,
+    /// Whether the touch moved farther than [`TouchGestureSettings::tap_slop`].
+    moved: bool,
+    /// Whether other touches were pressed at the same time.
+    multi_touch: bool,
+    /// Whether the touch already produced a long press.
+    long_pressed: bool,
+}
+
+/// The state of [`recognize_touch_gestures`].
+#[derive(Default)]
+pub struct TouchGestureState {
+    touches: HashMap<u64, TrackedTouch>,
+    /// When and where the last tap happened, if it can still become a double tap.
+    last_tap: Option<(Duration, Vec2)>,
+}
+
+/// Recognizes gestures from the [`Touches`] resource. See [`TouchGesturePlugin`].
+pub fn recognize_touch_gestures(
+    touches: Res<Touches>,
+    time: Res<Time<Real>>,
+    settings: Res<TouchGestureSettings>,
+    mut state: Local<TouchGestureState>,
+    mut taps: MessageWriter<TapGesture>,
+    mut double_taps: MessageWriter<DoubleTapGesture>,
+    mut long_presses: MessageWriter<LongPressGesture>,
+    mut swipes: MessageWriter<SwipeGesture>,
+    mut pans: MessageWriter<PanGesture>,
+    mut pinches: MessageWriter<PinchGesture>,
+    mut rotations: MessageWriter<RotationGesture>,
+) {
+    let now = time.elapsed();
+    let state = &mut *state;
+
+    for touch in touches.iter_just_pressed() {
+        state.touches.insert(
+            touch.id(),
+            TrackedTouch {
+                start: now,
+                last_position: touch.position(),
+                moved: false,
+                multi_touch: false,
+                long_pressed: false,
+            },
+        );
+    }
+
+    let pressed_count = touches.iter().count();
+    let mut previous_centroid = Vec2::ZERO;
+    let mut centroid = Vec2::ZERO;
+    let mut common_count = 0;
+    let mut panning = false;
+    for touch in touches.iter() {
+        let Some(tracked) = state.touches.get_mut(&touch.id()) else {
+            continue;
+        };
+        previous_centroid += tracked.last_position;
+        centroid += touch.position();
+        common_count += 1;
+
+        tracked.multi_touch |= pressed_count > 1;
+        tracked.moved |= touch.distance().length() > settings.tap_slop;
+        panning |= tracked.moved;
+
+        if !tracked.moved
+            && !tracked.multi_touch
+            && !tracked.long_pressed
+            && now - tracked.start >= settings.long_press_duration
+        {
+            tracked.long_pressed = true;
+            long_presses.write(LongPressGesture(touch.position()));
+        }
+    }
+
+    if panning && common_count > 0 {
+        let delta = (centroid - previous_centroid) / common_count as f32;
+        if delta != Vec2::ZERO {
+            pans.write(PanGesture(delta));
+        }
+    }
+
+    if pressed_count == 2 {
+        let mut pair = touches
+            .iter()
+            .filter_map(|touch| Some((state.touches.get(&touch.id())?, touch)));
+        if let (Some((first_tracked, first)), Some((second_tracked, second))) =
+            (pair.next(), pair.next())
+        {
+            let previous = second_tracked.last_position - first_tracked.last_position;
+            let current = second.position() - first.position();
+            if previous != Vec2::ZERO && current != Vec2::ZERO {
+                let scale = current.length() / previous.length() - 1.0;
+                if scale != 0.0 {
+                    pinches.write(PinchGesture(scale));
+                }
+                // Window coordinates point down, so the angle is flipped to be counterclockwise.
+                let angle = -previous.angle_to(current).to_degrees();
+                if angle != 0.0 {
+                    rotations.write(RotationGesture(angle));
+                }
+            }
+        }
+    }
+
+    for touch in touches.iter() {
+        if let Some(tracked) = state.touches.get_mut(&touch.id()) {
+            tracked.last_position = touch.position();
+        }
+    }
+
+    for touch in touches.iter_just_released() {
+        let Some(tracked) = state.touches.remove(&touch.id()) else {
+            continue;
+        };
+        let duration = now - tracked.start;
+        if !tracked.moved
+            && !tracked.multi_touch
+            && !tracked.long_pressed
+            && duration <= settings.tap_max_duration
+        {
+            let position = touch.position();
+            taps.write(TapGesture(position));
+            match state.last_tap {
+                Some((time, last_position))
+                    if now - time <= settings.double_tap_interval
+                        && last_position.distance(position) <= settings.double_tap_distance =>
+                {
+                    double_taps.write(DoubleTapGesture);
+                    state.last_tap = None;
+                }
+                _ => state.last_tap = Some((now, position)),
+            }
+        } else if tracked.moved && !tracked.multi_touch {
+            let displacement = touch.distance();
+            let velocity = displacement / duration.as_secs_f32().max(f32::EPSILON);
+            if displacement.length() >= settings.swipe_min_distance
+                && velocity.length() >= settings.swipe_min_velocity
+            {
+                swipes.write(SwipeGesture(velocity));
+            }
+        }
+    }
+
+    for touch in touches.iter_just_canceled() {
+        state.touches.remove(&touch.id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::touch::{touch_screen_input_system, TouchInput, TouchPhase};
+    use alloc::vec::Vec;
+    use bevy_ecs::message::Messages;
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<Touches>();
+        world.init_resource::<Time<Real>>();
+        world.init_resource::<TouchGestureSettings>();
+        world.init_resource::<Messages<TouchInput>>();
+        world.init_resource::<Messages<TapGesture>>();
+        world.init_resource::<Messages<DoubleTapGesture>>();
+        world.init_resource::<Messages<LongPressGesture>>();
+        world.init_resource::<Messages<SwipeGesture>>();
+        world.init_resource::<Messages<PanGesture>>();
+        world.init_resource::<Messages<PinchGesture>>();
+        world.init_resource::<Messages<RotationGesture>>();
+        world
+            .resource_mut::<Time<Real>>()
+            .update_with_duration(Duration::ZERO);
+        world
+    }
+
+    /// Advances the clock by `millis`, applies `inputs` and runs the recognizer.
+    fn frame(world: &mut World, millis: u64, inputs: &[(u64, TouchPhase, Vec2)]) {
+        world
+            .resource_mut::<Time<Real>>()
+            .update_with_duration(Duration::from_millis(millis));
+        for &(id, phase, position) in inputs {
+            world.write_message(TouchInput {
+                phase,
+                position,
+                window: Entity::PLACEHOLDER,
+                force: None,
+                id,
+            });
+        }
+        world.run_system_cached(touch_screen_input_system).unwrap();
+        world.run_system_cached(recognize_touch_gestures).unwrap();
+    }
+
+    fn drain<M: Message>(world: &mut World) -> Vec<M> {
+        world.resource_mut::<Messages<M>>().drain().collect()
+    }
+
+    #[test]
+    fn tap_and_double_tap() {
+        let mut world = setup();
+        frame(&mut world, 16, &[(0, TouchPhase::Started, Vec2::ONE)]);
+        frame(&mut world, 100, &[(0, TouchPhase::Ended, Vec2::ONE)]);
+        assert_eq!(drain::<TapGesture>(&mut world), [TapGesture(Vec2::ONE)]);
+        assert!(drain::<DoubleTapGesture>(&mut world).is_empty());
+
+        frame(
+            &mut world,
+            100,
+            &[(1, TouchPhase::Started, Vec2::splat(5.0))],
+        );
+        frame(&mut world, 100, &[(1, TouchPhase::Ended, Vec2::splat(5.0))]);
+        assert_eq!(drain::<TapGesture>(&mut world).len(), 1);
+        assert_eq!(drain::<DoubleTapGesture>(&mut world).len(), 1);
+    }
+
+    #[test]
+    fn long_press() {
+        let mut world = setup();
+        frame(&mut world, 16, &[(0, TouchPhase::Started, Vec2::ZERO)]);
+        frame(&mut world, 300, &[]);
+        assert!(drain::<LongPressGesture>(&mut world).is_empty());
+        frame(&mut world, 300, &[]);
+        frame(&mut world, 300, &[]);
+        assert_eq!(
+            drain::<LongPressGesture>(&mut world),
+            [LongPressGesture(Vec2::ZERO)]
+        );
+
+        frame(&mut world, 16, &[(0, TouchPhase::Ended, Vec2::ZERO)]);
+        assert!(drain::<TapGesture>(&mut world).is_empty());
+    }
+
+    #[test]
+    fn swipe_and_pan() {
+        let mut world = setup();
+        frame(&mut world, 16, &[(0, TouchPhase::Started, Vec2::ZERO)]);
+        frame(
+            &mut world,
+            50,
+            &[(0, TouchPhase::Moved, Vec2::new(60.0, 0.0))],
+        );
+        assert_eq!(
+            drain::<PanGesture>(&mut world),
+            [PanGesture(Vec2::new(60.0, 0.0))]
+        );
+        frame(
+            &mut world,
+            50,
+            &[
+                (0, TouchPhase::Moved, Vec2::new(100.0, 0.0)),
+                (0, TouchPhase::Ended, Vec2::new(100.0, 0.0)),
+            ],
+        );
+
+        let swipes = drain::<SwipeGesture>(&mut world);
+        assert_eq!(swipes.len(), 1);
+        assert!((swipes[0].0 - Vec2::new(1000.0, 0.0)).length() < 1.0);
+        assert!(drain::<TapGesture>(&mut world).is_empty());
+    }
+
+    #[test]
+    fn pinch_and_rotate() {
+        let mut world = setup();
+        frame(
+            &mut world,
+            16,
+            &[
+                (0, TouchPhase::Started, Vec2::new(-10.0, 0.0)),
+                (1, TouchPhase::Started, Vec2::new(10.0, 0.0)),
+            ],
+        );
+        frame(
+            &mut world,
+            16,
+            &[
+                (0, TouchPhase::Moved, Vec2::new(-20.0, 0.0)),
+                (1, TouchPhase::Moved, Vec2::new(20.0, 0.0)),
+            ],
+        );
+        assert_eq!(drain::<PinchGesture>(&mut world), [PinchGesture(1.0)]);
+        assert!(drain::<RotationGesture>(&mut world).is_empty());
+
+        // Rotate the fingers by 90 degrees counterclockwise on screen, where y points down.
+        frame(
+            &mut world,
+            16,
+            &[
+                (0, TouchPhase::Moved, Vec2::new(0.0, 20.0)),
+                (1, TouchPhase::Moved, Vec2::new(0.0, -20.0)),
+            ],
+        );
+        let rotations = drain::<RotationGesture>(&mut world);
+        assert_eq!(rotations.len(), 1);
+        assert!((rotations[0].0 - 90.0).abs() < 1e-3);
+    }
+}
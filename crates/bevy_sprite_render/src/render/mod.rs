@@ -18,7 +18,7 @@ use bevy_ecs::{
     system::{lifetimeless::*, SystemParamItem},
 };
 use bevy_image::{BevyDefault, Image, TextureAtlasLayout};
-use bevy_math::{Affine3A, FloatOrd, Quat, Rect, Vec2, Vec4};
+use bevy_math::{Affine3A, FloatOrd, Quat, Rect, Vec2, Vec3, Vec4};
 use bevy_mesh::VertexBufferLayout;
 use bevy_platform::collections::HashMap;
 use bevy_render::view::{RenderVisibleEntities, RetainedViewEntity};
@@ -94,6 +94,7 @@ bitflags::bitflags! {
         const HDR                               = 1 << 0;
         const TONEMAP_IN_SHADER                 = 1 << 1;
         const DEBAND_DITHER                     = 1 << 2;
+        const SDF                               = 1 << 3;
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
         const TONEMAP_METHOD_RESERVED_BITS      = Self::TONEMAP_METHOD_MASK_BITS << Self::TONEMAP_METHOD_SHIFT_BITS;
         const TONEMAP_METHOD_NONE               = 0 << Self::TONEMAP_METHOD_SHIFT_BITS;
@@ -184,7 +185,7 @@ impl SpecializedRenderPipeline for SpritePipeline {
             false => TextureFormat::bevy_default(),
         };
 
-        let instance_rate_vertex_buffer_layout = VertexBufferLayout {
+        let mut instance_rate_vertex_buffer_layout = VertexBufferLayout {
             array_stride: 80,
             step_mode: VertexStepMode::Instance,
             attributes: vec![
@@ -221,6 +222,19 @@ impl SpecializedRenderPipeline for SpritePipeline {
             ],
         };
 
+        if key.contains(SpritePipelineKey::SDF) {
+            shader_defs.push("SDF".into());
+            instance_rate_vertex_buffer_layout.array_stride = 96;
+            // @location(5) i_sdf: vec4<f32>,
+            instance_rate_vertex_buffer_layout
+                .attributes
+                .push(VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 80,
+                    shader_location: 5,
+                });
+        }
+
         RenderPipelineDescriptor {
             vertex: VertexState {
                 shader: self.shader.clone(),
@@ -273,6 +287,12 @@ pub struct ExtractedSlice {
     pub offset: Vec2,
     pub rect: Rect,
     pub size: Vec2,
+    /// Range, dilation and softness in texels of a signed distance field glyph, which stores
+    /// the distance to the glyph outline in its alpha channel.
+    ///
+    /// Signed distance fields are drawn with a separate pipeline, so either all or none of the
+    /// slices of an [`ExtractedSprite`] must set this.
+    pub sdf: Option<Vec3>,
 }
 
 pub struct ExtractedSprite {
@@ -302,6 +322,19 @@ pub enum ExtractedSpriteKind {
     Slices { indices: Range<usize> },
 }
 
+impl ExtractedSprite {
+    /// Whether the slices of this sprite are signed distance fields, see [`ExtractedSlice::sdf`].
+    fn is_sdf(&self, extracted_slices: &ExtractedSlices) -> bool {
+        match &self.kind {
+            ExtractedSpriteKind::Single { .. } => false,
+            ExtractedSpriteKind::Slices { indices } => extracted_slices
+                .slices
+                .get(indices.start)
+                .is_some_and(|slice| slice.sdf.is_some()),
+        }
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct ExtractedSprites {
     pub sprites: Vec<ExtractedSprite>,
@@ -434,10 +467,20 @@ impl SpriteInstance {
     }
 }
 
+/// Instance data of signed distance field glyphs, drawn with [`SpritePipelineKey::SDF`].
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct SdfSpriteInstance {
+    pub instance: SpriteInstance,
+    // Signed distance field range, dilation and softness
+    pub i_sdf: [f32; 4],
+}
+
 #[derive(Resource)]
 pub struct SpriteMeta {
     sprite_index_buffer: RawBufferVec<u32>,
     sprite_instance_buffer: RawBufferVec<SpriteInstance>,
+    sdf_instance_buffer: RawBufferVec<SdfSpriteInstance>,
 }
 
 impl Default for SpriteMeta {
@@ -445,6 +488,7 @@ impl Default for SpriteMeta {
         Self {
             sprite_index_buffer: RawBufferVec::<u32>::new(BufferUsages::INDEX),
             sprite_instance_buffer: RawBufferVec::<SpriteInstance>::new(BufferUsages::VERTEX),
+            sdf_instance_buffer: RawBufferVec::<SdfSpriteInstance>::new(BufferUsages::VERTEX),
         }
     }
 }
@@ -460,6 +504,8 @@ pub struct SpriteBatches(HashMap<(RetainedViewEntity, Entity), SpriteBatch>);
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct SpriteBatch {
     image_handle_id: AssetId<Image>,
+    /// Whether the range indexes into the signed distance field instance buffer.
+    sdf: bool,
     range: Range<u32>,
 }
 
@@ -475,6 +521,7 @@ pub fn queue_sprites(
     mut pipelines: ResMut<SpecializedRenderPipelines<SpritePipeline>>,
     pipeline_cache: Res<PipelineCache>,
     extracted_sprites: Res<ExtractedSprites>,
+    extracted_slices: Res<ExtractedSlices>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
     mut views: Query<(
         &RenderVisibleEntities,
@@ -519,6 +566,7 @@ pub fn queue_sprites(
         }
 
         let pipeline = pipelines.specialize(&pipeline_cache, &sprite_pipeline, view_key);
+        let mut sdf_pipeline = None;
 
         view_entities.clear();
         view_entities.extend(
@@ -541,6 +589,18 @@ pub fn queue_sprites(
             // These items will be sorted by depth with other phase items
             let sort_key = FloatOrd(extracted_sprite.transform.translation().z);
 
+            let pipeline = if extracted_sprite.is_sdf(&extracted_slices) {
+                *sdf_pipeline.get_or_insert_with(|| {
+                    pipelines.specialize(
+                        &pipeline_cache,
+                        &sprite_pipeline,
+                        view_key | SpritePipelineKey::SDF,
+                    )
+                })
+            } else {
+                pipeline
+            };
+
             // Add the item to the render phase
             transparent_phase.add(Transparent2d {
                 draw_function: draw_sprite_function,
@@ -620,9 +680,11 @@ pub fn prepare_sprite_image_bind_groups(
 
     // Clear the sprite instances
     sprite_meta.sprite_instance_buffer.clear();
+    sprite_meta.sdf_instance_buffer.clear();
 
     // Index buffer indices
     let mut index = 0;
+    let mut sdf_index = 0;
 
     let image_bind_groups = &mut *image_bind_groups;
 
@@ -631,6 +693,7 @@ pub fn prepare_sprite_image_bind_groups(
         let mut batch_item_index = 0;
         let mut batch_image_size = Vec2::ZERO;
         let mut batch_image_handle = AssetId::invalid();
+        let mut batch_sdf = false;
 
         // Iterate through the phase items and detect when successive sprites that can be batched.
         // Spawn an entity with a `SpriteBatch` component for each possible batch.
@@ -650,13 +713,15 @@ pub fn prepare_sprite_image_bind_groups(
                 continue;
             };
 
-            if batch_image_handle != extracted_sprite.image_handle_id {
+            let sdf = extracted_sprite.is_sdf(&extracted_slices);
+            if batch_image_handle != extracted_sprite.image_handle_id || batch_sdf != sdf {
                 let Some(gpu_image) = gpu_images.get(extracted_sprite.image_handle_id) else {
                     continue;
                 };
 
                 batch_image_size = gpu_image.size_2d().as_vec2();
                 batch_image_handle = extracted_sprite.image_handle_id;
+                batch_sdf = sdf;
                 image_bind_groups
                     .values
                     .entry(batch_image_handle)
@@ -675,7 +740,12 @@ pub fn prepare_sprite_image_bind_groups(
                 current_batch = Some(batches.entry((*retained_view, item.entity())).insert(
                     SpriteBatch {
                         image_handle_id: batch_image_handle,
-                        range: index..index,
+                        sdf,
+                        range: if sdf {
+                            sdf_index..sdf_index
+                        } else {
+                            index..index
+                        },
                     },
                 ));
             }
@@ -788,16 +858,23 @@ pub fn prepare_sprite_image_bind_groups(
                             );
 
                         // Store the vertex data and add the item to the render phase
-                        sprite_meta
-                            .sprite_instance_buffer
-                            .push(SpriteInstance::from(
-                                &transform,
-                                &extracted_sprite.color,
-                                &uv_offset_scale,
-                            ));
+                        let instance = SpriteInstance::from(
+                            &transform,
+                            &extracted_sprite.color,
+                            &uv_offset_scale,
+                        );
+                        if sdf {
+                            sprite_meta.sdf_instance_buffer.push(SdfSpriteInstance {
+                                instance,
+                                i_sdf: slice.sdf.unwrap_or_default().extend(0.).to_array(),
+                            });
+                            sdf_index += 1;
+                        } else {
+                            sprite_meta.sprite_instance_buffer.push(instance);
+                            index += 1;
+                        }
 
                         current_batch.as_mut().unwrap().get_mut().range.end += 1;
-                        index += 1;
                     }
                 }
             }
@@ -808,6 +885,9 @@ pub fn prepare_sprite_image_bind_groups(
         sprite_meta
             .sprite_instance_buffer
             .write_buffer(&render_device, &render_queue);
+        sprite_meta
+            .sdf_instance_buffer
+            .write_buffer(&render_device, &render_queue);
 
         if sprite_meta.sprite_index_buffer.len() != 6 {
             sprite_meta.sprite_index_buffer.clear();
@@ -911,14 +991,12 @@ impl<P: PhaseItem> RenderCommand<P> for DrawSpriteBatch {
             sprite_meta.sprite_index_buffer.buffer().unwrap().slice(..),
             IndexFormat::Uint32,
         );
-        pass.set_vertex_buffer(
-            0,
-            sprite_meta
-                .sprite_instance_buffer
-                .buffer()
-                .unwrap()
-                .slice(..),
-        );
+        let instance_buffer = if batch.sdf {
+            sprite_meta.sdf_instance_buffer.buffer()
+        } else {
+            sprite_meta.sprite_instance_buffer.buffer()
+        };
+        pass.set_vertex_buffer(0, instance_buffer.unwrap().slice(..));
         pass.draw_indexed(0..6, 0, batch.range.clone());
         RenderCommandResult::Success
     }
@@ -19,12 +19,19 @@ struct VertexInput {
     @location(2) i_model_transpose_col2: vec4<f32>,
     @location(3) i_color: vec4<f32>,
     @location(4) i_uv_offset_scale: vec4<f32>,
+#ifdef SDF
+    // x: range, y: dilation, z: softness of signed distance field glyphs, in texels.
+    @location(5) i_sdf: vec4<f32>,
+#endif
 }
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) uv: vec2<f32>,
     @location(1) @interpolate(flat) color: vec4<f32>,
+#ifdef SDF
+    @location(2) @interpolate(flat) sdf: vec4<f32>,
+#endif
 };
 
 @vertex
@@ -44,6 +51,9 @@ fn vertex(in: VertexInput) -> VertexOutput {
     )) * vec4<f32>(vertex_position, 1.0);
     out.uv = vec2<f32>(vertex_position.xy) * in.i_uv_offset_scale.zw + in.i_uv_offset_scale.xy;
     out.color = in.i_color;
+#ifdef SDF
+    out.sdf = in.i_sdf;
+#endif
 
     return out;
 }
@@ -53,7 +63,16 @@ fn vertex(in: VertexInput) -> VertexOutput {
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    var color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
+    let texture_color = textureSample(sprite_texture, sprite_sampler, in.uv);
+#ifdef SDF
+    // Signed distance field glyphs, see `ExtractedSlice::sdf`.
+    let sdf_distance = (texture_color.a - 0.5) * in.sdf.x + in.sdf.y;
+    let sdf_aa = max(0.5 * fwidth(sdf_distance), 0.0001);
+    let t = smoothstep(-in.sdf.z - sdf_aa, sdf_aa, sdf_distance);
+    var color = vec4(in.color.rgb, saturate(in.color.a * t));
+#else
+    var color = in.color * texture_color;
+#endif
 
 #ifdef TONEMAP_IN_SHADER
     color = tonemapping::tone_mapping(color, view.color_grading);
@@ -15,8 +15,9 @@ use bevy_render::sync_world::TemporaryRenderEntity;
 use bevy_render::Extract;
 use bevy_sprite::{Anchor, Text2dShadow};
 use bevy_text::{
-    ComputedTextBlock, PositionedGlyph, Strikethrough, StrikethroughColor, TextBackgroundColor,
-    TextBounds, TextColor, TextLayoutInfo, Underline, UnderlineColor,
+    ComputedTextBlock, FontSmoothing, PositionedGlyph, SdfGlyphParams, Strikethrough,
+    StrikethroughColor, TextBackgroundColor, TextBounds, TextColor, TextLayoutInfo, TextSdfEffects,
+    Underline, UnderlineColor,
 };
 use bevy_transform::prelude::GlobalTransform;
 
@@ -36,6 +37,7 @@ pub fn extract_text2d_sprite(
             &TextBounds,
             &Anchor,
             Option<&Text2dShadow>,
+            Option<&TextSdfEffects>,
             &GlobalTransform,
         )>,
     >,
@@ -62,6 +64,7 @@ pub fn extract_text2d_sprite(
         text_bounds,
         anchor,
         maybe_shadow,
+        maybe_effects,
         global_transform,
     ) in text2d_query.iter()
     {
@@ -79,6 +82,17 @@ pub fn extract_text2d_sprite(
 
         let top_left = (Anchor::TOP_LEFT.0 - anchor.as_vec()) * size;
 
+        let sdf_params = |glyph: &PositionedGlyph, rect, dilation, softness| {
+            computed_block
+                .entities()
+                .get(glyph.span_index)
+                .is_some_and(|t| t.font_smoothing == FontSmoothing::Sdf)
+                .then(|| {
+                    let params = SdfGlyphParams::new(rect, glyph.size, dilation, softness);
+                    Vec3::new(params.range, params.dilation, params.softness)
+                })
+        };
+
         for run in text_layout_info.run_geometry.iter() {
             let section_entity = computed_block.entities()[run.span_index].entity;
             let Ok(text_background_color) = text_background_colors_query.get(section_entity) else {
@@ -115,8 +129,9 @@ pub fn extract_text2d_sprite(
 
             for (
                 i,
-                PositionedGlyph {
+                glyph @ PositionedGlyph {
                     position,
+                    size,
                     atlas_info,
                     ..
                 },
@@ -130,7 +145,8 @@ pub fn extract_text2d_sprite(
                 extracted_slices.slices.push(ExtractedSlice {
                     offset: *position,
                     rect,
-                    size: rect.size(),
+                    size: *size,
+                    sdf: sdf_params(glyph, rect, 0., 0.),
                 });
 
                 if text_layout_info
@@ -213,13 +229,70 @@ pub fn extract_text2d_sprite(
 
         let transform =
             *global_transform * GlobalTransform::from_translation(top_left.extend(0.)) * scaling;
+
+        // Distance field effects are drawn as extra layers of glyphs behind the text.
+        for layer in maybe_effects.into_iter().flat_map(TextSdfEffects::layers) {
+            let color = layer.color.into();
+            let layer_transform = transform
+                * GlobalTransform::from_translation(
+                    (layer.offset * text_layout_info.scale_factor).extend(0.),
+                );
+            let dilation = layer.dilation * text_layout_info.scale_factor;
+            let softness = layer.softness * text_layout_info.scale_factor;
+
+            let mut sdf_glyphs = text_layout_info
+                .glyphs
+                .iter()
+                .filter_map(|glyph| {
+                    let rect = texture_atlases
+                        .get(glyph.atlas_info.texture_atlas)
+                        .unwrap()
+                        .textures[glyph.atlas_info.location.glyph_index]
+                        .as_rect();
+                    let sdf = sdf_params(glyph, rect, dilation, softness)?;
+                    Some((glyph, rect, sdf))
+                })
+                .peekable();
+            while let Some((glyph, rect, sdf)) = sdf_glyphs.next() {
+                extracted_slices.slices.push(ExtractedSlice {
+                    offset: glyph.position,
+                    rect,
+                    size: glyph.size,
+                    sdf: Some(sdf),
+                });
+
+                if sdf_glyphs
+                    .peek()
+                    .is_none_or(|(next, ..)| next.atlas_info.texture != glyph.atlas_info.texture)
+                {
+                    let render_entity = commands.spawn(TemporaryRenderEntity).id();
+                    extracted_sprites.sprites.push(ExtractedSprite {
+                        main_entity,
+                        render_entity,
+                        transform: layer_transform,
+                        color,
+                        image_handle_id: glyph.atlas_info.texture,
+                        flip_x: false,
+                        flip_y: true,
+                        kind: ExtractedSpriteKind::Slices {
+                            indices: start..end,
+                        },
+                    });
+                    start = end;
+                }
+
+                end += 1;
+            }
+        }
+
         let mut color = LinearRgba::WHITE;
         let mut current_span = usize::MAX;
 
         for (
             i,
-            PositionedGlyph {
+            glyph @ PositionedGlyph {
                 position,
+                size,
                 atlas_info,
                 span_index,
                 ..
@@ -247,7 +320,8 @@ pub fn extract_text2d_sprite(
             extracted_slices.slices.push(ExtractedSlice {
                 offset: *position,
                 rect,
-                size: rect.size(),
+                size: *size,
+                sdf: sdf_params(glyph, rect, 0., 0.),
             });
 
             if text_layout_info.glyphs.get(i + 1).is_none_or(|info| {
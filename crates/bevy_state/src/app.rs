@@ -1,13 +1,21 @@
 use bevy_app::{App, MainScheduleOrder, Plugin, PreStartup, PreUpdate, SubApp};
-use bevy_ecs::{message::Messages, schedule::IntoScheduleConfigs, world::FromWorld};
+use bevy_ecs::{
+    message::Messages,
+    schedule::{IntoScheduleConfigs, IntoSystemSet},
+    world::FromWorld,
+};
 use bevy_utils::once;
 use log::warn;
 
 use crate::{
+    entity_state::{
+        apply_entity_state_transitions, register_entity_state_observers,
+        register_entity_state_systems,
+    },
     state::{
         setup_state_transitions_in_world, ComputedStates, FreelyMutableState, NextState,
-        PreviousState, State, StateTransition, StateTransitionEvent, StateTransitionSystems,
-        States, SubStates,
+        PreviousState, State, StateStack, StateTransition, StateTransitionEvent,
+        StateTransitionSystems, States, SubStates,
     },
     state_scoped::{despawn_entities_on_enter_state, despawn_entities_on_exit_state},
 };
@@ -58,6 +66,24 @@ pub trait AppExtStates {
     /// This method is idempotent: it has no effect when called again using the same generic type.
     fn add_sub_state<S: SubStates>(&mut self) -> &mut Self;
 
+    /// Enables pushdown transitions for the freely mutable state `S`, by adding a [`StateStack<S>`] resource.
+    ///
+    /// Enables use of the [`OnPause`](crate::state::OnPause) and [`OnResume`](crate::state::OnResume) schedules.
+    /// `S` must be initialized first, using [`init_state`](Self::init_state) or [`insert_state`](Self::insert_state).
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    fn add_state_stack<S: FreelyMutableState>(&mut self) -> &mut Self;
+
+    /// Sets up the per-entity state machine [`EntityState<S>`](crate::entity_state::EntityState).
+    ///
+    /// Pending [`NextEntityState<S>`](crate::entity_state::NextEntityState) transitions are applied
+    /// during the [`StateTransition`](struct@StateTransition) schedule, after global state transitions,
+    /// and [`EnterEntityState<S>`](crate::entity_state::EnterEntityState) and [`ExitEntityState<S>`](crate::entity_state::ExitEntityState)
+    /// events are triggered for the entities whose state changed.
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    fn add_entity_state<S: States>(&mut self) -> &mut Self;
+
     #[cfg(feature = "bevy_reflect")]
     /// Registers the state type `T` using [`App::register_type`],
     /// and adds [`ReflectState`](crate::reflect::ReflectState) type data to `T` in the type registry.
@@ -67,6 +93,15 @@ pub trait AppExtStates {
     where
         S: States + FromReflect + GetTypeRegistration + Typed;
 
+    #[cfg(feature = "bevy_reflect")]
+    /// Registers the state type `T`, [`EntityState<S>`](crate::entity_state::EntityState) and
+    /// [`NextEntityState<S>`](crate::entity_state::NextEntityState) using [`App::register_type`].
+    ///
+    /// This enables reflection code, such as inspectors or `bevy_remote`, to access and modify the state of entities.
+    fn register_type_entity_state<S>(&mut self) -> &mut Self
+    where
+        S: States + FromReflect + GetTypeRegistration + Typed;
+
     #[cfg(feature = "bevy_reflect")]
     /// Registers the state type `T` using [`App::register_type`],
     /// and adds [`crate::reflect::ReflectState`] and [`crate::reflect::ReflectFreelyMutableState`] type data to `T` in the type registry.
@@ -149,6 +184,25 @@ impl AppExtStates for SubApp {
         self
     }
 
+    fn add_state_stack<S: FreelyMutableState>(&mut self) -> &mut Self {
+        if !self.world().contains_resource::<State<S>>() {
+            let name = core::any::type_name::<S>();
+            warn!("A state stack was added for state `{name}`, but the state wasn't initialized in the app!");
+        }
+        if !self.world().contains_resource::<StateStack<S>>() {
+            self.init_resource::<StateStack<S>>();
+            let schedule = self.get_schedule_mut(StateTransition).expect(
+                "The `StateTransition` schedule is missing. Did you forget to add StatesPlugin or DefaultPlugins before calling add_state_stack?"
+            );
+            S::register_state_stack(schedule);
+        } else {
+            let name = core::any::type_name::<S>();
+            warn!("State stack {name} is already initialized.");
+        }
+
+        self
+    }
+
     fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
         warn_if_no_states_plugin_installed(self);
         if !self
@@ -209,6 +263,22 @@ impl AppExtStates for SubApp {
         self
     }
 
+    fn add_entity_state<S: States>(&mut self) -> &mut Self {
+        let apply_set = apply_entity_state_transitions::<S>.into_system_set();
+        let schedule = self.get_schedule_mut(StateTransition).expect(
+            "The `StateTransition` schedule is missing. Did you forget to add StatesPlugin or DefaultPlugins before calling add_entity_state?"
+        );
+        if !schedule.graph().system_sets.contains(apply_set) {
+            register_entity_state_systems::<S>(schedule);
+            register_entity_state_observers::<S>(self.world_mut());
+        } else {
+            let name = core::any::type_name::<S>();
+            warn!("Entity state {name} is already initialized.");
+        }
+
+        self
+    }
+
     #[cfg(feature = "bevy_reflect")]
     fn register_type_state<S>(&mut self) -> &mut Self
     where
@@ -221,6 +291,17 @@ impl AppExtStates for SubApp {
         self
     }
 
+    #[cfg(feature = "bevy_reflect")]
+    fn register_type_entity_state<S>(&mut self) -> &mut Self
+    where
+        S: States + FromReflect + GetTypeRegistration + Typed,
+    {
+        self.register_type::<S>();
+        self.register_type::<crate::entity_state::EntityState<S>>();
+        self.register_type::<crate::entity_state::NextEntityState<S>>();
+        self
+    }
+
     #[cfg(feature = "bevy_reflect")]
     fn register_type_mutable_state<S>(&mut self) -> &mut Self
     where
@@ -272,6 +353,11 @@ impl AppExtStates for App {
         self
     }
 
+    fn add_state_stack<S: FreelyMutableState>(&mut self) -> &mut Self {
+        self.main_mut().add_state_stack::<S>();
+        self
+    }
+
     fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
         self.main_mut().add_computed_state::<S>();
         self
@@ -282,6 +368,11 @@ impl AppExtStates for App {
         self
     }
 
+    fn add_entity_state<S: States>(&mut self) -> &mut Self {
+        self.main_mut().add_entity_state::<S>();
+        self
+    }
+
     #[cfg(feature = "bevy_reflect")]
     fn register_type_state<S>(&mut self) -> &mut Self
     where
@@ -291,6 +382,15 @@ impl AppExtStates for App {
         self
     }
 
+    #[cfg(feature = "bevy_reflect")]
+    fn register_type_entity_state<S>(&mut self) -> &mut Self
+    where
+        S: States + FromReflect + GetTypeRegistration + Typed,
+    {
+        self.main_mut().register_type_entity_state::<S>();
+        self
+    }
+
     #[cfg(feature = "bevy_reflect")]
     fn register_type_mutable_state<S>(&mut self) -> &mut Self
     where
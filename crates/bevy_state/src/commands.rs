@@ -1,7 +1,7 @@
 use bevy_ecs::{system::Commands, world::World};
 use log::debug;
 
-use crate::state::{FreelyMutableState, NextState};
+use crate::state::{FreelyMutableState, NextState, StateStack};
 
 /// Extension trait for [`Commands`] adding `bevy_state` helpers.
 pub trait CommandsStatesExt {
@@ -22,6 +22,18 @@ pub trait CommandsStatesExt {
     /// Note that commands introduce sync points to the ECS schedule, so modifying `NextState`
     /// directly may be more efficient depending on your use-case.
     fn set_state_if_neq<S: FreelyMutableState>(&mut self, state: S);
+
+    /// Pushes `state` on top of the current state, covering it until it is popped.
+    ///
+    /// Internally this schedules a command that calls [`StateStack::push`](crate::prelude::StateStack::push).
+    /// The state stack must have been added with `App::add_state_stack`.
+    fn push_state<S: FreelyMutableState>(&mut self, state: S);
+
+    /// Pops the current state, returning to the state it covers.
+    ///
+    /// Internally this schedules a command that calls [`StateStack::pop`](crate::prelude::StateStack::pop).
+    /// The state stack must have been added with `App::add_state_stack`.
+    fn pop_state<S: FreelyMutableState>(&mut self);
 }
 
 impl CommandsStatesExt for Commands<'_, '_> {
@@ -46,4 +58,16 @@ impl CommandsStatesExt for Commands<'_, '_> {
             next.set_if_neq(state);
         });
     }
+
+    fn push_state<S: FreelyMutableState>(&mut self, state: S) {
+        self.queue(move |w: &mut World| {
+            w.resource_mut::<StateStack<S>>().push(state);
+        });
+    }
+
+    fn pop_state<S: FreelyMutableState>(&mut self) {
+        self.queue(|w: &mut World| {
+            w.resource_mut::<StateStack<S>>().pop();
+        });
+    }
 }
@@ -0,0 +1,382 @@
+use alloc::boxed::Box;
+use core::ops::Deref;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    entity::Entity,
+    event::EntityEvent,
+    lifecycle::{Insert, Replace},
+    observer::On,
+    query::Changed,
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, Schedule},
+    system::{Commands, Query, Res},
+    world::{DeferredWorld, World},
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::prelude::*;
+use log::warn;
+
+use crate::state::{StateTransitionSystems, States};
+
+/// The current state of a per-entity finite-state machine.
+///
+/// This is the component-based counterpart of [`State<S>`](crate::state::State):
+/// each entity with this component has its own state, which is changed by setting
+/// its [`NextEntityState<S>`] component.
+///
+/// Whenever this component is inserted, including when it is changed by a transition,
+/// an [`EnterEntityState<S>`] event is triggered for the entity.
+/// Whenever it is replaced or removed, including when the entity is despawned,
+/// an [`ExitEntityState<S>`] event is triggered first.
+///
+/// Transitions are applied during the [`StateTransition`](crate::state::StateTransition) schedule,
+/// once they have been set up using `App::add_entity_state`.
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum AiState {
+///     #[default]
+///     Idle,
+///     Chasing,
+/// }
+///
+/// # #[derive(Component)]
+/// # struct Target;
+/// fn spawn_agent(mut commands: Commands) {
+///     commands
+///         .spawn(EntityState::new(AiState::Idle))
+///         .observe(|enter: On<EnterEntityState<AiState>>| {
+///             println!("{} is now {:?}", enter.entity, enter.state);
+///         });
+/// }
+///
+/// fn start_chasing(mut agents: Query<&mut NextEntityState<AiState>, With<Target>>) {
+///     for mut next in &mut agents {
+///         next.set(AiState::Chasing);
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+#[component(immutable)]
+#[require(NextEntityState<S>)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, PartialEq, Clone)
+)]
+pub struct EntityState<S: States>(pub(crate) S);
+
+impl<S: States> EntityState<S> {
+    /// Creates a new entity state with a specific value.
+    ///
+    /// To change the state of an entity use [`NextEntityState<S>`] rather than inserting a new `EntityState<S>`,
+    /// so that transitions are validated.
+    pub fn new(state: S) -> Self {
+        Self(state)
+    }
+
+    /// Get the current state.
+    pub fn get(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S: States> PartialEq<S> for EntityState<S> {
+    fn eq(&self, other: &S) -> bool {
+        self.get() == other
+    }
+}
+
+impl<S: States> Deref for EntityState<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+/// The next state of the [`EntityState<S>`] of an entity.
+///
+/// This is the component-based counterpart of [`NextState<S>`](crate::state::NextState).
+/// It is automatically added along with [`EntityState<S>`].
+///
+/// Note that these transitions can be overridden by other systems:
+/// only the value of this component during the [`StateTransition`](crate::state::StateTransition) schedule matters.
+#[derive(Component, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, Debug)
+)]
+pub enum NextEntityState<S: States> {
+    /// No state transition is pending
+    #[default]
+    Unchanged,
+    /// There is a pending transition for state `S`
+    Pending(S),
+    /// There is a pending transition for state `S`
+    ///
+    /// This will not trigger any event if the target state is the same as the current one.
+    PendingIfNeq(S),
+}
+
+impl<S: States> NextEntityState<S> {
+    /// Tentatively set a pending state transition to `state`.
+    ///
+    /// This will trigger [`ExitEntityState`] and [`EnterEntityState`] even if the state does not change.
+    /// If you want to skip them when transitioning to the same state, use [`set_if_neq`](Self::set_if_neq) instead.
+    pub fn set(&mut self, state: S) {
+        *self = Self::Pending(state);
+    }
+
+    /// Tentatively set a pending state transition to `state`.
+    ///
+    /// Like [`set`](Self::set), but will not trigger any event if the target state is the same as the current one.
+    /// If [`set`](Self::set) has already been called in the same frame with the same state, the events will be triggered anyways.
+    pub fn set_if_neq(&mut self, state: S) {
+        if !matches!(self, Self::Pending(s) if s == &state) {
+            *self = Self::PendingIfNeq(state);
+        }
+    }
+
+    /// Remove any pending changes to [`EntityState<S>`]
+    pub fn reset(&mut self) {
+        *self = Self::Unchanged;
+    }
+}
+
+/// An [`EntityEvent`] triggered whenever an entity enters a state, including when [`EntityState<S>`] is first inserted.
+#[derive(EntityEvent, Debug, Clone, PartialEq, Eq)]
+pub struct EnterEntityState<S: States> {
+    /// The entity whose state changed.
+    pub entity: Entity,
+    /// The state being entered.
+    pub state: S,
+}
+
+/// An [`EntityEvent`] triggered whenever an entity exits a state, including when [`EntityState<S>`] is removed.
+#[derive(EntityEvent, Debug, Clone, PartialEq, Eq)]
+pub struct ExitEntityState<S: States> {
+    /// The entity whose state changed.
+    pub entity: Entity,
+    /// The state being exited.
+    pub state: S,
+}
+
+/// A resource validating the transitions of [`EntityState<S>`].
+///
+/// When present, transitions for which the validation function returns `false` are rejected:
+/// the [`NextEntityState<S>`] is reset, and the entity stays in its current state.
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum DoorState {
+///     #[default]
+///     Open,
+///     Closed,
+///     Locked,
+/// }
+///
+/// // A door must be closed before it can be locked, and unlocked before it can be opened.
+/// let validator = EntityStateValidator::new(|from: &DoorState, to: &DoorState| {
+///     !matches!(
+///         (from, to),
+///         (DoorState::Open, DoorState::Locked) | (DoorState::Locked, DoorState::Open)
+///     )
+/// });
+/// assert!(!validator.is_valid(&DoorState::Open, &DoorState::Locked));
+/// ```
+#[derive(Resource)]
+pub struct EntityStateValidator<S: States> {
+    is_valid: Box<dyn Fn(&S, &S) -> bool + Send + Sync>,
+}
+
+impl<S: States> EntityStateValidator<S> {
+    /// Creates a validator from a function taking the exited and entered states.
+    pub fn new(is_valid: impl Fn(&S, &S) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            is_valid: Box::new(is_valid),
+        }
+    }
+
+    /// Returns `true` if the transition from `exited` to `entered` is allowed.
+    pub fn is_valid(&self, exited: &S, entered: &S) -> bool {
+        (self.is_valid)(exited, entered)
+    }
+}
+
+/// Registers the system applying [`NextEntityState<S>`] to `schedule`,
+/// which should be the [`StateTransition`](crate::state::StateTransition) schedule.
+///
+/// It runs after the transitions of global states, so that their [`OnEnter`](crate::state::OnEnter)
+/// schedules can queue entity transitions.
+/// It is called by `App::add_entity_state`, but can be called manually if `App` is not used.
+pub fn register_entity_state_systems<S: States>(schedule: &mut Schedule) {
+    schedule.add_systems(
+        apply_entity_state_transitions::<S>.after(StateTransitionSystems::EnterSchedules),
+    );
+}
+
+/// Registers the observers triggering [`EnterEntityState<S>`] and [`ExitEntityState<S>`].
+///
+/// It is called by `App::add_entity_state`, but can be called manually if `App` is not used.
+pub fn register_entity_state_observers<S: States>(world: &mut World) {
+    world.add_observer(trigger_enter_entity_state::<S>);
+    world.add_observer(trigger_exit_entity_state::<S>);
+}
+
+/// Applies the pending [`NextEntityState<S>`] of every entity, replacing their [`EntityState<S>`].
+pub fn apply_entity_state_transitions<S: States>(
+    mut commands: Commands,
+    validator: Option<Res<EntityStateValidator<S>>>,
+    mut query: Query<
+        (Entity, &EntityState<S>, &mut NextEntityState<S>),
+        Changed<NextEntityState<S>>,
+    >,
+) {
+    for (entity, current, mut next) in &mut query {
+        let entered = match core::mem::take(next.bypass_change_detection()) {
+            NextEntityState::Unchanged => continue,
+            NextEntityState::PendingIfNeq(entered) if entered == current.0 => continue,
+            NextEntityState::Pending(entered) | NextEntityState::PendingIfNeq(entered) => entered,
+        };
+        if let Some(validator) = &validator
+            && !validator.is_valid(&current.0, &entered)
+        {
+            warn!(
+                "Rejected invalid transition of {entity} from {:?} to {entered:?}.",
+                current.0
+            );
+            continue;
+        }
+        commands.entity(entity).insert(EntityState(entered));
+    }
+}
+
+fn trigger_enter_entity_state<S: States>(
+    insert: On<Insert, EntityState<S>>,
+    mut world: DeferredWorld,
+) {
+    let entity = insert.entity;
+    if let Some(state) = world.get::<EntityState<S>>(entity) {
+        let state = state.0.clone();
+        world.trigger(EnterEntityState { entity, state });
+    }
+}
+
+fn trigger_exit_entity_state<S: States>(
+    replace: On<Replace, EntityState<S>>,
+    mut world: DeferredWorld,
+) {
+    let entity = replace.entity;
+    if let Some(state) = world.get::<EntityState<S>>(entity) {
+        let state = state.0.clone();
+        world.trigger(ExitEntityState { entity, state });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use bevy_ecs::prelude::*;
+    use bevy_state_macros::States;
+
+    use super::*;
+    use crate::state::{setup_state_transitions_in_world, StateTransition};
+
+    #[derive(States, Default, PartialEq, Eq, Hash, Debug, Clone)]
+    enum AiState {
+        #[default]
+        Idle,
+        Chasing,
+        Dead,
+    }
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<(&'static str, AiState)>);
+
+    fn setup() -> World {
+        let mut world = World::new();
+        setup_state_transitions_in_world(&mut world);
+        world
+            .resource_mut::<Schedules>()
+            .get_mut(StateTransition)
+            .map(register_entity_state_systems::<AiState>);
+        register_entity_state_observers::<AiState>(&mut world);
+        world.init_resource::<Log>();
+        world.add_observer(
+            |enter: On<EnterEntityState<AiState>>, mut log: ResMut<Log>| {
+                log.0.push(("enter", enter.state.clone()));
+            },
+        );
+        world.add_observer(|exit: On<ExitEntityState<AiState>>, mut log: ResMut<Log>| {
+            log.0.push(("exit", exit.state.clone()));
+        });
+        world
+    }
+
+    #[test]
+    fn entity_state_transitions_trigger_events() {
+        let mut world = setup();
+        let entity = world.spawn(EntityState::new(AiState::Idle)).id();
+        assert_eq!(world.resource::<Log>().0, vec![("enter", AiState::Idle)]);
+
+        world
+            .get_mut::<NextEntityState<AiState>>(entity)
+            .unwrap()
+            .set(AiState::Chasing);
+        world.run_schedule(StateTransition);
+        assert_eq!(
+            world.get::<EntityState<AiState>>(entity).unwrap(),
+            &AiState::Chasing
+        );
+
+        let mut next = world.get_mut::<NextEntityState<AiState>>(entity).unwrap();
+        NextEntityState::set_if_neq(&mut next, AiState::Chasing);
+        world.run_schedule(StateTransition);
+
+        world.despawn(entity);
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec![
+                ("enter", AiState::Idle),
+                ("exit", AiState::Idle),
+                ("enter", AiState::Chasing),
+                ("exit", AiState::Chasing),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_entity_state_transitions_are_rejected() {
+        let mut world = setup();
+        world.insert_resource(EntityStateValidator::new(|from: &AiState, _: &AiState| {
+            *from != AiState::Dead
+        }));
+        let entity = world.spawn(EntityState::new(AiState::Dead)).id();
+
+        world
+            .get_mut::<NextEntityState<AiState>>(entity)
+            .unwrap()
+            .set(AiState::Idle);
+        world.run_schedule(StateTransition);
+        assert_eq!(
+            world.get::<EntityState<AiState>>(entity).unwrap(),
+            &AiState::Dead
+        );
+        assert!(matches!(
+            world.get::<NextEntityState<AiState>>(entity).unwrap(),
+            NextEntityState::Unchanged
+        ));
+    }
+}
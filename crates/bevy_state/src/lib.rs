@@ -29,6 +29,8 @@
 //! - The [`in_state<S>`](crate::condition::in_state) and [`state_changed<S>`](crate::condition::state_changed) run conditions - which are used
 //!   to determine whether a system should run based on the current state.
 //!
+//! For finite state machines local to a single entity, such as AI agents, see the [`entity_state`] module.
+//!
 //! Bevy also provides functionality for managing the lifetime of entities in the context of game states, using the [`state_scoped`] module.
 //! Specifically, the marker components [`DespawnOnEnter<S>`](crate::state_scoped::DespawnOnEnter) and [`DespawnOnExit<S>`](crate::state_scoped::DespawnOnExit) are provided for despawning entities on state transition.
 //! This, especially in combination with system scheduling, enables a flexible and expressive way to manage spawning and despawning entities.
@@ -57,6 +59,8 @@ pub mod app;
 pub mod commands;
 /// Provides definitions for the runtime conditions that interact with the state system
 pub mod condition;
+/// Provides per-entity state machines, stored as components.
+pub mod entity_state;
 /// Provides definitions for the basic traits required by the state system
 pub mod state;
 
@@ -87,10 +91,13 @@ pub mod prelude {
     pub use crate::{
         commands::CommandsStatesExt,
         condition::*,
+        entity_state::{
+            EnterEntityState, EntityState, EntityStateValidator, ExitEntityState, NextEntityState,
+        },
         state::{
             last_transition, ComputedStates, EnterSchedules, ExitSchedules, NextState, OnEnter,
-            OnExit, OnTransition, PreviousState, State, StateSet, StateTransition,
-            StateTransitionEvent, States, SubStates, TransitionSchedules,
+            OnExit, OnPause, OnResume, OnTransition, PreviousState, State, StateSet, StateStack,
+            StateTransition, StateTransitionEvent, States, SubStates, TransitionSchedules,
         },
         state_scoped::{DespawnOnEnter, DespawnOnExit},
     };
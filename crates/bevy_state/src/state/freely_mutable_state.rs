@@ -5,7 +5,10 @@ use bevy_ecs::{
     system::{Commands, IntoSystem, ResMut},
 };
 
-use super::{states::States, take_next_state, transitions::*, NextState, PreviousState, State};
+use super::{
+    apply_state_stack, states::States, take_next_state, transitions::*, NextState, PreviousState,
+    State,
+};
 
 /// This trait allows a state to be mutated directly using the [`NextState<S>`](crate::state::NextState) resource.
 ///
@@ -44,6 +47,17 @@ pub trait FreelyMutableState: States {
                     .in_set(EnterSchedules::<Self>::default()),
             );
     }
+
+    /// This function registers the system applying the operations of [`StateStack<Self>`](super::StateStack).
+    ///
+    /// It must be called after [`register_state`](Self::register_state).
+    fn register_state_stack(schedule: &mut Schedule) {
+        schedule.add_systems(
+            apply_state_stack::<Self>
+                .before(apply_state_transition::<Self>)
+                .in_set(ApplyStateTransition::<Self>::default()),
+        );
+    }
 }
 
 fn apply_state_transition<S: FreelyMutableState>(
@@ -2,6 +2,7 @@ mod computed_states;
 mod freely_mutable_state;
 mod resources;
 mod state_set;
+mod state_stack;
 mod states;
 mod sub_states;
 mod transitions;
@@ -11,6 +12,7 @@ pub use computed_states::*;
 pub use freely_mutable_state::*;
 pub use resources::*;
 pub use state_set::*;
+pub use state_stack::*;
 pub use states::*;
 pub use sub_states::*;
 pub use transitions::*;
@@ -0,0 +1,363 @@
+use alloc::vec::Vec;
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    message::MessageWriter,
+    resource::Resource,
+    system::{Commands, Res, ResMut},
+};
+use log::warn;
+
+use super::{
+    freely_mutable_state::FreelyMutableState, internal_apply_state_transition, states::States,
+    NextState, PreviousState, State, StateTransitionEvent,
+};
+
+/// A stack of states, enabling pushdown transitions for [`State<S>`].
+///
+/// While [`NextState<S>`](crate::state::NextState) can only replace the current state,
+/// [`StateStack<S>`] can [`push`](Self::push) a new state on top of the current one, which is then
+/// said to be *covered*, and later [`pop`](Self::pop) back to it.
+/// This is useful for pause menus, dialogs or nested modes, which should return to
+/// whatever state they were opened from.
+///
+/// The top of the stack is always the value of [`State<S>`]: the stack only stores the covered states.
+/// Operations are applied during the [`StateTransition`](crate::state::StateTransition) schedule,
+/// and send a [`StateTransitionEvent<S>`] like any other transition.
+/// Only one transition of `S` can happen per run of that schedule: if a [`NextState<S>`](crate::state::NextState)
+/// transition is pending as well, it is applied first and the stack operation waits for the next run.
+///
+/// - [`push`](Self::push) runs [`OnPause`](crate::state::OnPause) for the covered state instead of
+///   [`OnExit`](crate::state::OnExit), then [`OnEnter`](crate::state::OnEnter) for the new state.
+///   Entities marked with [`DespawnOnExit`](crate::state_scoped::DespawnOnExit) for the covered state are kept.
+/// - [`pop`](Self::pop) runs [`OnExit`](crate::state::OnExit) for the current state, then
+///   [`OnResume`](crate::state::OnResume) for the uncovered state instead of [`OnEnter`](crate::state::OnEnter).
+///   Entities marked with [`DespawnOnEnter`](crate::state_scoped::DespawnOnEnter) for the uncovered state are kept.
+/// - [`replace`](Self::replace) behaves like [`NextState::set`](crate::state::NextState::set),
+///   leaving the covered states untouched.
+///
+/// [`OnTransition`](crate::state::OnTransition) runs for all three operations.
+///
+/// This resource is added by `App::add_state_stack`.
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     InGame,
+///     Paused,
+/// }
+///
+/// fn open_pause_menu(mut stack: ResMut<StateStack<GameState>>) {
+///     stack.push(GameState::Paused);
+/// }
+///
+/// fn close_pause_menu(mut stack: ResMut<StateStack<GameState>>) {
+///     stack.pop();
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct StateStack<S: States> {
+    covered: Vec<S>,
+    pending: Option<PendingStackOperation<S>>,
+    last_operation: Option<StackOperation>,
+}
+
+#[derive(Debug, Clone)]
+enum PendingStackOperation<S> {
+    Push(S),
+    Pop,
+    Replace(S),
+}
+
+/// The kind of operation applied to a [`StateStack`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StackOperation {
+    /// A state was pushed on top of the current one.
+    Push,
+    /// The current state was popped, uncovering the previous one.
+    Pop,
+    /// The current state was replaced.
+    Replace,
+}
+
+impl<S: States> Default for StateStack<S> {
+    fn default() -> Self {
+        Self {
+            covered: Vec::new(),
+            pending: None,
+            last_operation: None,
+        }
+    }
+}
+
+impl<S: States> StateStack<S> {
+    /// Tentatively push `state` on top of the current state.
+    ///
+    /// This overrides any operation queued since the last state transition.
+    pub fn push(&mut self, state: S) {
+        self.pending = Some(PendingStackOperation::Push(state));
+    }
+
+    /// Tentatively pop the current state, returning to the state it covers.
+    ///
+    /// If no state is covered, this has no effect and a warning is logged when it gets applied.
+    /// This overrides any operation queued since the last state transition.
+    pub fn pop(&mut self) {
+        self.pending = Some(PendingStackOperation::Pop);
+    }
+
+    /// Tentatively replace the current state with `state`, without changing the covered states.
+    ///
+    /// This overrides any operation queued since the last state transition.
+    pub fn replace(&mut self, state: S) {
+        self.pending = Some(PendingStackOperation::Replace(state));
+    }
+
+    /// Remove any pending operation.
+    pub fn reset(&mut self) {
+        self.pending = None;
+    }
+
+    /// Returns the covered states, from the bottom of the stack to the top.
+    ///
+    /// The current state, on top of the stack, is stored in [`State<S>`] and is not included.
+    pub fn covered(&self) -> &[S] {
+        &self.covered
+    }
+
+    /// Returns the number of states in the stack, including the current one.
+    pub fn depth(&self) -> usize {
+        self.covered.len() + 1
+    }
+
+    /// Returns the operation applied during the last run of the
+    /// [`StateTransition`](crate::state::StateTransition) schedule, if any.
+    pub fn last_operation(&self) -> Option<StackOperation> {
+        self.last_operation
+    }
+}
+
+pub(crate) fn apply_state_stack<S: FreelyMutableState>(
+    event: MessageWriter<StateTransitionEvent<S>>,
+    commands: Commands,
+    current_state: Option<ResMut<State<S>>>,
+    previous_state: Option<ResMut<PreviousState<S>>>,
+    next_state: Option<Res<NextState<S>>>,
+    stack: Option<ResMut<StateStack<S>>>,
+) {
+    let Some(mut stack) = stack else {
+        return;
+    };
+    stack.bypass_change_detection().last_operation = None;
+    let Some(current_state) = current_state else {
+        return;
+    };
+    // Leave the operation pending until the next run if `NextState` is about to transition,
+    // since only the last `StateTransitionEvent` of a run is used to run the transition schedules.
+    let transition_pending = match next_state.as_deref() {
+        Some(NextState::Pending(_)) => true,
+        Some(NextState::PendingIfNeq(next)) => next != current_state.get(),
+        Some(NextState::Unchanged) | None => false,
+    };
+    if transition_pending {
+        return;
+    }
+    let Some(pending) = stack.bypass_change_detection().pending.take() else {
+        return;
+    };
+    let stack = &mut *stack;
+
+    let (operation, entered) = match pending {
+        PendingStackOperation::Push(entered) => {
+            stack.covered.push(current_state.get().clone());
+            (StackOperation::Push, entered)
+        }
+        PendingStackOperation::Pop => {
+            let Some(entered) = stack.covered.pop() else {
+                let name = core::any::type_name::<S>();
+                warn!("Tried to pop the state stack of {name}, but no state is covered.");
+                return;
+            };
+            (StackOperation::Pop, entered)
+        }
+        PendingStackOperation::Replace(entered) => (StackOperation::Replace, entered),
+    };
+    stack.last_operation = Some(operation);
+
+    internal_apply_state_transition(
+        event,
+        commands,
+        Some(current_state),
+        previous_state,
+        Some(entered),
+        true,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use bevy_app::App;
+    use bevy_ecs::prelude::*;
+    use bevy_state_macros::States;
+
+    use crate::{
+        app::{AppExtStates, StatesPlugin},
+        commands::CommandsStatesExt,
+        state::{OnEnter, OnExit, OnPause, OnResume, State, StateStack},
+        state_scoped::DespawnOnExit,
+    };
+
+    #[derive(States, Default, PartialEq, Eq, Hash, Debug, Clone)]
+    enum GameState {
+        #[default]
+        InGame,
+        Paused,
+        Settings,
+    }
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn log(entry: &'static str) -> impl Fn(ResMut<Log>) {
+        move |mut log: ResMut<Log>| log.0.push(entry)
+    }
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .init_state::<GameState>()
+            .add_state_stack::<GameState>()
+            .init_resource::<Log>()
+            .add_systems(OnEnter(GameState::InGame), log("enter game"))
+            .add_systems(OnExit(GameState::InGame), log("exit game"))
+            .add_systems(OnPause(GameState::InGame), log("pause game"))
+            .add_systems(OnResume(GameState::InGame), log("resume game"))
+            .add_systems(OnEnter(GameState::Paused), log("enter paused"))
+            .add_systems(OnExit(GameState::Paused), log("exit paused"))
+            .add_systems(OnPause(GameState::Paused), log("pause paused"))
+            .add_systems(OnEnter(GameState::Settings), log("enter settings"));
+        app.update();
+        app.world_mut().resource_mut::<Log>().0.clear();
+        app
+    }
+
+    fn state(app: &App) -> &GameState {
+        app.world().resource::<State<GameState>>().get()
+    }
+
+    #[test]
+    fn push_and_pop_run_pause_and_resume_schedules() {
+        let mut app = setup();
+
+        app.world_mut().commands().push_state(GameState::Paused);
+        app.update();
+        assert_eq!(state(&app), &GameState::Paused);
+        let stack = app.world().resource::<StateStack<GameState>>();
+        assert_eq!(stack.covered(), &[GameState::InGame]);
+        assert_eq!(stack.depth(), 2);
+
+        app.world_mut().commands().pop_state::<GameState>();
+        app.update();
+        assert_eq!(state(&app), &GameState::InGame);
+        assert!(app
+            .world()
+            .resource::<StateStack<GameState>>()
+            .covered()
+            .is_empty());
+
+        assert_eq!(
+            app.world().resource::<Log>().0,
+            vec!["pause game", "enter paused", "exit paused", "resume game"]
+        );
+    }
+
+    #[test]
+    fn replace_keeps_covered_states() {
+        let mut app = setup();
+
+        app.world_mut().commands().push_state(GameState::Paused);
+        app.update();
+        app.world_mut()
+            .resource_mut::<StateStack<GameState>>()
+            .replace(GameState::Settings);
+        app.update();
+        assert_eq!(state(&app), &GameState::Settings);
+        assert_eq!(
+            app.world().resource::<StateStack<GameState>>().covered(),
+            &[GameState::InGame]
+        );
+        assert_eq!(
+            app.world().resource::<Log>().0,
+            vec![
+                "pause game",
+                "enter paused",
+                "exit paused",
+                "enter settings"
+            ]
+        );
+    }
+
+    #[test]
+    fn next_state_and_stack_operation_run_on_separate_frames() {
+        let mut app = setup();
+
+        app.world_mut().commands().set_state(GameState::Paused);
+        app.world_mut().commands().push_state(GameState::Settings);
+        app.update();
+        assert_eq!(state(&app), &GameState::Paused);
+        assert_eq!(
+            app.world().resource::<Log>().0,
+            vec!["exit game", "enter paused"]
+        );
+
+        app.update();
+        assert_eq!(state(&app), &GameState::Settings);
+        assert_eq!(
+            app.world().resource::<StateStack<GameState>>().covered(),
+            &[GameState::Paused]
+        );
+        assert_eq!(
+            app.world().resource::<Log>().0,
+            vec![
+                "exit game",
+                "enter paused",
+                "pause paused",
+                "enter settings"
+            ]
+        );
+    }
+
+    #[test]
+    fn popping_an_empty_stack_is_ignored() {
+        let mut app = setup();
+
+        app.world_mut().commands().pop_state::<GameState>();
+        app.update();
+        assert_eq!(state(&app), &GameState::InGame);
+        assert!(app.world().resource::<Log>().0.is_empty());
+    }
+
+    #[test]
+    fn covered_states_keep_their_scoped_entities() {
+        let mut app = setup();
+        let game_entity = app.world_mut().spawn(DespawnOnExit(GameState::InGame)).id();
+
+        app.world_mut().commands().push_state(GameState::Paused);
+        app.update();
+        let paused_entity = app.world_mut().spawn(DespawnOnExit(GameState::Paused)).id();
+        assert!(app.world().get_entity(game_entity).is_ok());
+
+        app.world_mut().commands().pop_state::<GameState>();
+        app.update();
+        assert!(app.world().get_entity(game_entity).is_ok());
+        assert!(app.world().get_entity(paused_entity).is_err());
+
+        app.world_mut().commands().set_state(GameState::Settings);
+        app.update();
+        assert!(app.world().get_entity(game_entity).is_err());
+    }
+}
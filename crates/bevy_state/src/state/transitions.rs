@@ -9,6 +9,7 @@ use bevy_ecs::{
 
 use super::{
     resources::{PreviousState, State},
+    state_stack::{StackOperation, StateStack},
     states::States,
 };
 
@@ -38,6 +39,20 @@ pub struct OnTransition<S: States> {
     pub entered: S,
 }
 
+/// The label of a [`Schedule`] that **only** runs whenever the provided state of [`State<S>`]
+/// gets covered by a state pushed onto its [`StateStack<S>`](super::StateStack).
+///
+/// It runs instead of [`OnExit`] for the covered state.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct OnPause<S: States>(pub S);
+
+/// The label of a [`Schedule`] that **only** runs whenever the provided state of [`State<S>`]
+/// gets uncovered by popping its [`StateStack<S>`](super::StateStack).
+///
+/// It runs instead of [`OnEnter`] for the uncovered state.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct OnResume<S: States>(pub S);
+
 /// Runs [state transitions](States).
 ///
 /// By default, it will be triggered once before [`PreStartup`] and then each frame after [`PreUpdate`], but
@@ -254,7 +269,12 @@ pub(crate) fn run_enter<S: States>(
         return;
     };
 
-    let _ = world.try_run_schedule(OnEnter(entered));
+    let stack = world.get_resource::<StateStack<S>>();
+    if stack.and_then(StateStack::last_operation) == Some(StackOperation::Pop) {
+        let _ = world.try_run_schedule(OnResume(entered));
+    } else {
+        let _ = world.try_run_schedule(OnEnter(entered));
+    }
 }
 
 pub(crate) fn run_exit<S: States>(
@@ -271,7 +291,12 @@ pub(crate) fn run_exit<S: States>(
         return;
     };
 
-    let _ = world.try_run_schedule(OnExit(exited));
+    let stack = world.get_resource::<StateStack<S>>();
+    if stack.and_then(StateStack::last_operation) == Some(StackOperation::Push) {
+        let _ = world.try_run_schedule(OnPause(exited));
+    } else {
+        let _ = world.try_run_schedule(OnExit(exited));
+    }
 }
 
 pub(crate) fn run_transition<S: States>(
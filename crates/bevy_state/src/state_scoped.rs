@@ -6,12 +6,12 @@ use bevy_ecs::{
     entity_disabling::Disabled,
     message::MessageReader,
     query::Allow,
-    system::{Commands, Query},
+    system::{Commands, Query, Res},
 };
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::prelude::*;
 
-use crate::state::{StateTransitionEvent, States};
+use crate::state::{StackOperation, StateStack, StateTransitionEvent, States};
 
 /// Entities marked with this component will be removed
 /// when the world's state of the matching type no longer matches the supplied value.
@@ -68,10 +68,13 @@ where
 /// Despawns entities marked with [`DespawnOnExit<S>`] when their state no
 /// longer matches the world state.
 ///
+/// Entities are kept when their state is only covered by a state pushed onto the [`StateStack<S>`].
+///
 /// If the entity has already been despawned no warning will be emitted.
 pub fn despawn_entities_on_exit_state<S: States>(
     mut commands: Commands,
     mut transitions: MessageReader<StateTransitionEvent<S>>,
+    stack: Option<Res<StateStack<S>>>,
     query: Query<(Entity, &DespawnOnExit<S>), Allow<Disabled>>,
 ) {
     // We use the latest event, because state machine internals generate at most 1
@@ -86,6 +89,9 @@ pub fn despawn_entities_on_exit_state<S: States>(
     let Some(exited) = &transition.exited else {
         return;
     };
+    if stack.is_some_and(|stack| stack.last_operation() == Some(StackOperation::Push)) {
+        return;
+    }
     for (entity, binding) in &query {
         if binding.0 == *exited {
             commands.entity(entity).try_despawn();
@@ -136,10 +142,13 @@ pub struct DespawnOnEnter<S: States>(pub S);
 /// Despawns entities marked with [`DespawnOnEnter<S>`] when their state
 /// matches the world state.
 ///
+/// Entities are kept when their state is uncovered by popping the [`StateStack<S>`].
+///
 /// If the entity has already been despawned no warning will be emitted.
 pub fn despawn_entities_on_enter_state<S: States>(
     mut commands: Commands,
     mut transitions: MessageReader<StateTransitionEvent<S>>,
+    stack: Option<Res<StateStack<S>>>,
     query: Query<(Entity, &DespawnOnEnter<S>), Allow<Disabled>>,
 ) {
     // We use the latest event, because state machine internals generate at most 1
@@ -154,6 +163,9 @@ pub fn despawn_entities_on_enter_state<S: States>(
     let Some(entered) = &transition.entered else {
         return;
     };
+    if stack.is_some_and(|stack| stack.last_operation() == Some(StackOperation::Pop)) {
+        return;
+    }
     for (entity, binding) in &query {
         if binding.0 == *entered {
             commands.entity(entity).try_despawn();
@@ -4,7 +4,10 @@ use bevy_math::{IVec2, UVec2};
 use bevy_platform::collections::HashMap;
 use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
 
-use crate::{FontSmoothing, GlyphAtlasInfo, GlyphAtlasLocation, TextError};
+use crate::{
+    get_sdf_glyph_texture, sdf_cache_key, FontSmoothing, GlyphAtlasInfo, GlyphAtlasLocation,
+    TextError,
+};
 
 /// Rasterized glyphs are cached, stored in, and retrieved from, a `FontAtlas`.
 ///
@@ -45,8 +48,11 @@ impl FontAtlas {
             // Need to keep this image CPU persistent in order to add additional glyphs later on
             RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
         );
-        if font_smoothing == FontSmoothing::None {
-            image.sampler = ImageSampler::nearest();
+        match font_smoothing {
+            FontSmoothing::None => image.sampler = ImageSampler::nearest(),
+            // Distance fields are scaled by the renderer, so they must always be filtered.
+            FontSmoothing::Sdf => image.sampler = ImageSampler::linear(),
+            FontSmoothing::AntiAliased => {}
         }
         let texture = textures.add(image);
         let texture_atlas = texture_atlases_layout.add(TextureAtlasLayout::new_empty(size));
@@ -124,6 +130,8 @@ impl core::fmt::Debug for FontAtlas {
 }
 
 /// Adds the given subpixel-offset glyph to the given font atlases
+///
+/// With [`FontSmoothing::Sdf`], the glyph's signed distance field is added instead.
 pub fn add_glyph_to_atlas(
     font_atlases: &mut Vec<FontAtlas>,
     texture_atlases: &mut Assets<TextureAtlasLayout>,
@@ -133,19 +141,22 @@ pub fn add_glyph_to_atlas(
     layout_glyph: &cosmic_text::LayoutGlyph,
     font_smoothing: FontSmoothing,
 ) -> Result<GlyphAtlasInfo, TextError> {
-    let physical_glyph = layout_glyph.physical((0., 0.), 1.0);
-
-    let (glyph_texture, offset) =
-        get_outlined_glyph_texture(font_system, swash_cache, &physical_glyph, font_smoothing)?;
-    let mut add_char_to_font_atlas = |atlas: &mut FontAtlas| -> Result<(), TextError> {
-        atlas.add_glyph(
-            textures,
-            texture_atlases,
+    let (cache_key, (glyph_texture, offset)) = if font_smoothing == FontSmoothing::Sdf {
+        let cache_key = sdf_cache_key(layout_glyph);
+        (
+            cache_key,
+            get_sdf_glyph_texture(font_system, swash_cache, cache_key)?,
+        )
+    } else {
+        let physical_glyph = layout_glyph.physical((0., 0.), 1.0);
+        (
             physical_glyph.cache_key,
-            &glyph_texture,
-            offset,
+            get_outlined_glyph_texture(font_system, swash_cache, &physical_glyph, font_smoothing)?,
         )
     };
+    let mut add_char_to_font_atlas = |atlas: &mut FontAtlas| -> Result<(), TextError> {
+        atlas.add_glyph(textures, texture_atlases, cache_key, &glyph_texture, offset)
+    };
     if !font_atlases
         .iter_mut()
         .any(|atlas| add_char_to_font_atlas(atlas).is_ok())
@@ -166,19 +177,12 @@ pub fn add_glyph_to_atlas(
             font_smoothing,
         );
 
-        new_atlas.add_glyph(
-            textures,
-            texture_atlases,
-            physical_glyph.cache_key,
-            &glyph_texture,
-            offset,
-        )?;
+        new_atlas.add_glyph(textures, texture_atlases, cache_key, &glyph_texture, offset)?;
 
         font_atlases.push(new_atlas);
     }
 
-    get_glyph_atlas_info(font_atlases, physical_glyph.cache_key)
-        .ok_or(TextError::InconsistentAtlasState)
+    get_glyph_atlas_info(font_atlases, cache_key).ok_or(TextError::InconsistentAtlasState)
 }
 
 /// Get the texture of the glyph as a rendered image, and its offset
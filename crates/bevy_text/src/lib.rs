@@ -38,7 +38,10 @@ mod font_atlas;
 mod font_atlas_set;
 mod font_loader;
 mod glyph;
+#[cfg(feature = "markup")]
+mod markup;
 mod pipeline;
+mod sdf;
 mod text;
 mod text_access;
 
@@ -50,7 +53,10 @@ pub use font_atlas::*;
 pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph::*;
+#[cfg(feature = "markup")]
+pub use markup::*;
 pub use pipeline::*;
+pub use sdf::*;
 pub use text::*;
 pub use text_access::*;
 
@@ -62,7 +68,7 @@ pub mod prelude {
     pub use crate::{
         Font, FontHinting, FontSmoothing, FontSource, FontStyle, FontWeight, FontWidth, Justify,
         LineBreak, Strikethrough, StrikethroughColor, TextColor, TextError, TextFont, TextLayout,
-        TextSpan, Underline, UnderlineColor,
+        TextSdfEffects, TextSpan, Underline, UnderlineColor,
     };
 }
 
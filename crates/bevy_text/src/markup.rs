@@ -0,0 +1,597 @@
+//! A small BBCode-like markup language for styling text spans inline.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use bevy_color::{Color, Srgba};
+use bevy_ecs::{
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    world::{EntityRef, EntityWorldMut, World},
+};
+use thiserror::Error;
+
+use crate::{
+    FontStyle, FontWeight, LineHeight, Strikethrough, TextColor, TextFont, TextRoot, TextSpan,
+    Underline,
+};
+
+/// Errors that can occur when parsing [`TextMarkup`].
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum MarkupError {
+    /// A `[` starting a tag was never followed by a `]`.
+    #[error("unterminated tag")]
+    UnterminatedTag,
+    /// The tag is not one of the supported tags.
+    #[error("unknown tag `{0}`")]
+    UnknownTag(String),
+    /// The value of a tag could not be parsed, or a tag that doesn't take a value was given one.
+    #[error("invalid value `{value}` for tag `{tag}`")]
+    InvalidValue {
+        /// The name of the tag.
+        tag: String,
+        /// The value of the tag.
+        value: String,
+    },
+    /// A tag that requires a value was used without one.
+    #[error("missing value for tag `{0}`")]
+    MissingValue(String),
+    /// A closing tag does not match the most recently opened tag.
+    #[error("unexpected closing tag `{0}`")]
+    UnexpectedClosingTag(String),
+    /// A tag was never closed.
+    #[error("unclosed tag `{0}`")]
+    UnclosedTag(String),
+}
+
+/// Style overrides applied to a [`MarkupSpan`]. Fields that are `None` keep the style of the
+/// text block's root entity.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkupStyle {
+    /// Overrides the [`TextColor`].
+    pub color: Option<Color>,
+    /// Overrides [`TextFont::weight`].
+    pub weight: Option<FontWeight>,
+    /// Overrides [`TextFont::style`].
+    pub style: Option<FontStyle>,
+    /// Overrides [`TextFont::font_size`].
+    pub size: Option<f32>,
+    /// Whether the text is drawn with [`Underline`].
+    pub underline: bool,
+    /// Whether the text is drawn with [`Strikethrough`].
+    pub strikethrough: bool,
+}
+
+impl MarkupStyle {
+    /// Returns `base` with the font overrides of this style applied.
+    pub fn font(&self, base: &TextFont) -> TextFont {
+        let mut font = base.clone();
+        if let Some(weight) = self.weight {
+            font.weight = weight;
+        }
+        if let Some(style) = self.style {
+            font.style = style;
+        }
+        if let Some(size) = self.size {
+            font.font_size = size;
+        }
+        font
+    }
+
+    /// Returns the style of a text entity, relative to the style of its root entity.
+    fn from_entity(entity: EntityRef, base_font: &TextFont, base_color: Color) -> Self {
+        let font = entity.get::<TextFont>().unwrap_or(base_font);
+        let color = entity
+            .get::<TextColor>()
+            .map_or(base_color, |color| color.0);
+        Self {
+            color: (color != base_color).then_some(color),
+            weight: (font.weight != base_font.weight).then_some(font.weight),
+            style: (font.style != base_font.style).then_some(font.style),
+            size: (font.font_size != base_font.font_size).then_some(font.font_size),
+            underline: entity.contains::<Underline>(),
+            strikethrough: entity.contains::<Strikethrough>(),
+        }
+    }
+
+    fn apply_tag(&mut self, tag: &str, value: Option<&str>) -> Result<(), MarkupError> {
+        let invalid = |value: &str| MarkupError::InvalidValue {
+            tag: tag.to_string(),
+            value: value.to_string(),
+        };
+        match (tag, value) {
+            ("b" | "i" | "u" | "s", Some(value)) => return Err(invalid(value)),
+            ("b", None) => self.weight = Some(FontWeight::BOLD),
+            ("i", None) => self.style = Some(FontStyle::Italic),
+            ("u", None) => self.underline = true,
+            ("s", None) => self.strikethrough = true,
+            ("weight" | "style" | "size" | "color", None) => {
+                return Err(MarkupError::MissingValue(tag.to_string()))
+            }
+            ("weight", Some(value)) => {
+                self.weight = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|weight| (1..=1000).contains(weight))
+                        .map(FontWeight)
+                        .ok_or_else(|| invalid(value))?,
+                );
+            }
+            ("style", Some(value)) => {
+                self.style = Some(match value {
+                    "normal" => FontStyle::Normal,
+                    "italic" => FontStyle::Italic,
+                    "oblique" => FontStyle::Oblique,
+                    _ => return Err(invalid(value)),
+                });
+            }
+            ("size", Some(value)) => {
+                self.size = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|size: &f32| size.is_finite() && *size > 0.)
+                        .ok_or_else(|| invalid(value))?,
+                );
+            }
+            ("color", Some(value)) => {
+                self.color = Some(Srgba::hex(value).map_err(|_| invalid(value))?.into());
+            }
+            _ => return Err(MarkupError::UnknownTag(tag.to_string())),
+        }
+        Ok(())
+    }
+}
+
+/// A run of text sharing a single [`MarkupStyle`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkupSpan {
+    /// The text of the span, without markup.
+    pub text: String,
+    /// The style of the span.
+    pub style: MarkupStyle,
+}
+
+/// Styled text parsed from markup, as a flat list of spans. Nested tags are resolved into the
+/// combined style of each span.
+///
+/// Use [`TextMarkup::apply`] to turn the spans into [`TextSpan`] children of a text entity,
+/// and [`TextMarkup::from_text_block`] to read them back. Formatting a [`TextMarkup`] with
+/// [`Display`](fmt::Display) produces markup which parses back into the same spans.
+///
+/// # Syntax
+///
+/// | Tag                          | Effect                                   |
+/// |------------------------------|------------------------------------------|
+/// | `[b]...[/b]`                 | [`FontWeight::BOLD`]                     |
+/// | `[weight=300]...[/weight]`   | Any [`FontWeight`]                       |
+/// | `[i]...[/i]`                 | [`FontStyle::Italic`]                    |
+/// | `[style=oblique]...[/style]` | `normal`, `italic` or `oblique`          |
+/// | `[size=32]...[/size]`        | Font size in pixels                      |
+/// | `[color=#ff8000]...[/color]` | A CSS-style hexadecimal [`TextColor`]    |
+/// | `[u]...[/u]`                 | [`Underline`]                            |
+/// | `[s]...[/s]`                 | [`Strikethrough`]                        |
+///
+/// Tags can be nested, and must be closed in the reverse order they were opened.
+/// A literal `[` is written as `[[`.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_text::TextMarkup;
+/// let markup = TextMarkup::parse("Press [b][color=#FF0000]start[/color][/b] to [[continue]").unwrap();
+/// assert_eq!(markup.spans.len(), 3);
+/// assert_eq!(markup.spans[2].text, " to [continue]");
+///
+/// let text = markup.to_string();
+/// assert_eq!(text, "Press [color=#FF0000][b]start[/b][/color] to [[continue]");
+/// assert_eq!(TextMarkup::parse(&text).unwrap(), markup);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextMarkup {
+    /// The spans of the text, in order.
+    pub spans: Vec<MarkupSpan>,
+}
+
+impl TextMarkup {
+    /// Parses a marked-up string.
+    pub fn parse(markup: &str) -> Result<Self, MarkupError> {
+        let mut result = Self::default();
+        let mut style = MarkupStyle::default();
+        let mut open_tags: Vec<(&str, MarkupStyle)> = Vec::new();
+        let mut text = String::new();
+        let mut rest = markup;
+
+        while let Some(start) = rest.find('[') {
+            text.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+            if let Some(escaped) = rest.strip_prefix('[') {
+                text.push('[');
+                rest = escaped;
+                continue;
+            }
+
+            let end = rest.find(']').ok_or(MarkupError::UnterminatedTag)?;
+            let tag = &rest[..end];
+            rest = &rest[end + 1..];
+            result.push(core::mem::take(&mut text), style.clone());
+
+            if let Some(name) = tag.strip_prefix('/') {
+                match open_tags.pop() {
+                    Some((open, previous)) if open == name => style = previous,
+                    _ => return Err(MarkupError::UnexpectedClosingTag(name.to_string())),
+                }
+            } else {
+                let (name, value) = match tag.split_once('=') {
+                    Some((name, value)) => (name, Some(value)),
+                    None => (tag, None),
+                };
+                open_tags.push((name, style.clone()));
+                style.apply_tag(name, value)?;
+            }
+        }
+        text.push_str(rest);
+        result.push(text, style);
+
+        if let Some((name, _)) = open_tags.pop() {
+            return Err(MarkupError::UnclosedTag(name.to_string()));
+        }
+        Ok(result)
+    }
+
+    /// Appends text with the given style, merging it into the last span if the styles match.
+    pub fn push(&mut self, text: impl Into<String>, style: MarkupStyle) {
+        let text = text.into();
+        if text.is_empty() {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some(last) if last.style == style => last.text.push_str(&text),
+            _ => self.spans.push(MarkupSpan { text, style }),
+        }
+    }
+
+    /// Replaces the text of a text block with these spans.
+    ///
+    /// The text of the root component `R` is cleared, any existing [`TextSpan`] children are
+    /// despawned, and each span is spawned as a [`TextSpan`] child. Spans inherit the
+    /// [`TextFont`], [`TextColor`] and [`LineHeight`] of the root entity, with the overrides of
+    /// their [`MarkupStyle`] applied.
+    ///
+    /// This can be queued as an entity command:
+    ///
+    /// ```
+    /// # use bevy_ecs::{entity::Entity, system::Commands, world::EntityWorldMut};
+    /// # use bevy_text::{TextMarkup, TextRoot};
+    /// fn show_dialogue<R: TextRoot>(mut commands: Commands, entity: Entity) {
+    ///     let markup = TextMarkup::parse("[i]Hello[/i], world!").unwrap();
+    ///     commands
+    ///         .entity(entity)
+    ///         .queue(move |mut entity: EntityWorldMut| markup.apply::<R>(&mut entity));
+    /// }
+    /// ```
+    pub fn apply<R: TextRoot>(&self, entity: &mut EntityWorldMut) {
+        let base_font = entity.get::<TextFont>().cloned().unwrap_or_default();
+        let base_color = entity.get::<TextColor>().copied().unwrap_or_default();
+        let line_height = entity.get::<LineHeight>().copied().unwrap_or_default();
+        match entity.get_mut::<R>() {
+            Some(mut root) => root.write_span().clear(),
+            None => {
+                entity.insert(R::from(String::new()));
+            }
+        }
+
+        let root = entity.id();
+        let children: Vec<Entity> = entity
+            .get::<Children>()
+            .map(|children| children.iter().copied().collect())
+            .unwrap_or_default();
+        entity.world_scope(|world| {
+            for child in children {
+                if world.get::<TextSpan>(child).is_some() {
+                    world.despawn(child);
+                }
+            }
+            for span in &self.spans {
+                let mut span_entity = world.spawn((
+                    TextSpan(span.text.clone()),
+                    span.style.font(&base_font),
+                    span.style.color.map_or(base_color, TextColor),
+                    line_height,
+                    ChildOf(root),
+                ));
+                if span.style.underline {
+                    span_entity.insert(Underline);
+                }
+                if span.style.strikethrough {
+                    span_entity.insert(Strikethrough);
+                }
+            }
+        });
+    }
+
+    /// Reads the spans of a text block back into markup, describing each span's style relative
+    /// to the style of the root entity.
+    ///
+    /// Returns `None` if `root` doesn't have the root text component `R`.
+    pub fn from_text_block<R: TextRoot>(world: &World, root: Entity) -> Option<Self> {
+        let root = world.get_entity(root).ok()?;
+        let base_font = root.get::<TextFont>().cloned().unwrap_or_default();
+        let base_color = root.get::<TextColor>().copied().unwrap_or_default().0;
+
+        let mut markup = Self::default();
+        markup.push(
+            root.get::<R>()?.read_span(),
+            MarkupStyle::from_entity(root, &base_font, base_color),
+        );
+        markup.push_children(world, root, &base_font, base_color);
+        Some(markup)
+    }
+
+    fn push_children(
+        &mut self,
+        world: &World,
+        entity: EntityRef,
+        base_font: &TextFont,
+        base_color: Color,
+    ) {
+        let Some(children) = entity.get::<Children>() else {
+            return;
+        };
+        for child in children.iter() {
+            let Ok(child) = world.get_entity(*child) else {
+                continue;
+            };
+            let Some(span) = child.get::<TextSpan>() else {
+                continue;
+            };
+            self.push(
+                span.0.clone(),
+                MarkupStyle::from_entity(child, base_font, base_color),
+            );
+            self.push_children(world, child, base_font, base_color);
+        }
+    }
+}
+
+impl fmt::Display for TextMarkup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for span in &self.spans {
+            let style = &span.style;
+            let mut closing = Vec::new();
+            if let Some(color) = style.color {
+                write!(f, "[color={}]", Srgba::from(color).to_hex())?;
+                closing.push("color");
+            }
+            if let Some(size) = style.size {
+                write!(f, "[size={size}]")?;
+                closing.push("size");
+            }
+            match style.weight {
+                Some(FontWeight::BOLD) => {
+                    f.write_str("[b]")?;
+                    closing.push("b");
+                }
+                Some(weight) => {
+                    write!(f, "[weight={}]", weight.0)?;
+                    closing.push("weight");
+                }
+                None => {}
+            }
+            match style.style {
+                Some(FontStyle::Italic) => {
+                    f.write_str("[i]")?;
+                    closing.push("i");
+                }
+                Some(FontStyle::Oblique) => {
+                    f.write_str("[style=oblique]")?;
+                    closing.push("style");
+                }
+                Some(FontStyle::Normal) => {
+                    f.write_str("[style=normal]")?;
+                    closing.push("style");
+                }
+                None => {}
+            }
+            if style.underline {
+                f.write_str("[u]")?;
+                closing.push("u");
+            }
+            if style.strikethrough {
+                f.write_str("[s]")?;
+                closing.push("s");
+            }
+
+            for (index, part) in span.text.split('[').enumerate() {
+                if index > 0 {
+                    f.write_str("[[")?;
+                }
+                f.write_str(part)?;
+            }
+
+            for tag in closing.iter().rev() {
+                write!(f, "[/{tag}]")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_color::palettes::basic::{RED, WHITE};
+    use bevy_ecs::component::Component;
+
+    use crate::TextSpanAccess;
+
+    #[derive(Component, Default)]
+    struct TestText(String);
+
+    impl From<String> for TestText {
+        fn from(value: String) -> Self {
+            Self(value)
+        }
+    }
+
+    impl TextSpanAccess for TestText {
+        fn read_span(&self) -> &str {
+            &self.0
+        }
+        fn write_span(&mut self) -> &mut String {
+            &mut self.0
+        }
+    }
+
+    impl TextRoot for TestText {}
+
+    fn span(text: &str, style: MarkupStyle) -> MarkupSpan {
+        MarkupSpan {
+            text: text.to_string(),
+            style,
+        }
+    }
+
+    #[test]
+    fn nested_tags_combine() {
+        let markup = TextMarkup::parse("[b]bold [i]both[/i][/b] [size=8]small[/size]").unwrap();
+        let bold = MarkupStyle {
+            weight: Some(FontWeight::BOLD),
+            ..Default::default()
+        };
+        assert_eq!(
+            markup.spans,
+            [
+                span("bold ", bold.clone()),
+                span(
+                    "both",
+                    MarkupStyle {
+                        style: Some(FontStyle::Italic),
+                        ..bold
+                    }
+                ),
+                span(" ", MarkupStyle::default()),
+                span(
+                    "small",
+                    MarkupStyle {
+                        size: Some(8.),
+                        ..Default::default()
+                    }
+                ),
+            ]
+        );
+        assert_eq!(TextMarkup::parse(&markup.to_string()).unwrap(), markup);
+    }
+
+    #[test]
+    fn unclosed_and_mismatched_tags() {
+        assert_eq!(
+            TextMarkup::parse("[b]bold"),
+            Err(MarkupError::UnclosedTag("b".to_string()))
+        );
+        assert_eq!(
+            TextMarkup::parse("[b][i]both[/b][/i]"),
+            Err(MarkupError::UnexpectedClosingTag("b".to_string()))
+        );
+        assert_eq!(
+            TextMarkup::parse("plain[/u]"),
+            Err(MarkupError::UnexpectedClosingTag("u".to_string()))
+        );
+        assert_eq!(
+            TextMarkup::parse("[color=#fff"),
+            Err(MarkupError::UnterminatedTag)
+        );
+        assert_eq!(
+            TextMarkup::parse("[big]text[/big]"),
+            Err(MarkupError::UnknownTag("big".to_string()))
+        );
+        assert_eq!(
+            TextMarkup::parse("[size]text[/size]"),
+            Err(MarkupError::MissingValue("size".to_string()))
+        );
+        assert_eq!(
+            TextMarkup::parse("[b=1]text[/b]"),
+            Err(MarkupError::InvalidValue {
+                tag: "b".to_string(),
+                value: "1".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn escaped_brackets() {
+        let markup = TextMarkup::parse("[[b] is [u]not[/u] bold]").unwrap();
+        assert_eq!(
+            markup.spans,
+            [
+                span("[b] is ", MarkupStyle::default()),
+                span(
+                    "not",
+                    MarkupStyle {
+                        underline: true,
+                        ..Default::default()
+                    }
+                ),
+                span(" bold]", MarkupStyle::default()),
+            ]
+        );
+        assert_eq!(markup.to_string(), "[[b] is [u]not[/u] bold]");
+    }
+
+    #[test]
+    fn apply_and_read_back() {
+        let mut world = World::new();
+        let base_font = TextFont {
+            font_size: 10.,
+            ..Default::default()
+        };
+        let root = world
+            .spawn((
+                TestText::from("old".to_string()),
+                base_font.clone(),
+                TextColor(WHITE.into()),
+            ))
+            .id();
+        let old_span = world.spawn((TextSpan::new("old span"), ChildOf(root))).id();
+
+        let markup =
+            TextMarkup::parse("Hello [b][color=#FF0000]world[/color][/b][s][size=32]![/size][/s]")
+                .unwrap();
+        markup.apply::<TestText>(&mut world.entity_mut(root));
+
+        assert_eq!(world.get::<TestText>(root).unwrap().0, "");
+        assert!(world.get_entity(old_span).is_err());
+        let spans: Vec<Entity> = world.get::<Children>(root).unwrap().to_vec();
+        assert_eq!(spans.len(), 3);
+
+        let hello = world.entity(spans[0]);
+        assert_eq!(hello.get::<TextSpan>().unwrap().0, "Hello ");
+        assert_eq!(hello.get::<TextFont>().unwrap().font_size, 10.);
+        assert_eq!(hello.get::<TextColor>().unwrap().0, WHITE.into());
+
+        let world_span = world.entity(spans[1]);
+        assert_eq!(world_span.get::<TextSpan>().unwrap().0, "world");
+        assert_eq!(
+            world_span.get::<TextFont>().unwrap().weight,
+            FontWeight::BOLD
+        );
+        assert_eq!(world_span.get::<TextColor>().unwrap().0, RED.into());
+
+        let bang = world.entity(spans[2]);
+        assert_eq!(bang.get::<TextSpan>().unwrap().0, "!");
+        assert_eq!(bang.get::<TextFont>().unwrap().font_size, 32.);
+        assert!(bang.contains::<Strikethrough>());
+        assert!(!bang.contains::<Underline>());
+
+        assert_eq!(
+            TextMarkup::from_text_block::<TestText>(&world, root),
+            Some(markup)
+        );
+        assert_eq!(
+            TextMarkup::from_text_block::<TestText>(&world, spans[0]),
+            None
+        );
+    }
+}
@@ -11,9 +11,10 @@ use bevy_math::{Rect, UVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 
 use crate::{
-    add_glyph_to_atlas, error::TextError, get_glyph_atlas_info, ComputedTextBlock, Font,
-    FontAtlasKey, FontAtlasSet, FontHinting, FontSmoothing, FontSource, FontStyle, FontWeight,
-    Justify, LineBreak, LineHeight, PositionedGlyph, TextBounds, TextEntity, TextFont, TextLayout,
+    add_glyph_to_atlas, error::TextError, get_glyph_atlas_info, sdf_cache_key, ComputedTextBlock,
+    Font, FontAtlasKey, FontAtlasSet, FontHinting, FontSmoothing, FontSource, FontStyle,
+    FontWeight, Justify, LineBreak, LineHeight, PositionedGlyph, TextBounds, TextEntity, TextFont,
+    TextLayout, SDF_FONT_SIZE,
 };
 use cosmic_text::{Attrs, Buffer, Family, Metrics, Shaping, Wrap};
 
@@ -417,16 +418,21 @@ impl TextPipeline {
                 };
 
                 let physical_glyph = layout_glyph.physical((0., 0.), 1.);
+                let cache_key = if font_smoothing == FontSmoothing::Sdf {
+                    sdf_cache_key(layout_glyph)
+                } else {
+                    physical_glyph.cache_key
+                };
 
                 let font_atlases = font_atlas_set
                     .entry(FontAtlasKey {
-                        id: physical_glyph.cache_key.font_id,
-                        font_size_bits: physical_glyph.cache_key.font_size_bits,
+                        id: cache_key.font_id,
+                        font_size_bits: cache_key.font_size_bits,
                         font_smoothing,
                     })
                     .or_default();
 
-                let atlas_info = get_glyph_atlas_info(font_atlases, physical_glyph.cache_key)
+                let atlas_info = get_glyph_atlas_info(font_atlases, cache_key)
                     .map(Ok)
                     .unwrap_or_else(|| {
                         add_glyph_to_atlas(
@@ -445,18 +451,30 @@ impl TextPipeline {
                 let glyph_rect = texture_atlas.textures[location.glyph_index];
                 let left = location.offset.x as f32;
                 let top = location.offset.y as f32;
-                let glyph_size = UVec2::new(glyph_rect.width(), glyph_rect.height());
-
-                // offset by half the size because the origin is center
-                let x = glyph_size.x as f32 / 2.0 + left + physical_glyph.x as f32;
-                let y =
-                    run.line_y.round() + physical_glyph.y as f32 - top + glyph_size.y as f32 / 2.0;
-
-                let position = Vec2::new(x, y);
+                let glyph_size = UVec2::new(glyph_rect.width(), glyph_rect.height()).as_vec2();
+
+                let (position, size) = if font_smoothing == FontSmoothing::Sdf {
+                    // Distance fields are generated at `SDF_FONT_SIZE` and scaled to the glyph's size,
+                    // keeping the unrounded glyph position.
+                    let scale = layout_glyph.font_size / SDF_FONT_SIZE;
+                    let size = glyph_size * scale;
+                    let x = layout_glyph.x + layout_glyph.font_size * layout_glyph.x_offset;
+                    let y = run.line_y + layout_glyph.y
+                        - layout_glyph.font_size * layout_glyph.y_offset;
+                    (
+                        Vec2::new(x + left * scale, y - top * scale) + size / 2.0,
+                        size,
+                    )
+                } else {
+                    // offset by half the size because the origin is center
+                    let x = glyph_size.x / 2.0 + left + physical_glyph.x as f32;
+                    let y = run.line_y.round() + physical_glyph.y as f32 - top + glyph_size.y / 2.0;
+                    (Vec2::new(x, y), glyph_size)
+                };
 
                 let pos_glyph = PositionedGlyph {
                     position,
-                    size: glyph_size.as_vec2(),
+                    size,
                     atlas_info,
                     span_index,
                     byte_index: layout_glyph.start,
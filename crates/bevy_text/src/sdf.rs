@@ -0,0 +1,524 @@
+use bevy_asset::RenderAssetUsages;
+use bevy_color::Color;
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_image::Image;
+use bevy_math::{IVec2, Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::TextError;
+
+/// The font size at which glyphs are rasterized into signed distance field atlases.
+///
+/// Glyphs using [`FontSmoothing::Sdf`](crate::FontSmoothing::Sdf) are always generated at this size
+/// and scaled to their final size by the renderer, so a single atlas is shared by every font size.
+pub const SDF_FONT_SIZE: f32 = 48.;
+
+/// The distance in atlas texels, on either side of a glyph's outline, encoded by its signed distance field.
+///
+/// Each distance field texture is padded by this many texels. Effects such as outlines, glows and
+/// soft shadows can extend at most this far from the glyph, which at the canonical [`SDF_FONT_SIZE`]
+/// amounts to one sixth of the font size.
+pub const SDF_RANGE: f32 = 8.;
+
+/// Returns the [`CacheKey`](cosmic_text::CacheKey) used to store the signed distance field of a glyph.
+///
+/// Unlike bitmap glyphs, distance fields don't depend on the font size or the subpixel offset of the glyph.
+pub fn sdf_cache_key(layout_glyph: &cosmic_text::LayoutGlyph) -> cosmic_text::CacheKey {
+    cosmic_text::CacheKey::new(
+        layout_glyph.font_id,
+        layout_glyph.glyph_id,
+        SDF_FONT_SIZE,
+        (0., 0.),
+        layout_glyph.font_weight,
+        layout_glyph.cache_key_flags | cosmic_text::CacheKeyFlags::DISABLE_HINTING,
+    )
+    .0
+}
+
+/// Generate the signed distance field of a glyph from its outline, and its offset.
+///
+/// The distance to the outline is stored in the alpha channel: `0.5` lies exactly on the outline,
+/// with larger values inside the glyph and smaller values outside it, reaching `1.0` and `0.0`
+/// at [`SDF_RANGE`] texels away from the outline. Color glyphs are reduced to their silhouette.
+///
+/// Glyphs without outlines, such as those of bitmap fonts, are rasterized instead, storing their coverage
+/// in place of the distance.
+pub fn get_sdf_glyph_texture(
+    font_system: &mut cosmic_text::FontSystem,
+    swash_cache: &mut cosmic_text::SwashCache,
+    cache_key: cosmic_text::CacheKey,
+) -> Result<(Image, IVec2), TextError> {
+    let Some(commands) = swash_cache.get_outline_commands_uncached(font_system, cache_key) else {
+        // Bitmap fonts and color emoji may not have outlines.
+        let image = swash_cache
+            .get_image_uncached(font_system, cache_key)
+            .ok_or(TextError::FailedToGetGlyphImage(cache_key))?;
+        return Ok(coverage_glyph_texture(image));
+    };
+
+    let segments = flatten_outline(&commands);
+
+    let (width, height, offset) = if segments.is_empty() {
+        (0, 0, IVec2::ZERO)
+    } else {
+        let (min, max) = segments.iter().flat_map(|&(a, b)| [a, b]).fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), point| (min.min(point), max.max(point)),
+        );
+        let min = (min - SDF_RANGE).floor().as_ivec2();
+        let max = (max + SDF_RANGE).ceil().as_ivec2();
+        let size = max - min;
+        (size.x as u32, size.y as u32, IVec2::new(min.x, max.y))
+    };
+
+    let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+    for row in 0..height {
+        for column in 0..width {
+            // Outlines are y-up, while texture rows go down from the top of the glyph.
+            let point = Vec2::new(
+                offset.x as f32 + column as f32 + 0.5,
+                offset.y as f32 - row as f32 - 0.5,
+            );
+            // Distances are negative inside the glyph, so `0.5 - distance / (2 * SDF_RANGE)` maps
+            // the outline to `0.5`, and `SDF_RANGE` texels inside and outside of it to `1.0` and `0.0`.
+            let distance = signed_distance(&segments, point);
+            let value = (0.5 - distance / (2. * SDF_RANGE)).clamp(0., 1.);
+            data.extend([255, 255, 255, (value * 255.).round() as u8]);
+        }
+    }
+
+    Ok((
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        ),
+        offset,
+    ))
+}
+
+/// Stores the coverage of a rasterized glyph in the alpha channel, in place of its signed distance field.
+///
+/// This is used for glyphs without outlines: the coverage crosses `0.5` around their edges, so they are still
+/// rendered, but effects extending past the outline, such as outlines and glows, are clipped to the glyph.
+fn coverage_glyph_texture(image: cosmic_text::SwashImage) -> (Image, IVec2) {
+    let cosmic_text::Placement {
+        left,
+        top,
+        width,
+        height,
+    } = image.placement;
+    let coverage: Vec<u8> = match image.content {
+        cosmic_text::SwashContent::Mask => image.data,
+        cosmic_text::SwashContent::Color | cosmic_text::SwashContent::SubpixelMask => {
+            image.data.chunks_exact(4).map(|pixel| pixel[3]).collect()
+        }
+    };
+    let data = coverage
+        .into_iter()
+        .flat_map(|a| [255, 255, 255, a])
+        .collect();
+
+    (
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        ),
+        IVec2::new(left, top),
+    )
+}
+
+/// Approximate the curves of a glyph outline by line segments.
+fn flatten_outline(commands: &[cosmic_text::Command]) -> Vec<(Vec2, Vec2)> {
+    const QUAD_STEPS: usize = 8;
+    const CURVE_STEPS: usize = 12;
+
+    let mut segments = Vec::new();
+    let mut start = Vec2::ZERO;
+    let mut current = Vec2::ZERO;
+
+    for command in commands {
+        match *command {
+            cosmic_text::Command::MoveTo(p) => {
+                if current != start {
+                    segments.push((current, start));
+                }
+                start = Vec2::new(p.x, p.y);
+                current = start;
+            }
+            cosmic_text::Command::LineTo(p) => {
+                let p = Vec2::new(p.x, p.y);
+                segments.push((current, p));
+                current = p;
+            }
+            cosmic_text::Command::QuadTo(c, p) => {
+                let (c, p) = (Vec2::new(c.x, c.y), Vec2::new(p.x, p.y));
+                let p0 = current;
+                for step in 1..=QUAD_STEPS {
+                    let t = step as f32 / QUAD_STEPS as f32;
+                    let next = p0.lerp(c, t).lerp(c.lerp(p, t), t);
+                    segments.push((current, next));
+                    current = next;
+                }
+            }
+            cosmic_text::Command::CurveTo(c1, c2, p) => {
+                let (c1, c2, p) = (
+                    Vec2::new(c1.x, c1.y),
+                    Vec2::new(c2.x, c2.y),
+                    Vec2::new(p.x, p.y),
+                );
+                let p0 = current;
+                for step in 1..=CURVE_STEPS {
+                    let t = step as f32 / CURVE_STEPS as f32;
+                    let a = p0.lerp(c1, t);
+                    let b = c1.lerp(c2, t);
+                    let c = c2.lerp(p, t);
+                    let next = a.lerp(b, t).lerp(b.lerp(c, t), t);
+                    segments.push((current, next));
+                    current = next;
+                }
+            }
+            cosmic_text::Command::Close => {
+                if current != start {
+                    segments.push((current, start));
+                }
+                current = start;
+            }
+        }
+    }
+
+    if current != start {
+        segments.push((current, start));
+    }
+
+    segments
+}
+
+/// The distance from `point` to the closest segment, negative inside the outline and positive
+/// outside of it.
+///
+/// Insideness follows the nonzero winding rule used by TrueType and CFF fonts.
+fn signed_distance(segments: &[(Vec2, Vec2)], point: Vec2) -> f32 {
+    let mut distance_squared = f32::MAX;
+    let mut winding = 0;
+
+    for &(a, b) in segments {
+        let ab = b - a;
+        let t = ((point - a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0., 1.);
+        distance_squared = distance_squared.min(point.distance_squared(a + ab * t));
+
+        if (a.y <= point.y) != (b.y <= point.y) {
+            let cross = ab.perp_dot(point - a);
+            if b.y > a.y && cross > 0. {
+                winding += 1;
+            } else if b.y <= a.y && cross < 0. {
+                winding -= 1;
+            }
+        }
+    }
+
+    let distance = distance_squared.sqrt();
+    if winding != 0 {
+        -distance
+    } else {
+        distance
+    }
+}
+
+/// Shader parameters of a signed distance field glyph, in atlas texels.
+///
+/// Renderers compute the distance of a fragment to the glyph outline as
+/// `(alpha - 0.5) * range + dilation` and fade it out over `softness`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SdfGlyphParams {
+    /// The distance encoded by the full range of the distance field.
+    pub range: f32,
+    /// How far the glyph outline is grown.
+    pub dilation: f32,
+    /// The distance over which the grown outline fades out.
+    pub softness: f32,
+}
+
+impl SdfGlyphParams {
+    /// Parameters for a glyph with the given atlas `rect` and on-screen `size`,
+    /// with `dilation` and `softness` in the same units as `size`.
+    pub fn new(rect: Rect, size: Vec2, dilation: f32, softness: f32) -> Self {
+        let texels_per_unit = if size.x > 0. {
+            rect.width() / size.x
+        } else {
+            1.
+        };
+        Self {
+            range: 2. * SDF_RANGE,
+            dilation: dilation * texels_per_unit,
+            softness: softness * texels_per_unit,
+        }
+    }
+}
+
+/// Shader effects drawn around text that uses [`FontSmoothing::Sdf`](crate::FontSmoothing::Sdf).
+///
+/// Add this component to a text root entity, such as `Text` or `Text2d`, to draw the effects behind
+/// every signed distance field glyph in the text block. Glyphs using other smoothing modes are unaffected.
+///
+/// All distances are in logical pixels. Since the distance field only extends [`SDF_RANGE`] texels
+/// around each glyph, an effect's extent (outline width plus glow radius or shadow softness) is limited
+/// to about one sixth of the font size.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+pub struct TextSdfEffects {
+    /// A solid outline around each glyph.
+    pub outline: Option<SdfOutline>,
+    /// A soft glow around each glyph, starting from the edge of the outline.
+    pub glow: Option<SdfGlow>,
+    /// A drop shadow below each glyph, matching the silhouette of the outlined glyph.
+    pub shadow: Option<SdfShadow>,
+}
+
+/// A solid outline drawn by [`TextSdfEffects`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug, Clone, PartialEq)]
+pub struct SdfOutline {
+    /// The width of the outline in logical pixels.
+    pub width: f32,
+    /// The color of the outline.
+    pub color: Color,
+}
+
+impl Default for SdfOutline {
+    fn default() -> Self {
+        Self {
+            width: 2.,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// A soft glow drawn by [`TextSdfEffects`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug, Clone, PartialEq)]
+pub struct SdfGlow {
+    /// The distance over which the glow fades out, in logical pixels.
+    pub radius: f32,
+    /// The color of the glow at the edge of the glyph.
+    pub color: Color,
+}
+
+impl Default for SdfGlow {
+    fn default() -> Self {
+        Self {
+            radius: 4.,
+            color: Color::WHITE,
+        }
+    }
+}
+
+/// A drop shadow drawn by [`TextSdfEffects`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug, Clone, PartialEq)]
+pub struct SdfShadow {
+    /// Offset of the shadow in logical pixels, with positive y pointing down the text block.
+    pub offset: Vec2,
+    /// The distance over which the edge of the shadow fades out, in logical pixels.
+    pub softness: f32,
+    /// The color of the shadow.
+    pub color: Color,
+}
+
+impl Default for SdfShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::splat(2.),
+            softness: 2.,
+            color: Color::srgba(0., 0., 0., 0.8),
+        }
+    }
+}
+
+/// A single layer of signed distance field glyphs drawn by the renderer.
+///
+/// The shader computes the coverage of a fragment from its distance to the glyph outline in pixels,
+/// `distance`, as `smoothstep(-softness, 0, distance + dilation)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SdfLayer {
+    /// The color of the layer.
+    pub color: Color,
+    /// Offset of the layer in logical pixels, with positive y pointing down the text block.
+    pub offset: Vec2,
+    /// How far the glyph outline is grown, in logical pixels.
+    pub dilation: f32,
+    /// The distance over which the grown outline fades out, in logical pixels.
+    pub softness: f32,
+}
+
+impl TextSdfEffects {
+    /// Returns the effect layers to draw behind the glyphs, in back-to-front order.
+    pub fn layers(&self) -> impl Iterator<Item = SdfLayer> {
+        let outline_width = self.outline.map_or(0., |outline| outline.width.max(0.));
+        let shadow = self.shadow.map(|shadow| SdfLayer {
+            color: shadow.color,
+            offset: shadow.offset,
+            dilation: outline_width,
+            softness: shadow.softness.max(0.),
+        });
+        let glow = self.glow.map(|glow| SdfLayer {
+            color: glow.color,
+            offset: Vec2::ZERO,
+            dilation: outline_width,
+            softness: glow.radius.max(0.),
+        });
+        let outline = self.outline.map(|outline| SdfLayer {
+            color: outline.color,
+            offset: Vec2::ZERO,
+            dilation: outline_width,
+            softness: 0.,
+        });
+        [shadow, glow, outline].into_iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmic_text::Command;
+
+    fn square() -> Vec<(Vec2, Vec2)> {
+        let corners = [
+            Vec2::new(0., 0.),
+            Vec2::new(10., 0.),
+            Vec2::new(10., 10.),
+            Vec2::new(0., 10.),
+        ];
+        (0..4).map(|i| (corners[i], corners[(i + 1) % 4])).collect()
+    }
+
+    #[test]
+    fn coverage_glyph_texture_keeps_the_alpha() {
+        let mut image = cosmic_text::SwashImage::new();
+        image.content = cosmic_text::SwashContent::Color;
+        image.placement = cosmic_text::Placement {
+            left: 2,
+            top: 5,
+            width: 2,
+            height: 1,
+        };
+        image.data = vec![10, 20, 30, 0, 40, 50, 60, 200];
+
+        let (texture, offset) = coverage_glyph_texture(image);
+        assert_eq!(offset, IVec2::new(2, 5));
+        assert_eq!(texture.width(), 2);
+        assert_eq!(texture.height(), 1);
+        assert_eq!(
+            texture.data.as_deref(),
+            Some(&[255, 255, 255, 0, 255, 255, 255, 200][..])
+        );
+    }
+
+    #[test]
+    fn signed_distance_is_negative_inside() {
+        let square = square();
+        assert_eq!(signed_distance(&square, Vec2::new(5., 5.)), -5.);
+        assert_eq!(signed_distance(&square, Vec2::new(2., 5.)), -2.);
+        assert_eq!(signed_distance(&square, Vec2::new(13., 5.)), 3.);
+        assert_eq!(signed_distance(&square, Vec2::new(-1., 10.)), 1.);
+        assert_eq!(signed_distance(&square, Vec2::new(10., 5.)), 0.);
+        assert_eq!(signed_distance(&square, Vec2::new(0., 0.)), 0.);
+
+        // The winding direction of the outline doesn't matter.
+        let reversed: Vec<_> = square.iter().rev().map(|&(a, b)| (b, a)).collect();
+        assert_eq!(signed_distance(&reversed, Vec2::new(5., 5.)), -5.);
+        assert_eq!(signed_distance(&reversed, Vec2::new(13., 5.)), 3.);
+    }
+
+    #[test]
+    fn flatten_outline_closes_contours() {
+        let segments = flatten_outline(&[
+            Command::MoveTo([0., 0.].into()),
+            Command::LineTo([10., 0.].into()),
+            Command::QuadTo([10., 10.].into(), [0., 10.].into()),
+            Command::Close,
+            // An unclosed contour is closed by the next `MoveTo`, or at the end.
+            Command::MoveTo([20., 0.].into()),
+            Command::LineTo([30., 0.].into()),
+            Command::CurveTo([30., 5.].into(), [25., 10.].into(), [20., 10.].into()),
+        ]);
+
+        assert_eq!(segments.len(), 1 + 8 + 1 + 1 + 12 + 1);
+        assert_eq!(segments[0], (Vec2::new(0., 0.), Vec2::new(10., 0.)));
+        assert_eq!(segments[8].1, Vec2::new(0., 10.));
+        assert_eq!(segments[9], (Vec2::new(0., 10.), Vec2::new(0., 0.)));
+        assert_eq!(segments[22].1, Vec2::new(20., 10.));
+        assert_eq!(segments[23], (Vec2::new(20., 10.), Vec2::new(20., 0.)));
+
+        // Each segment starts where the previous one ended, within a contour.
+        for contour in [&segments[..10], &segments[10..]] {
+            for pair in contour.windows(2) {
+                assert_eq!(pair[0].1, pair[1].0);
+            }
+            assert_eq!(contour.last().unwrap().1, contour[0].0);
+        }
+
+        // Curve points lie on the curve.
+        let midpoint = segments[4].1;
+        assert!((midpoint - Vec2::new(7.5, 7.5)).length() < 1e-5);
+    }
+
+    #[test]
+    fn effect_layers_are_back_to_front() {
+        let effects = TextSdfEffects {
+            outline: Some(SdfOutline {
+                width: 3.,
+                color: Color::BLACK,
+            }),
+            glow: Some(SdfGlow::default()),
+            shadow: Some(SdfShadow::default()),
+        };
+        let layers: Vec<_> = effects.layers().collect();
+        assert_eq!(
+            layers,
+            [
+                SdfLayer {
+                    color: SdfShadow::default().color,
+                    offset: SdfShadow::default().offset,
+                    dilation: 3.,
+                    softness: SdfShadow::default().softness,
+                },
+                SdfLayer {
+                    color: SdfGlow::default().color,
+                    offset: Vec2::ZERO,
+                    dilation: 3.,
+                    softness: SdfGlow::default().radius,
+                },
+                SdfLayer {
+                    color: Color::BLACK,
+                    offset: Vec2::ZERO,
+                    dilation: 3.,
+                    softness: 0.,
+                },
+            ]
+        );
+
+        let glow_only = TextSdfEffects {
+            glow: Some(SdfGlow::default()),
+            ..Default::default()
+        };
+        let layers: Vec<_> = glow_only.layers().collect();
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].dilation, 0.);
+        assert_eq!(TextSdfEffects::default().layers().count(), 0);
+    }
+}
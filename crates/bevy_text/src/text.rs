@@ -933,6 +933,14 @@ pub enum FontSmoothing {
     /// even at small font sizes and low resolutions with modern vector fonts.
     #[default]
     AntiAliased,
+    /// Glyphs are rendered from signed distance fields, which stay sharp at any scale.
+    ///
+    /// A single atlas at [`SDF_FONT_SIZE`](crate::SDF_FONT_SIZE) is shared by every font size,
+    /// making this well suited to zoomed world-space or animated text. Enables the shader effects
+    /// of [`TextSdfEffects`](crate::TextSdfEffects).
+    ///
+    /// **Note:** Small text is rendered without hinting and may look softer than [`FontSmoothing::AntiAliased`].
+    Sdf,
     // TODO: Add subpixel antialias support
     // SubpixelAntiAliased,
 }
@@ -213,7 +213,7 @@ impl Time<Fixed> {
         self.context().overstep.as_secs_f64() / self.context().timestep.as_secs_f64()
     }
 
-    fn expend(&mut self) -> bool {
+    pub(crate) fn expend(&mut self) -> bool {
         let timestep = self.timestep();
         if let Some(new_value) = self.context_mut().overstep.checked_sub(timestep) {
             // reduce accumulated and increase elapsed by period
@@ -0,0 +1,283 @@
+use alloc::vec::Vec;
+use bevy_app::App;
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    world::World,
+};
+use core::{mem, time::Duration};
+
+use crate::{fixed::Fixed, time::Time, virt::Virtual};
+
+/// A schedule run zero or more times per update at its own fixed rate, independently of
+/// [`FixedMain`](bevy_app::FixedMain).
+///
+/// Each fixed schedule has its own [`Time<Fixed>`] clock, tracking its timestep and overstep.
+/// While the schedule runs, its clock is swapped into the [`Time<Fixed>`] resource and the
+/// generic [`Time`] resource, so systems in it can use them as they would in [`FixedMain`](bevy_app::FixedMain).
+/// Changes made to [`Time<Fixed>`] from within the schedule, such as
+/// [`Time::<Fixed>::set_timestep`], apply to its own clock.
+///
+/// Register fixed schedules with [`AppExtFixedSchedules::add_fixed_schedule`].
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::schedule::ScheduleLabel;
+/// # use bevy_time::prelude::*;
+/// # use bevy_time::{AppExtFixedSchedules, FixedCatchUp, FixedSchedule, TimePlugin};
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// struct AiTick;
+///
+/// let mut app = App::new();
+/// app.add_plugins(TimePlugin).add_fixed_schedule(
+///     FixedSchedule::new(AiTick, Time::<Fixed>::from_hz(10.0))
+///         .with_catch_up(FixedCatchUp::Discard(2)),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedSchedule {
+    label: InternedScheduleLabel,
+    /// The clock of this schedule.
+    pub clock: Time<Fixed>,
+    /// How many timesteps this schedule may run per update.
+    pub catch_up: FixedCatchUp,
+}
+
+impl FixedSchedule {
+    /// Creates a fixed schedule running `label` at the timestep of `clock`.
+    pub fn new(label: impl ScheduleLabel, clock: Time<Fixed>) -> Self {
+        Self {
+            label: label.intern(),
+            clock,
+            catch_up: FixedCatchUp::default(),
+        }
+    }
+
+    /// Sets the catch-up policy of this schedule.
+    pub fn with_catch_up(mut self, catch_up: FixedCatchUp) -> Self {
+        self.catch_up = catch_up;
+        self
+    }
+
+    /// Returns the label of the schedule being run.
+    pub fn label(&self) -> InternedScheduleLabel {
+        self.label
+    }
+}
+
+/// Limits how many timesteps a [`FixedSchedule`] may run per update, when it falls behind.
+///
+/// When an update takes longer than the timestep, for example after a frame hitch,
+/// the schedule has to run several times to catch up. Limiting this avoids spending even
+/// more time on the next update, at the cost of the schedule running slower than real time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FixedCatchUp {
+    /// Run as many timesteps as have accumulated, like [`FixedMain`](bevy_app::FixedMain).
+    #[default]
+    Unlimited,
+    /// Run at most this many timesteps per update.
+    /// The remaining overstep is kept, and caught up over the following updates.
+    Carry(u32),
+    /// Run at most this many timesteps per update.
+    /// The remaining whole timesteps are discarded, only keeping the fraction of a timestep.
+    Discard(u32),
+}
+
+impl FixedCatchUp {
+    fn allows(self, steps: u32) -> bool {
+        match self {
+            FixedCatchUp::Unlimited => true,
+            FixedCatchUp::Carry(max) | FixedCatchUp::Discard(max) => steps < max,
+        }
+    }
+}
+
+/// The [`FixedSchedule`]s run by [`run_fixed_schedules`], in registration order.
+///
+/// This resource is added by [`AppExtFixedSchedules::add_fixed_schedule`].
+/// It is removed from the world while the fixed schedules run: use [`Time<Fixed>`] to
+/// access the clock of the current schedule from its systems.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct FixedSchedules {
+    schedules: Vec<FixedSchedule>,
+}
+
+impl FixedSchedules {
+    /// Adds a fixed schedule, replacing any fixed schedule with the same label.
+    pub fn insert(&mut self, schedule: FixedSchedule) {
+        match self.get_mut(schedule.label) {
+            Some(existing) => *existing = schedule,
+            None => self.schedules.push(schedule),
+        }
+    }
+
+    /// Removes the fixed schedule with the given label, returning it if it was present.
+    pub fn remove(&mut self, label: impl ScheduleLabel) -> Option<FixedSchedule> {
+        let label = label.intern();
+        let index = self.schedules.iter().position(|s| s.label == label)?;
+        Some(self.schedules.remove(index))
+    }
+
+    /// Returns the fixed schedule with the given label, if any.
+    pub fn get(&self, label: impl ScheduleLabel) -> Option<&FixedSchedule> {
+        let label = label.intern();
+        self.schedules.iter().find(|s| s.label == label)
+    }
+
+    /// Returns the fixed schedule with the given label mutably, if any.
+    pub fn get_mut(&mut self, label: impl ScheduleLabel) -> Option<&mut FixedSchedule> {
+        let label = label.intern();
+        self.schedules.iter_mut().find(|s| s.label == label)
+    }
+
+    /// Iterates over the fixed schedules, in the order they run.
+    pub fn iter(&self) -> impl Iterator<Item = &FixedSchedule> {
+        self.schedules.iter()
+    }
+}
+
+/// Extension trait for [`App`] to register [`FixedSchedule`]s.
+pub trait AppExtFixedSchedules {
+    /// Runs a schedule at its own fixed rate, after [`FixedMain`](bevy_app::FixedMain).
+    ///
+    /// If a fixed schedule with the same label was already added, it is replaced.
+    /// This requires [`TimePlugin`](crate::TimePlugin).
+    fn add_fixed_schedule(&mut self, schedule: FixedSchedule) -> &mut Self;
+}
+
+impl AppExtFixedSchedules for App {
+    fn add_fixed_schedule(&mut self, schedule: FixedSchedule) -> &mut Self {
+        self.init_schedule(schedule.label);
+        self.world_mut()
+            .get_resource_or_init::<FixedSchedules>()
+            .insert(schedule);
+        self
+    }
+}
+
+/// Runs each of the [`FixedSchedules`] zero or more times based on delta of
+/// [`Time<Virtual>`](Virtual), the overstep of its clock and its [`FixedCatchUp`] policy.
+///
+/// This runs right after [`run_fixed_main_schedule`](crate::run_fixed_main_schedule).
+pub fn run_fixed_schedules(world: &mut World) {
+    let Some(mut schedules) = world.remove_resource::<FixedSchedules>() else {
+        return;
+    };
+    let delta = world.resource::<Time<Virtual>>().delta();
+
+    for fixed in &mut schedules.schedules {
+        fixed.clock.accumulate_overstep(delta);
+        mem::swap(&mut *world.resource_mut::<Time<Fixed>>(), &mut fixed.clock);
+
+        let catch_up = fixed.catch_up;
+        let _ = world.try_schedule_scope(fixed.label, |world, schedule| {
+            let mut steps = 0;
+            while catch_up.allows(steps) && world.resource_mut::<Time<Fixed>>().expend() {
+                steps += 1;
+                *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+                schedule.run(world);
+            }
+        });
+
+        mem::swap(&mut *world.resource_mut::<Time<Fixed>>(), &mut fixed.clock);
+        if let FixedCatchUp::Discard(_) = fixed.catch_up {
+            let timestep = fixed.clock.timestep();
+            let overstep = fixed.clock.overstep();
+            let excess = overstep.as_nanos() - overstep.as_nanos() % timestep.as_nanos();
+            fixed
+                .clock
+                .discard_overstep(Duration::from_nanos(excess as u64));
+        }
+    }
+
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+    world.insert_resource(schedules);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TimePlugin, TimeUpdateStrategy};
+    use bevy_app::FixedUpdate;
+    use bevy_ecs::{
+        resource::Resource,
+        system::{Res, ResMut},
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Slow;
+
+    #[derive(Resource, Default)]
+    struct Counts {
+        main: u32,
+        slow: u32,
+        slow_timestep: Duration,
+    }
+
+    fn setup(catch_up: FixedCatchUp) -> App {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .insert_resource(Time::<Fixed>::from_hz(100.0))
+            .add_fixed_schedule(
+                FixedSchedule::new(Slow, Time::<Fixed>::from_hz(10.0)).with_catch_up(catch_up),
+            )
+            .init_resource::<Counts>()
+            .add_systems(FixedUpdate, |mut counts: ResMut<Counts>| counts.main += 1)
+            .add_systems(
+                Slow,
+                |mut counts: ResMut<Counts>, fixed: Res<Time<Fixed>>, time: Res<Time>| {
+                    assert_eq!(fixed.delta(), time.delta());
+                    counts.slow += 1;
+                    counts.slow_timestep = fixed.timestep();
+                },
+            );
+        // Virtual time ignores the first update.
+        app.update();
+        app
+    }
+
+    fn update(app: &mut App, duration: Duration) {
+        *app.world_mut().resource_mut::<TimeUpdateStrategy>() =
+            TimeUpdateStrategy::ManualDuration(duration);
+        app.update();
+    }
+
+    #[test]
+    fn fixed_schedules_run_at_their_own_rate() {
+        let mut app = setup(FixedCatchUp::Unlimited);
+        for _ in 0..5 {
+            update(&mut app, Duration::from_millis(50));
+        }
+
+        let counts = app.world().resource::<Counts>();
+        assert_eq!(counts.main, 25);
+        assert_eq!(counts.slow, 2);
+        assert_eq!(counts.slow_timestep, Duration::from_millis(100));
+
+        // The main clock is restored after the fixed schedules run.
+        let fixed = app.world().resource::<Time<Fixed>>();
+        assert_eq!(fixed.timestep(), Duration::from_millis(10));
+        let schedules = app.world().resource::<FixedSchedules>();
+        let slow = schedules.get(Slow).unwrap();
+        assert_eq!(slow.clock.overstep(), Duration::from_millis(50));
+        assert_eq!(slow.clock.elapsed(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn catch_up_policies_limit_steps_per_update() {
+        let mut app = setup(FixedCatchUp::Carry(1));
+        update(&mut app, Duration::from_millis(250));
+        assert_eq!(app.world().resource::<Counts>().slow, 1);
+        update(&mut app, Duration::ZERO);
+        assert_eq!(app.world().resource::<Counts>().slow, 2);
+
+        let mut app = setup(FixedCatchUp::Discard(1));
+        update(&mut app, Duration::from_millis(250));
+        assert_eq!(app.world().resource::<Counts>().slow, 1);
+        let schedules = app.world().resource::<FixedSchedules>();
+        let slow = schedules.get(Slow).unwrap();
+        assert_eq!(slow.clock.overstep(), Duration::from_millis(50));
+        update(&mut app, Duration::ZERO);
+        assert_eq!(app.world().resource::<Counts>().slow, 1);
+    }
+}
@@ -15,16 +15,22 @@ extern crate alloc;
 /// Common run conditions
 pub mod common_conditions;
 mod fixed;
+mod fixed_schedules;
 mod real;
+mod scheduled_commands;
 mod stopwatch;
 mod time;
+mod time_domain;
 mod timer;
 mod virt;
 
 pub use fixed::*;
+pub use fixed_schedules::*;
 pub use real::*;
+pub use scheduled_commands::*;
 pub use stopwatch::*;
 pub use time::*;
+pub use time_domain::*;
 pub use timer::*;
 pub use virt::*;
 
@@ -33,7 +39,7 @@ pub use virt::*;
 /// This includes the most common types in this crate, re-exported for your convenience.
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Fixed, Real, Time, Timer, TimerMode, Virtual};
+    pub use crate::{Fixed, LocalTime, Real, Time, TimeDomain, Timer, TimerMode, Virtual};
 }
 
 use bevy_app::{prelude::*, RunFixedMainLoop};
@@ -74,7 +80,8 @@ impl Plugin for TimePlugin {
             app.register_type::<Time>()
                 .register_type::<Time<Real>>()
                 .register_type::<Time<Virtual>>()
-                .register_type::<Time<Fixed>>();
+                .register_type::<Time<Fixed>>()
+                .register_type::<TimeDomain>();
         }
 
         app.add_systems(
@@ -83,9 +90,13 @@ impl Plugin for TimePlugin {
                 .in_set(TimeSystems)
                 .ambiguous_with(message_update_system),
         )
+        .add_systems(First, run_scheduled_commands.after(TimeSystems))
+        .add_systems(FixedFirst, run_fixed_scheduled_commands)
         .add_systems(
             RunFixedMainLoop,
-            run_fixed_main_schedule.in_set(RunFixedMainLoopSystems::FixedMainLoop),
+            (run_fixed_main_schedule, run_fixed_schedules)
+                .chain()
+                .in_set(RunFixedMainLoopSystems::FixedMainLoop),
         );
 
         // Ensure the messages are not dropped until `FixedMain` systems can observe them
@@ -0,0 +1,309 @@
+use alloc::{boxed::Box, vec::Vec};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    error::HandleError,
+    query::QueryState,
+    system::{Command, Commands, EntityCommands, Local},
+    world::World,
+};
+use bevy_platform::cell::SyncCell;
+use core::{fmt, time::Duration};
+
+use crate::{
+    fixed::Fixed,
+    real::Real,
+    time::Time,
+    timer::{Timer, TimerMode},
+    virt::Virtual,
+};
+
+/// A [`Command`] run after a delay, or periodically.
+///
+/// Each scheduled command lives on its own entity, which serves as its handle:
+/// despawning the entity cancels the command. Repeating commands keep running until their entity
+/// is despawned, so they can be tied to a state with `DespawnOnExit`. One-shot commands despawn
+/// their entity after running.
+///
+/// The clock driving the command is chosen with the [`CommandClock`] component.
+/// Scheduled commands are usually spawned with [`ScheduledCommandsExt`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::{CommandClock, ScheduledCommand, ScheduledCommandsExt};
+/// # use core::time::Duration;
+/// fn schedule(mut commands: Commands) {
+///     commands.run_after(Duration::from_secs_f32(2.5), |world: &mut World| {
+///         world.spawn_empty();
+///     });
+///
+///     let autosave = commands
+///         .run_every(Duration::from_secs(10), |_world: &mut World| {})
+///         .insert(CommandClock::Real)
+///         .id();
+///
+///     // Later, cancel it.
+///     commands.entity(autosave).despawn();
+/// }
+/// ```
+#[derive(Component)]
+#[require(CommandClock)]
+pub struct ScheduledCommand {
+    timer: Timer,
+    command: Option<SyncCell<Box<dyn FnMut(&mut World) + Send>>>,
+}
+
+impl ScheduledCommand {
+    /// Creates a scheduled command running `command` once, after `delay`.
+    pub fn once<C: Command<T> + HandleError<T>, T>(delay: Duration, command: C) -> Self {
+        let mut command = Some(command);
+        Self {
+            timer: Timer::new(delay, TimerMode::Once),
+            command: Some(SyncCell::new(Box::new(move |world: &mut World| {
+                if let Some(command) = command.take() {
+                    command.handle_error().apply(world);
+                }
+            }))),
+        }
+    }
+
+    /// Creates a scheduled command running a clone of `command` every `period`.
+    pub fn repeating<C: Command<T> + HandleError<T> + Clone, T>(
+        period: Duration,
+        command: C,
+    ) -> Self {
+        Self {
+            timer: Timer::new(period, TimerMode::Repeating),
+            command: Some(SyncCell::new(Box::new(move |world: &mut World| {
+                command.clone().handle_error().apply(world);
+            }))),
+        }
+    }
+
+    /// Returns the timer tracking when the command runs next.
+    pub fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    /// Returns the timer tracking when the command runs next, mutably.
+    ///
+    /// This can be used to pause the command, or change its delay or period.
+    pub fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+}
+
+impl fmt::Debug for ScheduledCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScheduledCommand")
+            .field("timer", &self.timer)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The clock driving a [`ScheduledCommand`].
+#[derive(Component, Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CommandClock {
+    /// [`Time<Real>`], which is not affected by pausing or scaling virtual time.
+    Real,
+    /// [`Time<Virtual>`], the default game clock.
+    #[default]
+    Virtual,
+    /// [`Time<Fixed>`]. The command runs in [`FixedFirst`](bevy_app::FixedFirst) instead of
+    /// [`First`](bevy_app::First).
+    Fixed,
+}
+
+/// Extension trait for [`Commands`] to schedule delayed and repeated commands.
+pub trait ScheduledCommandsExt {
+    /// Runs `command` once, after `delay`.
+    ///
+    /// Returns the commands of the entity holding the [`ScheduledCommand`]:
+    /// despawn it to cancel the command, or insert a [`CommandClock`] to pick the clock driving it.
+    fn run_after<C: Command<T> + HandleError<T>, T>(
+        &mut self,
+        delay: Duration,
+        command: C,
+    ) -> EntityCommands<'_>;
+
+    /// Runs a clone of `command` every `period`, until the returned entity is despawned.
+    ///
+    /// Returns the commands of the entity holding the [`ScheduledCommand`]:
+    /// despawn it to cancel the command, or insert a [`CommandClock`] to pick the clock driving it.
+    fn run_every<C: Command<T> + HandleError<T> + Clone, T>(
+        &mut self,
+        period: Duration,
+        command: C,
+    ) -> EntityCommands<'_>;
+}
+
+impl ScheduledCommandsExt for Commands<'_, '_> {
+    fn run_after<C: Command<T> + HandleError<T>, T>(
+        &mut self,
+        delay: Duration,
+        command: C,
+    ) -> EntityCommands<'_> {
+        self.spawn(ScheduledCommand::once(delay, command))
+    }
+
+    fn run_every<C: Command<T> + HandleError<T> + Clone, T>(
+        &mut self,
+        period: Duration,
+        command: C,
+    ) -> EntityCommands<'_> {
+        self.spawn(ScheduledCommand::repeating(period, command))
+    }
+}
+
+/// Runs the [`ScheduledCommand`]s driven by [`CommandClock::Real`] and [`CommandClock::Virtual`]
+/// which are due.
+pub fn run_scheduled_commands(
+    world: &mut World,
+    query: &mut QueryState<(Entity, &mut ScheduledCommand, &CommandClock)>,
+    mut due: Local<Vec<(Entity, u32)>>,
+) {
+    let real = world.resource::<Time<Real>>().delta();
+    let virt = world.resource::<Time<Virtual>>().delta();
+    run_due_commands(world, query, &mut due, |clock| match clock {
+        CommandClock::Real => Some(real),
+        CommandClock::Virtual => Some(virt),
+        CommandClock::Fixed => None,
+    });
+}
+
+/// Runs the [`ScheduledCommand`]s driven by [`CommandClock::Fixed`] which are due.
+pub fn run_fixed_scheduled_commands(
+    world: &mut World,
+    query: &mut QueryState<(Entity, &mut ScheduledCommand, &CommandClock)>,
+    mut due: Local<Vec<(Entity, u32)>>,
+) {
+    let fixed = world.resource::<Time<Fixed>>().delta();
+    run_due_commands(world, query, &mut due, |clock| {
+        (*clock == CommandClock::Fixed).then_some(fixed)
+    });
+}
+
+fn run_due_commands(
+    world: &mut World,
+    query: &mut QueryState<(Entity, &mut ScheduledCommand, &CommandClock)>,
+    due: &mut Vec<(Entity, u32)>,
+    delta: impl Fn(&CommandClock) -> Option<Duration>,
+) {
+    for (entity, mut scheduled, clock) in query.iter_mut(world) {
+        let Some(delta) = delta(clock) else {
+            continue;
+        };
+        let times = scheduled.timer.tick(delta).times_finished_this_tick();
+        if times > 0 {
+            due.push((entity, times));
+        }
+    }
+
+    for (entity, times) in due.drain(..) {
+        // The command may have been cancelled by another one.
+        let Some(mut command) = world
+            .get_mut::<ScheduledCommand>(entity)
+            .and_then(|mut scheduled| scheduled.command.take())
+        else {
+            continue;
+        };
+        for _ in 0..times {
+            (command.get())(world);
+        }
+
+        let Some(mut scheduled) = world.get_mut::<ScheduledCommand>(entity) else {
+            continue;
+        };
+        if scheduled.timer.mode() == TimerMode::Repeating {
+            scheduled.command = Some(command);
+        } else {
+            world.despawn(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TimePlugin, TimeUpdateStrategy};
+    use bevy_app::App;
+    use bevy_ecs::resource::Resource;
+
+    #[derive(Resource, Default)]
+    struct Count(u32);
+
+    fn increment(world: &mut World) {
+        world.resource_mut::<Count>().0 += 1;
+    }
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .init_resource::<Count>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        // Time does not advance on the first update.
+        app.update();
+        app
+    }
+
+    fn count(app: &App) -> u32 {
+        app.world().resource::<Count>().0
+    }
+
+    #[test]
+    fn run_after_runs_once() {
+        let mut app = setup();
+        let entity = app
+            .world_mut()
+            .commands()
+            .run_after(Duration::from_millis(250), increment)
+            .id();
+        app.world_mut().flush();
+
+        app.update();
+        app.update();
+        assert_eq!(count(&app), 0);
+        app.update();
+        assert_eq!(count(&app), 1);
+        assert!(app.world().get_entity(entity).is_err());
+        app.update();
+        assert_eq!(count(&app), 1);
+    }
+
+    #[test]
+    fn run_every_repeats_until_cancelled() {
+        let mut app = setup();
+        let entity = app
+            .world_mut()
+            .commands()
+            .run_every(Duration::from_millis(100), increment)
+            .id();
+        app.world_mut().flush();
+
+        app.update();
+        app.update();
+        assert_eq!(count(&app), 2);
+        app.world_mut().despawn(entity);
+        app.update();
+        assert_eq!(count(&app), 2);
+    }
+
+    #[test]
+    fn command_clock_selects_time() {
+        let mut app = setup();
+        app.world_mut().resource_mut::<Time<Virtual>>().pause();
+        app.world_mut()
+            .commands()
+            .run_after(Duration::from_millis(100), increment);
+        app.world_mut()
+            .commands()
+            .run_after(Duration::from_millis(100), increment)
+            .insert(CommandClock::Real);
+        app.world_mut().flush();
+
+        app.update();
+        assert_eq!(count(&app), 1);
+    }
+}
@@ -0,0 +1,239 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    hierarchy::ChildOf,
+    system::{Query, Res, SystemParam},
+};
+use core::time::Duration;
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::reflect::ReflectComponent,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+use crate::{time::Time, timer::Timer};
+
+/// A local time domain, scaling the passage of time for an entity and its descendants.
+///
+/// While [`Time<Virtual>`](crate::Virtual) pauses and scales time for the whole app, time domains
+/// only affect part of the hierarchy, which is useful for bullet-time effects where the world slows
+/// down while the player or UI animations keep running at normal speed.
+///
+/// Nested time domains are combined: an entity in a domain running at half speed, inside another
+/// domain running at half speed, runs at a quarter of the speed of its [`Time`].
+/// The resulting delta is read with [`LocalTime`], and is honored by `AnimationPlayer` and
+/// audio playback speed.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::TimeDomain;
+/// fn bullet_time(mut commands: Commands, world_root: Single<Entity, With<WorldRoot>>) {
+///     commands.entity(*world_root).insert(TimeDomain::new(0.25));
+/// }
+/// # #[derive(Component)]
+/// # struct WorldRoot;
+/// ```
+#[derive(Component, Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, Debug, PartialEq, Clone)
+)]
+pub struct TimeDomain {
+    relative_speed: f32,
+    paused: bool,
+}
+
+impl TimeDomain {
+    /// Creates a time domain advancing at the given speed relative to its parent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `relative_speed` is negative or not finite.
+    pub fn new(relative_speed: f32) -> Self {
+        let mut domain = Self::default();
+        domain.set_relative_speed(relative_speed);
+        domain
+    }
+
+    /// Returns the speed this domain advances relative to its parent.
+    ///
+    /// This does not take into account whether the domain is paused.
+    #[inline]
+    pub fn relative_speed(&self) -> f32 {
+        self.relative_speed
+    }
+
+    /// Returns the speed this domain advances relative to its parent,
+    /// or `0.0` if it is paused.
+    #[inline]
+    pub fn effective_speed(&self) -> f32 {
+        if self.paused {
+            0.0
+        } else {
+            self.relative_speed
+        }
+    }
+
+    /// Sets the speed this domain advances relative to its parent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is negative or not finite.
+    #[inline]
+    pub fn set_relative_speed(&mut self, ratio: f32) {
+        assert!(ratio.is_finite(), "tried to go infinitely fast");
+        assert!(ratio >= 0.0, "tried to go back in time");
+        self.relative_speed = ratio;
+    }
+
+    /// Stops time for this domain if it is running, otherwise resumes it.
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.paused ^= true;
+    }
+
+    /// Stops time for this domain, until resumed.
+    #[inline]
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes time for this domain.
+    #[inline]
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns `true` if time is currently paused for this domain.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+impl Default for TimeDomain {
+    fn default() -> Self {
+        Self {
+            relative_speed: 1.0,
+            paused: false,
+        }
+    }
+}
+
+/// A [`SystemParam`] reading the [`Time`] of entities, as scaled by their [`TimeDomain`]s.
+///
+/// `T` is the context of the underlying clock, like for [`Time<T>`]: by default, this uses the
+/// generic [`Time`], which follows [`Time<Virtual>`](crate::Virtual) or [`Time<Fixed>`](crate::Fixed)
+/// depending on the schedule.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::LocalTime;
+/// #[derive(Component)]
+/// struct Spin(f32);
+///
+/// fn spin(time: LocalTime, mut query: Query<(Entity, &mut Spin)>) {
+///     for (entity, mut spin) in &mut query {
+///         spin.0 += time.delta_secs(entity);
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct LocalTime<'w, 's, T: Default + Send + Sync + 'static = ()> {
+    time: Res<'w, Time<T>>,
+    domains: Query<'w, 's, (Option<&'static TimeDomain>, Option<&'static ChildOf>)>,
+}
+
+impl<'w, 's, T: Default + Send + Sync + 'static> LocalTime<'w, 's, T> {
+    /// Returns the underlying clock, unaffected by time domains.
+    #[inline]
+    pub fn time(&self) -> &Time<T> {
+        &self.time
+    }
+
+    /// Returns the speed time advances for `entity` relative to [`Time<T>`], combining the
+    /// [`TimeDomain`]s of the entity and its ancestors.
+    ///
+    /// Returns `1.0` if the entity is not in any time domain, and `0.0` if any of its domains is paused.
+    pub fn relative_speed(&self, entity: Entity) -> f32 {
+        let mut speed = 1.0;
+        let mut current = Some(entity);
+        while let Some(entity) = current
+            && let Ok((domain, child_of)) = self.domains.get(entity)
+        {
+            if let Some(domain) = domain {
+                speed *= domain.effective_speed();
+            }
+            current = child_of.map(ChildOf::parent);
+        }
+        speed
+    }
+
+    /// Returns how much time has advanced for `entity` since the last update.
+    #[inline]
+    pub fn delta(&self, entity: Entity) -> Duration {
+        self.time.delta().mul_f32(self.relative_speed(entity))
+    }
+
+    /// Returns how much time has advanced for `entity` since the last update, as [`f32`] seconds.
+    #[inline]
+    pub fn delta_secs(&self, entity: Entity) -> f32 {
+        self.time.delta_secs() * self.relative_speed(entity)
+    }
+
+    /// Returns how much time has advanced for `entity` since the last update, as [`f64`] seconds.
+    #[inline]
+    pub fn delta_secs_f64(&self, entity: Entity) -> f64 {
+        self.time.delta_secs_f64() * self.relative_speed(entity) as f64
+    }
+
+    /// Advances `timer` by the time that passed for `entity` since the last update.
+    ///
+    /// See [`Timer::tick`].
+    #[inline]
+    pub fn tick<'t>(&self, entity: Entity, timer: &'t mut Timer) -> &'t Timer {
+        timer.tick(self.delta(entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TimerMode;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    #[test]
+    fn nested_time_domains_combine() {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs(1));
+        world.insert_resource(time);
+
+        let root = world.spawn(TimeDomain::new(0.5)).id();
+        let child = world.spawn((ChildOf(root), TimeDomain::new(0.5))).id();
+        let grandchild = world.spawn(ChildOf(child)).id();
+        let outside = world.spawn_empty().id();
+
+        let (speeds, delta, timer) = world
+            .run_system_once(move |time: LocalTime| {
+                let mut timer = Timer::from_seconds(0.25, TimerMode::Once);
+                time.tick(child, &mut timer);
+                (
+                    [root, child, grandchild, outside].map(|entity| time.relative_speed(entity)),
+                    time.delta(grandchild),
+                    timer,
+                )
+            })
+            .unwrap();
+        assert_eq!(speeds, [0.5, 0.25, 0.25, 1.0]);
+        assert_eq!(delta, Duration::from_millis(250));
+        assert!(timer.is_finished());
+
+        world.get_mut::<TimeDomain>(root).unwrap().pause();
+        let speed = world
+            .run_system_once(move |time: LocalTime| time.relative_speed(grandchild))
+            .unwrap();
+        assert_eq!(speed, 0.0);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::{components::Transform, plugins::TransformSystems};
+use bevy_app::{App, FixedFirst, FixedLast, Plugin, PostUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::Quat;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::prelude::*;
+use bevy_time::{Fixed, Time};
+
+/// Smooths the rendered [`GlobalTransform`](crate::components::GlobalTransform) of an entity moved
+/// in the fixed timestep schedules, by interpolating between its [`Transform`] before and after the last fixed update.
+///
+/// The [`Transform`] of the entity is blended using [`Time<Fixed>::overstep_fraction`] only while transforms
+/// are propagated, and restored right after, so gameplay code sees its actual value.
+/// This results in the entity and its descendants being rendered up to one fixed timestep in the past.
+///
+/// This requires [`TransformInterpolationPlugin`]. Use [`TransformExtrapolation`] to predict the movement
+/// of the entity instead, at the cost of mispredictions when its velocity changes.
+///
+/// The [`Transform`] of the entity should only be modified in the fixed timestep schedules.
+/// It is reported as changed on the frames where its blended value changes.
+/// To move the entity instantly, without blending, call [`TransformEasing::teleport`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[require(TransformEasing)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, PartialEq, Debug, Clone)
+)]
+pub struct TransformInterpolation;
+
+/// Smooths the rendered [`GlobalTransform`](crate::components::GlobalTransform) of an entity moved
+/// in the fixed timestep schedules, by extrapolating its [`Transform`] after the last fixed update, using its change during that update.
+///
+/// The [`Transform`] of the entity is extrapolated using [`Time<Fixed>::overstep_fraction`] only while transforms
+/// are propagated, and restored right after, so gameplay code sees its actual value.
+/// This results in the entity and its descendants being rendered without latency, but at a predicted position
+/// that can be wrong when its velocity changes.
+///
+/// This requires [`TransformInterpolationPlugin`]. If the entity also has [`TransformInterpolation`],
+/// it is interpolated instead.
+///
+/// The [`Transform`] of the entity should only be modified in the fixed timestep schedules.
+/// It is reported as changed on the frames where its blended value changes.
+/// To move the entity instantly, without blending, call [`TransformEasing::teleport`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[require(TransformEasing)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, PartialEq, Debug, Clone)
+)]
+pub struct TransformExtrapolation;
+
+/// The [`Transform`] snapshots used by [`TransformInterpolation`] and [`TransformExtrapolation`].
+///
+/// It is automatically added along with them.
+///
+/// In [`PostUpdate`], between [`TransformSystems::Ease`] and [`TransformSystems::Restore`], the
+/// [`Transform`] of the entity holds the blended value instead of the actual one. Systems reading
+/// it there, like [`TransformSystems::Propagate`], see where the entity is rendered.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, PartialEq, Debug, Clone)
+)]
+pub struct TransformEasing {
+    start: Option<Transform>,
+    end: Option<Transform>,
+    actual: Option<Transform>,
+    eased: Option<Transform>,
+    teleport: bool,
+}
+
+impl TransformEasing {
+    /// Skips blending for the current fixed timestep, so that the entity instantly moves to its new [`Transform`].
+    pub fn teleport(&mut self) {
+        self.teleport = true;
+    }
+
+    /// Returns the [`Transform`] of the entity before the last fixed timestep, if any.
+    pub fn start(&self) -> Option<Transform> {
+        self.start
+    }
+
+    /// Returns the [`Transform`] of the entity after the last fixed timestep, if any.
+    pub fn end(&self) -> Option<Transform> {
+        self.end
+    }
+}
+
+/// Adds [`Transform`] interpolation and extrapolation for entities with
+/// [`TransformInterpolation`] or [`TransformExtrapolation`].
+///
+/// This requires [`Time<Fixed>`], usually added by `TimePlugin`, and [`TransformPlugin`](crate::TransformPlugin).
+#[derive(Default)]
+pub struct TransformInterpolationPlugin;
+
+impl Plugin for TransformInterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            PostUpdate,
+            (
+                TransformSystems::Ease.before(TransformSystems::Propagate),
+                TransformSystems::Restore.after(TransformSystems::Propagate),
+            ),
+        )
+        .add_systems(FixedFirst, snapshot_start_transforms)
+        .add_systems(FixedLast, snapshot_end_transforms)
+        .add_systems(
+            PostUpdate,
+            (
+                ease_transforms.in_set(TransformSystems::Ease),
+                restore_transforms.in_set(TransformSystems::Restore),
+            ),
+        );
+    }
+}
+
+/// Stores the [`Transform`] of eased entities before each fixed timestep.
+pub fn snapshot_start_transforms(mut query: Query<(&Transform, &mut TransformEasing)>) {
+    for (transform, mut easing) in &mut query {
+        easing.start = Some(*transform);
+    }
+}
+
+/// Stores the [`Transform`] of eased entities after each fixed timestep.
+pub fn snapshot_end_transforms(mut query: Query<(&Transform, &mut TransformEasing)>) {
+    for (transform, mut easing) in &mut query {
+        easing.end = Some(*transform);
+        if easing.teleport {
+            easing.start = Some(*transform);
+            easing.teleport = false;
+        }
+    }
+}
+
+/// Blends the [`Transform`] of eased entities before transform propagation, using [`Time<Fixed>::overstep_fraction`].
+///
+/// The actual [`Transform`] is restored by [`restore_transforms`].
+pub fn ease_transforms(
+    time: Res<Time<Fixed>>,
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut TransformEasing,
+            Has<TransformInterpolation>,
+        ),
+        Or<(With<TransformInterpolation>, With<TransformExtrapolation>)>,
+    >,
+) {
+    let overstep = time.overstep_fraction();
+    for (mut transform, mut easing, interpolate) in &mut query {
+        let (Some(start), Some(end)) = (easing.start, easing.end) else {
+            continue;
+        };
+        let eased = if interpolate {
+            Transform {
+                translation: start.translation.lerp(end.translation, overstep),
+                rotation: start.rotation.slerp(end.rotation, overstep),
+                scale: start.scale.lerp(end.scale, overstep),
+            }
+        } else {
+            let rotation = end.rotation * start.rotation.inverse();
+            Transform {
+                translation: start.translation.lerp(end.translation, 1.0 + overstep),
+                rotation: Quat::IDENTITY.slerp(rotation, overstep) * end.rotation,
+                scale: start.scale.lerp(end.scale, 1.0 + overstep),
+            }
+        };
+
+        easing.actual = Some(*transform);
+        // Only report a change when the blended value changed since the last propagation.
+        if easing.eased == Some(eased) {
+            *transform.bypass_change_detection() = eased;
+        } else {
+            *transform = eased;
+        }
+        easing.eased = Some(eased);
+    }
+}
+
+/// Restores the actual [`Transform`] of eased entities after transform propagation,
+/// so that only their [`GlobalTransform`](crate::components::GlobalTransform) and the ones of their descendants are blended.
+///
+/// The restored value isn't reported as a change.
+pub fn restore_transforms(mut query: Query<(&mut Transform, &mut TransformEasing)>) {
+    for (mut transform, mut easing) in &mut query {
+        if let Some(actual) = easing.actual.take() {
+            *transform.bypass_change_detection() = actual;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::GlobalTransform, TransformPlugin};
+    use bevy_math::Vec3;
+
+    fn setup() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((TransformPlugin, TransformInterpolationPlugin))
+            .init_resource::<Time<Fixed>>()
+            .add_systems(
+                bevy_app::FixedUpdate,
+                |mut query: Query<&mut Transform, Without<ChildOf>>| {
+                    for mut transform in &mut query {
+                        transform.translation.x += 10.0;
+                    }
+                },
+            );
+        let entity = app.world_mut().spawn(Transform::default()).id();
+        (app, entity)
+    }
+
+    fn fixed_update(app: &mut App) {
+        app.world_mut().run_schedule(FixedFirst);
+        app.world_mut().run_schedule(bevy_app::FixedUpdate);
+        app.world_mut().run_schedule(FixedLast);
+    }
+
+    fn overstep(app: &mut App, fraction: f32) {
+        let mut time = app.world_mut().resource_mut::<Time<Fixed>>();
+        let overstep = time.overstep();
+        time.discard_overstep(overstep);
+        let timestep = time.timestep();
+        time.accumulate_overstep(timestep.mul_f32(fraction));
+    }
+
+    fn rendered_x(app: &mut App, entity: Entity) -> f32 {
+        app.world_mut().run_schedule(PostUpdate);
+        app.world()
+            .get::<GlobalTransform>(entity)
+            .unwrap()
+            .translation()
+            .x
+    }
+
+    #[test]
+    fn interpolation_blends_global_transform() {
+        let (mut app, entity) = setup();
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(TransformInterpolation);
+        fixed_update(&mut app);
+        overstep(&mut app, 0.25);
+        assert_eq!(rendered_x(&mut app, entity), 2.5);
+
+        // The actual transform is restored, and blending is not applied twice.
+        assert_eq!(
+            app.world().get::<Transform>(entity).unwrap().translation,
+            Vec3::new(10.0, 0.0, 0.0)
+        );
+        overstep(&mut app, 0.5);
+        assert_eq!(rendered_x(&mut app, entity), 5.0);
+
+        fixed_update(&mut app);
+        assert_eq!(rendered_x(&mut app, entity), 15.0);
+    }
+
+    #[test]
+    fn descendants_follow_the_blended_transform() {
+        let (mut app, entity) = setup();
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(TransformInterpolation);
+        let child = app
+            .world_mut()
+            .spawn((Transform::from_xyz(1.0, 0.0, 0.0), ChildOf(entity)))
+            .id();
+        fixed_update(&mut app);
+        overstep(&mut app, 0.25);
+        assert_eq!(rendered_x(&mut app, entity), 2.5);
+        assert_eq!(
+            app.world()
+                .get::<GlobalTransform>(child)
+                .unwrap()
+                .translation()
+                .x,
+            3.5
+        );
+        assert_eq!(
+            app.world().get::<Transform>(child).unwrap().translation.x,
+            1.0
+        );
+
+        overstep(&mut app, 0.5);
+        app.world_mut().run_schedule(PostUpdate);
+        assert_eq!(
+            app.world()
+                .get::<GlobalTransform>(child)
+                .unwrap()
+                .translation()
+                .x,
+            6.0
+        );
+    }
+
+    #[test]
+    fn extrapolation_predicts_global_transform() {
+        let (mut app, entity) = setup();
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(TransformExtrapolation);
+        fixed_update(&mut app);
+        overstep(&mut app, 0.25);
+        assert_eq!(rendered_x(&mut app, entity), 12.5);
+    }
+
+    #[test]
+    fn teleport_skips_blending() {
+        let (mut app, entity) = setup();
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(TransformInterpolation);
+        fixed_update(&mut app);
+        app.world_mut()
+            .get_mut::<TransformEasing>(entity)
+            .unwrap()
+            .teleport();
+        fixed_update(&mut app);
+        overstep(&mut app, 0.5);
+        assert_eq!(rendered_x(&mut app, entity), 20.0);
+
+        fixed_update(&mut app);
+        assert_eq!(rendered_x(&mut app, entity), 25.0);
+    }
+
+    #[test]
+    fn unchanged_blend_is_not_reported_as_changed() {
+        let (mut app, entity) = setup();
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(TransformInterpolation);
+        fixed_update(&mut app);
+        overstep(&mut app, 0.25);
+        assert_eq!(rendered_x(&mut app, entity), 2.5);
+        let last_changed = |app: &App| {
+            app.world()
+                .entity(entity)
+                .get_ref::<Transform>()
+                .unwrap()
+                .last_changed()
+        };
+        let changed = last_changed(&app);
+
+        assert_eq!(rendered_x(&mut app, entity), 2.5);
+        assert_eq!(last_changed(&app), changed);
+
+        overstep(&mut app, 0.5);
+        assert_eq!(rendered_x(&mut app, entity), 5.0);
+        assert_ne!(last_changed(&app), changed);
+    }
+}
@@ -20,6 +20,10 @@ pub mod components;
 /// Transform related traits
 pub mod traits;
 
+/// Fixed timestep transform interpolation and extrapolation
+#[cfg(feature = "bevy-support")]
+pub mod interpolation;
+
 /// Transform related plugins
 #[cfg(feature = "bevy-support")]
 pub mod plugins;
@@ -45,6 +49,10 @@ pub mod prelude {
     pub use crate::{
         commands::BuildChildrenTransformExt,
         helper::TransformHelper,
+        interpolation::{
+            TransformEasing, TransformExtrapolation, TransformInterpolation,
+            TransformInterpolationPlugin,
+        },
         plugins::{TransformPlugin, TransformSystems},
         systems::StaticTransformOptimizations,
         traits::TransformPoint,
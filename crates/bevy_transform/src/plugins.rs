@@ -13,6 +13,17 @@ use bevy_ecs::schedule::{IntoScheduleConfigs, SystemSet};
 pub enum TransformSystems {
     /// Propagates changes in transform to children's [`GlobalTransform`]
     Propagate,
+    /// Blends the [`Transform`](crate::components::Transform) of entities with
+    /// [`TransformInterpolation`](crate::interpolation::TransformInterpolation) or
+    /// [`TransformExtrapolation`](crate::interpolation::TransformExtrapolation), before [`TransformSystems::Propagate`].
+    ///
+    /// This is only used by [`TransformInterpolationPlugin`](crate::interpolation::TransformInterpolationPlugin).
+    Ease,
+    /// Restores the [`Transform`](crate::components::Transform) of entities blended in
+    /// [`TransformSystems::Ease`], after [`TransformSystems::Propagate`].
+    ///
+    /// This is only used by [`TransformInterpolationPlugin`](crate::interpolation::TransformInterpolationPlugin).
+    Restore,
 }
 
 /// The base plugin for handling [`Transform`](crate::components::Transform) components
@@ -63,8 +63,9 @@ use gradient::GradientPlugin;
 
 use bevy_platform::collections::{HashMap, HashSet};
 use bevy_text::{
-    ComputedTextBlock, PositionedGlyph, Strikethrough, StrikethroughColor, TextBackgroundColor,
-    TextColor, TextLayoutInfo, Underline, UnderlineColor,
+    ComputedTextBlock, FontSmoothing, PositionedGlyph, SdfGlyphParams, Strikethrough,
+    StrikethroughColor, TextBackgroundColor, TextColor, TextLayoutInfo, TextSdfEffects, Underline,
+    UnderlineColor,
 };
 use bevy_transform::components::GlobalTransform;
 use box_shadow::BoxShadowPlugin;
@@ -391,6 +392,10 @@ pub struct ExtractedGlyph {
     pub color: LinearRgba,
     pub translation: Vec2,
     pub rect: Rect,
+    /// The size of the glyph on screen, which differs from the size of `rect` for distance field glyphs.
+    pub size: Vec2,
+    /// Distance field parameters for glyphs rendered with [`FontSmoothing::Sdf`].
+    pub sdf: Option<SdfGlyphParams>,
 }
 
 #[derive(Resource, Default)]
@@ -914,6 +919,7 @@ pub fn extract_text_sections(
             &ComputedTextBlock,
             &TextColor,
             &TextLayoutInfo,
+            Option<&TextSdfEffects>,
         )>,
     >,
     text_styles: Extract<Query<&TextColor>>,
@@ -933,6 +939,7 @@ pub fn extract_text_sections(
         computed_block,
         text_color,
         text_layout_info,
+        maybe_effects,
     ) in &uinode_query
     {
         // Skip if not visible or if size is set to zero (e.g. when a parent is set to `Display::None`)
@@ -946,14 +953,70 @@ pub fn extract_text_sections(
 
         let transform = Affine2::from(*transform) * Affine2::from_translation(-0.5 * uinode.size());
 
+        let is_sdf = |glyph: &PositionedGlyph| {
+            computed_block
+                .entities()
+                .get(glyph.span_index)
+                .is_some_and(|t| t.font_smoothing == FontSmoothing::Sdf)
+        };
+
+        // Distance field effects are drawn as extra layers of glyphs behind the text.
+        for layer in maybe_effects.into_iter().flat_map(TextSdfEffects::layers) {
+            let color = layer.color.to_linear();
+            let layer_transform =
+                transform * Affine2::from_translation(layer.offset / uinode.inverse_scale_factor());
+            let dilation = layer.dilation / uinode.inverse_scale_factor();
+            let softness = layer.softness / uinode.inverse_scale_factor();
+
+            let mut sdf_glyphs = text_layout_info
+                .glyphs
+                .iter()
+                .filter(|glyph| is_sdf(glyph))
+                .peekable();
+            while let Some(glyph) = sdf_glyphs.next() {
+                let rect = texture_atlases
+                    .get(glyph.atlas_info.texture_atlas)
+                    .unwrap()
+                    .textures[glyph.atlas_info.location.glyph_index]
+                    .as_rect();
+                extracted_uinodes.glyphs.push(ExtractedGlyph {
+                    color,
+                    translation: glyph.position,
+                    rect,
+                    size: glyph.size,
+                    sdf: Some(SdfGlyphParams::new(rect, glyph.size, dilation, softness)),
+                });
+
+                if sdf_glyphs
+                    .peek()
+                    .is_none_or(|next| next.atlas_info.texture != glyph.atlas_info.texture)
+                {
+                    extracted_uinodes.uinodes.push(ExtractedUiNode {
+                        z_order: uinode.stack_index as f32 + stack_z_offsets::TEXT,
+                        render_entity: commands.spawn(TemporaryRenderEntity).id(),
+                        image: glyph.atlas_info.texture,
+                        clip: clip.map(|clip| clip.clip),
+                        extracted_camera_entity,
+                        item: ExtractedUiItem::Glyphs { range: start..end },
+                        main_entity: entity.into(),
+                        transform: layer_transform,
+                    });
+                    start = end;
+                }
+
+                end += 1;
+            }
+        }
+
         let mut color = text_color.0.to_linear();
 
         let mut current_span_index = 0;
 
         for (
             i,
-            PositionedGlyph {
+            glyph @ PositionedGlyph {
                 position,
+                size,
                 atlas_info,
                 span_index,
                 ..
@@ -980,6 +1043,8 @@ pub fn extract_text_sections(
                 color,
                 translation: *position,
                 rect,
+                size: *size,
+                sdf: is_sdf(glyph).then(|| SdfGlyphParams::new(rect, *size, 0., 0.)),
             });
 
             if text_layout_info
@@ -1059,12 +1124,17 @@ pub fn extract_text_shadows(
             i,
             PositionedGlyph {
                 position,
+                size,
                 atlas_info,
                 span_index,
                 ..
             },
         ) in text_layout_info.glyphs.iter().enumerate()
         {
+            let is_sdf = computed_block
+                .entities()
+                .get(*span_index)
+                .is_some_and(|t| t.font_smoothing == FontSmoothing::Sdf);
             let rect = texture_atlases
                 .get(atlas_info.texture_atlas)
                 .unwrap()
@@ -1074,6 +1144,8 @@ pub fn extract_text_shadows(
                 color: shadow.color.into(),
                 translation: *position,
                 rect,
+                size: *size,
+                sdf: is_sdf.then(|| SdfGlyphParams::new(rect, *size, 0., 0.)),
             });
 
             if text_layout_info.glyphs.get(i + 1).is_none_or(|info| {
@@ -1375,6 +1447,8 @@ pub mod shader_flags {
     pub const BORDER_RIGHT: u32 = 1024;
     pub const BORDER_BOTTOM: u32 = 2048;
     pub const BORDER_ALL: u32 = BORDER_LEFT + BORDER_TOP + BORDER_RIGHT + BORDER_BOTTOM;
+    /// Texture is a signed distance field glyph
+    pub const SDF: u32 = 4096;
 }
 
 pub fn queue_uinodes(
@@ -1733,13 +1807,19 @@ pub fn prepare_uinodes(
                         for glyph in &extracted_uinodes.glyphs[range.clone()] {
                             let color = glyph.color.to_f32_array();
                             let glyph_rect = glyph.rect;
-                            let rect_size = glyph_rect.size();
+                            let rect_size = glyph.size;
+                            // Atlas texels per pixel, distance field glyphs are scaled to their size.
+                            let uv_scale = if rect_size.cmpgt(Vec2::ZERO).all() {
+                                glyph_rect.size() / rect_size
+                            } else {
+                                Vec2::ONE
+                            };
 
                             // Specify the corners of the glyph
                             let positions = QUAD_VERTEX_POSITIONS.map(|pos| {
                                 extracted_uinode
                                     .transform
-                                    .transform_point2(glyph.translation + pos * glyph_rect.size())
+                                    .transform_point2(glyph.translation + pos * rect_size)
                                     .extend(0.)
                             });
 
@@ -1784,6 +1864,7 @@ pub fn prepare_uinodes(
                                 continue;
                             }
 
+                            let positions_diff = positions_diff.map(|diff| diff * uv_scale);
                             let uvs = [
                                 Vec2::new(
                                     glyph.rect.min.x + positions_diff[0].x,
@@ -1804,14 +1885,25 @@ pub fn prepare_uinodes(
                             ]
                             .map(|pos| pos / atlas_extent);
 
+                            // Distance field parameters are passed in place of the unused border widths.
+                            let (sdf_flag, border) = match glyph.sdf {
+                                Some(sdf) => (
+                                    shader_flags::SDF,
+                                    [sdf.range, sdf.dilation, sdf.softness, 0.],
+                                ),
+                                None => (0, [0.; 4]),
+                            };
+
                             for i in 0..4 {
+                                let flags =
+                                    shader_flags::TEXTURED | sdf_flag | shader_flags::CORNERS[i];
                                 ui_meta.vertices.push(UiVertex {
                                     position: positions_clipped[i].into(),
                                     uv: uvs[i].into(),
                                     color,
-                                    flags: shader_flags::TEXTURED | shader_flags::CORNERS[i],
+                                    flags,
                                     radius: [0.0; 4],
-                                    border: [0.0; 4],
+                                    border,
                                     size: rect_size.into(),
                                     point: [0.0; 2],
                                 });
@@ -11,6 +11,8 @@ const BORDER_TOP: u32 = 512u;
 const BORDER_RIGHT: u32 = 1024u;
 const BORDER_BOTTOM: u32 = 2048u;
 const BORDER_ANY: u32 = BORDER_LEFT + BORDER_TOP + BORDER_RIGHT + BORDER_BOTTOM;
+// must align with SDF shader_flag from bevy_ui/render/mod.rs
+const SDF: u32 = 4096u;
 
 fn enabled(flags: u32, mask: u32) -> bool {
     return (flags & mask) != 0u;
@@ -218,7 +220,16 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     // This allows us to draw both textured and untextured shapes together in the same batch.
     let color = select(in.color, in.color * texture_color, enabled(in.flags, TEXTURED));
 
-    if enabled(in.flags, BORDER_ANY) {
+    // Signed distance field glyphs store the distance to the glyph outline in the alpha channel.
+    // The range, dilation and softness of the glyph, in atlas texels, are passed in `border`.
+    // Derivatives must be computed in uniform control flow, so this is evaluated for every fragment.
+    let sdf_distance = (texture_color.a - 0.5) * in.border.x + in.border.y;
+    let sdf_aa = max(0.5 * fwidth(sdf_distance), 0.0001);
+
+    if enabled(in.flags, SDF) {
+        let t = smoothstep(-in.border.z - sdf_aa, sdf_aa, sdf_distance);
+        return vec4(in.color.rgb, saturate(in.color.a * t));
+    } else if enabled(in.flags, BORDER_ANY) {
         return draw_uinode_border(color, in.point, in.size, in.radius, in.border, in.flags);
     } else {
         return draw_uinode_background(color, in.point, in.size, in.radius, in.border);
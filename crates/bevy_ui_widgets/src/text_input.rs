@@ -0,0 +1,1286 @@
+use core::ops::Range;
+
+use accesskit::Role;
+use bevy_a11y::AccessibilityNode;
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_camera::{visibility::Visibility, NormalizedRenderTarget, RenderTarget};
+use bevy_color::Color;
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut, Ref},
+    component::Component,
+    entity::{ContainsEntity, Entity},
+    hierarchy::{ChildOf, Children},
+    lifecycle::Insert,
+    observer::On,
+    query::{Has, With, Without},
+    resource::Resource,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Local, Query, Res, ResMut},
+    world::DeferredWorld,
+};
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput},
+    ButtonInput, ButtonState,
+};
+use bevy_input_focus::{
+    dispatch_focused_input, FocusedInput, InputFocus, InputFocusSystems, InputFocusVisible,
+};
+use bevy_math::Vec2;
+use bevy_picking::events::{Drag, Pointer, Press};
+use bevy_picking::pointer::PointerButton;
+use bevy_text::{
+    ComputedTextBlock, LineHeight, TextBackgroundColor, TextColor, TextFont, TextSpan, Underline,
+};
+use bevy_ui::{
+    widget::Text, ComputedNode, ComputedUiRenderTargetInfo, ComputedUiTargetCamera,
+    InteractionDisabled, Node, PositionType, UiGlobalTransform, UiScale, UiSystems, Val,
+};
+use bevy_window::{Ime, PrimaryWindow, Window};
+
+use crate::{Activate, ValueChange};
+
+/// The maximum number of steps kept in the undo history of a [`TextInputState`].
+const MAX_UNDO_STEPS: usize = 100;
+
+/// Headless widget implementation for editable text fields. The current value, cursor and
+/// selection are stored in the required [`TextInputState`] component, which the widget updates
+/// itself in response to keyboard, IME and pointer input. A [`ValueChange<String>`] event is
+/// emitted whenever the value is edited, and single-line inputs emit [`Activate`] when `Enter`
+/// is pressed.
+///
+/// The widget makes no assumptions about the hierarchical structure of the field, but expects
+/// a descendant [`Text`] entity marked with [`TextInputText`], into which the (possibly masked)
+/// value is written. Selected text is highlighted with [`TextInput::selection_color`], and IME
+/// composition text is underlined. An optional descendant marked with [`TextInputCaret`] is
+/// absolutely positioned at the cursor while the input is focused; its size and color are up
+/// to the stylist.
+///
+/// Clicking the input gives it [`InputFocus`]. Add a [`TabIndex`](bevy_input_focus::tab_navigation::TabIndex)
+/// to make it reachable via tab navigation: the `Tab` key is never consumed by the input.
+/// Copy, cut and paste go through the [`TextInputClipboard`] resource.
+#[derive(Component, Debug, Clone)]
+#[require(
+    AccessibilityNode(accesskit::Node::new(Role::TextInput)),
+    TextInputState
+)]
+pub struct TextInput {
+    /// Whether the input accepts line breaks. Single-line inputs strip line breaks from
+    /// inserted text.
+    pub multiline: bool,
+    /// The maximum number of characters in the value. Inserted text is truncated to fit.
+    pub max_length: Option<usize>,
+    /// If set, every character of the value is displayed as this character, e.g. for password
+    /// fields. Copying and cutting are disabled for masked inputs.
+    pub mask: Option<char>,
+    /// Edits that would produce a value rejected by this function are discarded.
+    pub validator: Option<fn(&str) -> bool>,
+    /// The background color of selected text.
+    pub selection_color: Color,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self {
+            multiline: false,
+            max_length: None,
+            mask: None,
+            validator: None,
+            selection_color: Color::srgba(0.25, 0.45, 0.9, 0.5),
+        }
+    }
+}
+
+/// Marker component that identifies which descendant [`Text`] entity displays the value of a
+/// [`TextInput`].
+#[derive(Component, Debug, Default)]
+pub struct TextInputText;
+
+/// Marker component that identifies which descendant element is the caret of a [`TextInput`].
+#[derive(Component, Debug, Default)]
+pub struct TextInputCaret;
+
+/// The span entities used to display the selected text and the text after it.
+#[derive(Component, Debug)]
+struct TextInputSpans([Entity; 2]);
+
+/// A cursor movement within a [`TextInputState`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TextMotion {
+    /// Move one character to the left.
+    Left,
+    /// Move one character to the right.
+    Right,
+    /// Move to the start of the current or previous word.
+    WordLeft,
+    /// Move to the start of the next word.
+    WordRight,
+    /// Move to the start of the current line.
+    LineStart,
+    /// Move to the end of the current line.
+    LineEnd,
+    /// Move to the same column in the previous line.
+    Up,
+    /// Move to the same column in the next line.
+    Down,
+    /// Move to the start of the value.
+    Start,
+    /// Move to the end of the value.
+    End,
+}
+
+/// Which text a deletion removes when nothing is selected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TextDeletion {
+    /// The character before the cursor.
+    Backward,
+    /// The character after the cursor.
+    Forward,
+    /// The word before the cursor.
+    WordBackward,
+    /// The word after the cursor.
+    WordForward,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum EditKind {
+    #[default]
+    Other,
+    Insert,
+    Delete,
+}
+
+#[derive(Clone, Debug)]
+struct Snapshot {
+    value: String,
+    cursor: usize,
+    anchor: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Preedit {
+    text: String,
+    cursor: Option<usize>,
+}
+
+/// The editing state of a [`TextInput`]: its value, cursor, selection, IME composition and
+/// undo history. All positions are byte offsets into the value.
+///
+/// The value can be read and replaced directly from systems, or edited with the same operations
+/// used by the widget's input handling.
+#[derive(Component, Debug, Default, Clone)]
+pub struct TextInputState {
+    value: String,
+    cursor: usize,
+    anchor: usize,
+    preedit: Option<Preedit>,
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    last_edit: EditKind,
+}
+
+impl TextInputState {
+    /// Creates a new state with the given value and the cursor at the end.
+    pub fn new(value: impl Into<String>) -> Self {
+        let mut state = Self::default();
+        state.set_value(value);
+        state
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the value, moves the cursor to the end and clears the undo history.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+        self.anchor = self.cursor;
+        self.preedit = None;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_edit = EditKind::Other;
+    }
+
+    /// Returns the position of the cursor.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the selected range, which is empty if nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// Returns the selected text.
+    pub fn selected_text(&self) -> &str {
+        &self.value[self.selection()]
+    }
+
+    /// Returns true if any text is selected.
+    pub fn has_selection(&self) -> bool {
+        self.cursor != self.anchor
+    }
+
+    /// Moves the cursor to `position`, clamped to the value. If `extend` is true the selection
+    /// is extended to the new position, otherwise the selection is cleared.
+    pub fn set_cursor(&mut self, position: usize, extend: bool) {
+        let mut position = position.min(self.value.len());
+        while !self.value.is_char_boundary(position) {
+            position -= 1;
+        }
+        self.cursor = position;
+        if !extend {
+            self.anchor = position;
+        }
+        self.last_edit = EditKind::Other;
+    }
+
+    /// Selects the whole value.
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.cursor = self.value.len();
+        self.last_edit = EditKind::Other;
+    }
+
+    /// Moves the cursor. If `extend` is true the selection is extended to the new position,
+    /// otherwise the selection is cleared.
+    ///
+    /// Moving left or right without extending collapses an existing selection to its start or
+    /// end. [`TextMotion::Up`] and [`TextMotion::Down`] move between lines separated by line
+    /// breaks, not between visually wrapped lines.
+    pub fn move_cursor(&mut self, motion: TextMotion, extend: bool) {
+        let value = self.value.as_str();
+        let cursor = self.cursor;
+        let position = match motion {
+            TextMotion::Left if !extend && self.has_selection() => self.selection().start,
+            TextMotion::Right if !extend && self.has_selection() => self.selection().end,
+            TextMotion::Left => prev_char(value, cursor),
+            TextMotion::Right => next_char(value, cursor),
+            TextMotion::WordLeft => word_left(value, cursor),
+            TextMotion::WordRight => word_right(value, cursor),
+            TextMotion::LineStart => line_start(value, cursor),
+            TextMotion::LineEnd => line_end(value, cursor),
+            TextMotion::Up => {
+                let start = line_start(value, cursor);
+                if start == 0 {
+                    0
+                } else {
+                    let column = value[start..cursor].chars().count();
+                    nth_char(value, line_start(value, start - 1), start - 1, column)
+                }
+            }
+            TextMotion::Down => {
+                let end = line_end(value, cursor);
+                if end == value.len() {
+                    end
+                } else {
+                    let column = value[line_start(value, cursor)..cursor].chars().count();
+                    nth_char(value, end + 1, line_end(value, end + 1), column)
+                }
+            }
+            TextMotion::Start => 0,
+            TextMotion::End => value.len(),
+        };
+        self.set_cursor(position, extend);
+    }
+
+    /// Replaces the selection with `text`, as if it was typed. Returns true if the value changed.
+    ///
+    /// The text is filtered according to the [`TextInput`] settings: control characters (and
+    /// line breaks for single-line inputs) are removed, and the text is truncated to respect
+    /// [`TextInput::max_length`]. Consecutive single-character insertions are undone together.
+    pub fn insert(&mut self, text: &str, input: &TextInput) -> bool {
+        let kind = if text.chars().count() == 1 {
+            EditKind::Insert
+        } else {
+            EditKind::Other
+        };
+        self.replace_selection(text, input, kind)
+    }
+
+    /// Replaces the selection with `text`, as a single undo step. Returns true if the value
+    /// changed.
+    pub fn paste(&mut self, text: &str, input: &TextInput) -> bool {
+        self.replace_selection(text, input, EditKind::Other)
+    }
+
+    /// Deletes the selection, or the text described by `deletion` if nothing is selected.
+    /// Returns true if the value changed.
+    pub fn delete(&mut self, deletion: TextDeletion, input: &TextInput) -> bool {
+        let anchor = self.anchor;
+        if !self.has_selection() {
+            let value = self.value.as_str();
+            self.anchor = match deletion {
+                TextDeletion::Backward => prev_char(value, self.cursor),
+                TextDeletion::Forward => next_char(value, self.cursor),
+                TextDeletion::WordBackward => word_left(value, self.cursor),
+                TextDeletion::WordForward => word_right(value, self.cursor),
+            };
+        }
+        let changed = self.replace_selection("", input, EditKind::Delete);
+        if !changed {
+            self.anchor = anchor;
+        }
+        changed
+    }
+
+    /// Removes the selected text and returns it. Returns `None` if nothing is selected or
+    /// the removal was rejected.
+    pub fn cut(&mut self, input: &TextInput) -> Option<String> {
+        let text = self.selected_text().to_string();
+        self.replace_selection("", input, EditKind::Other)
+            .then_some(text)
+    }
+
+    /// Reverts the last edit. Returns true if there was anything to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo_stack.pop() else {
+            return false;
+        };
+        let current = self.restore(snapshot);
+        self.redo_stack.push(current);
+        true
+    }
+
+    /// Reapplies the last undone edit. Returns true if there was anything to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
+        let current = self.restore(snapshot);
+        self.undo_stack.push(current);
+        true
+    }
+
+    /// Returns the text currently being composed by an input method, if any.
+    pub fn preedit(&self) -> Option<&str> {
+        self.preedit.as_ref().map(|preedit| preedit.text.as_str())
+    }
+
+    /// Sets the text currently being composed by an input method. The composition is displayed
+    /// in place of the selection until it is committed or cleared. `cursor` is a byte offset
+    /// into `text`, or `None` to hide the caret.
+    pub fn set_preedit(&mut self, text: impl Into<String>, cursor: Option<usize>) {
+        self.preedit = Some(Preedit {
+            text: text.into(),
+            cursor,
+        });
+    }
+
+    /// Clears the text being composed by an input method.
+    pub fn clear_preedit(&mut self) {
+        self.preedit = None;
+    }
+
+    fn replace_selection(&mut self, text: &str, input: &TextInput, kind: EditKind) -> bool {
+        let range = self.selection();
+        let mut text: String = text
+            .chars()
+            .filter(|&c| !c.is_control() || (input.multiline && c == '\n'))
+            .collect();
+        if let Some(max_length) = input.max_length {
+            let kept = self.value.chars().count() - self.value[range.clone()].chars().count();
+            if let Some((end, _)) = text.char_indices().nth(max_length.saturating_sub(kept)) {
+                text.truncate(end);
+            }
+        }
+        if text.is_empty() && range.is_empty() {
+            return false;
+        }
+
+        let mut value = self.value.clone();
+        value.replace_range(range.clone(), &text);
+        if input.validator.is_some_and(|validator| !validator(&value)) {
+            return false;
+        }
+
+        if kind == EditKind::Other || kind != self.last_edit {
+            self.undo_stack.push(self.snapshot());
+            if self.undo_stack.len() > MAX_UNDO_STEPS {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+        self.value = value;
+        self.cursor = range.start + text.len();
+        self.anchor = self.cursor;
+        self.last_edit = kind;
+        true
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            value: self.value.clone(),
+            cursor: self.cursor,
+            anchor: self.anchor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        let current = self.snapshot();
+        self.value = snapshot.value;
+        self.cursor = snapshot.cursor;
+        self.anchor = snapshot.anchor;
+        self.preedit = None;
+        self.last_edit = EditKind::Other;
+        current
+    }
+
+    /// Splits the displayed text into the text before the selection (or composition), the
+    /// selection (or composition) itself, and the text after it. Also returns the position of
+    /// the caret in the displayed text.
+    fn display(&self, mask: Option<char>) -> ([String; 3], Option<usize>) {
+        let range = self.selection();
+        let before = masked(&self.value[..range.start], mask);
+        let after = masked(&self.value[range.end..], mask);
+        if let Some(preedit) = &self.preedit {
+            let caret = preedit.cursor.map(|cursor| before.len() + cursor);
+            return ([before, preedit.text.clone(), after], caret);
+        }
+        let selected = masked(&self.value[range.clone()], mask);
+        let caret = if self.cursor == range.start {
+            before.len()
+        } else {
+            before.len() + selected.len()
+        };
+        ([before, selected, after], Some(caret))
+    }
+}
+
+/// A clipboard used by [`TextInput`] for copy, cut and paste.
+pub trait TextClipboard: Send + Sync + 'static {
+    /// Returns the text in the clipboard, if any.
+    fn read(&mut self) -> Option<String>;
+    /// Replaces the contents of the clipboard.
+    fn write(&mut self, text: &str);
+}
+
+/// A [`TextClipboard`] which only shares text between the inputs of this app.
+#[derive(Debug, Default)]
+pub struct LocalClipboard(Option<String>);
+
+impl TextClipboard for LocalClipboard {
+    fn read(&mut self) -> Option<String> {
+        self.0.clone()
+    }
+
+    fn write(&mut self, text: &str) {
+        self.0 = Some(text.to_string());
+    }
+}
+
+/// Resource holding the [`TextClipboard`] used by all text inputs. Defaults to a
+/// [`LocalClipboard`]; replace it to integrate with the system clipboard.
+#[derive(Resource)]
+pub struct TextInputClipboard(pub Box<dyn TextClipboard>);
+
+impl Default for TextInputClipboard {
+    fn default() -> Self {
+        Self(Box::new(LocalClipboard::default()))
+    }
+}
+
+fn prev_char(value: &str, position: usize) -> usize {
+    value[..position]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(index, _)| index)
+}
+
+fn next_char(value: &str, position: usize) -> usize {
+    value[position..]
+        .chars()
+        .next()
+        .map_or(position, |c| position + c.len_utf8())
+}
+
+/// Returns the position of the `n`th character in `value[start..end]`, or `end`.
+fn nth_char(value: &str, start: usize, end: usize, n: usize) -> usize {
+    value[start..end]
+        .char_indices()
+        .nth(n)
+        .map_or(end, |(index, _)| start + index)
+}
+
+fn line_start(value: &str, position: usize) -> usize {
+    value[..position].rfind('\n').map_or(0, |index| index + 1)
+}
+
+fn line_end(value: &str, position: usize) -> usize {
+    value[position..]
+        .find('\n')
+        .map_or(value.len(), |index| position + index)
+}
+
+fn char_class(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+fn word_left(value: &str, position: usize) -> usize {
+    let mut chars = value[..position]
+        .char_indices()
+        .rev()
+        .skip_while(|(_, c)| c.is_whitespace())
+        .peekable();
+    let Some(&(mut start, c)) = chars.peek() else {
+        return 0;
+    };
+    let class = char_class(c);
+    for (index, c) in chars {
+        if char_class(c) != class {
+            break;
+        }
+        start = index;
+    }
+    start
+}
+
+fn word_right(value: &str, position: usize) -> usize {
+    let mut chars = value[position..].char_indices().peekable();
+    if let Some(&(_, first)) = chars.peek() {
+        let class = char_class(first);
+        if class != 0 {
+            while chars.next_if(|&(_, c)| char_class(c) == class).is_some() {}
+        }
+    }
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    chars
+        .peek()
+        .map_or(value.len(), |(index, _)| position + index)
+}
+
+/// Replaces every character except line breaks with `mask`.
+fn masked(value: &str, mask: Option<char>) -> String {
+    match mask {
+        Some(mask) => value
+            .chars()
+            .map(|c| if c == '\n' { c } else { mask })
+            .collect(),
+        None => value.to_string(),
+    }
+}
+
+/// Converts a byte offset into the masked display text into a byte offset into the value.
+fn unmasked_position(value: &str, mask: Option<char>, position: usize) -> usize {
+    let Some(mask) = mask else {
+        return position.min(value.len());
+    };
+    let mut display_position = 0;
+    for (index, c) in value.char_indices() {
+        if display_position >= position {
+            return index;
+        }
+        display_position += if c == '\n' { 1 } else { mask.len_utf8() };
+    }
+    value.len()
+}
+
+/// Returns the horizontal position, top and height of a caret placed at byte `index` of
+/// buffer line `line`, in physical pixels relative to the top left of the text.
+fn caret_geometry(block: &ComputedTextBlock, line: usize, index: usize) -> Option<(f32, f32, f32)> {
+    let mut last = None;
+    for run in block
+        .buffer()
+        .layout_runs()
+        .filter(|run| run.line_i == line)
+    {
+        if let Some(glyph) = run.glyphs.iter().find(|glyph| glyph.start >= index) {
+            return Some((glyph.x, run.line_top, run.line_height));
+        }
+        let end = run.glyphs.last().map_or(0., |glyph| glyph.x + glyph.w);
+        last = Some((end, run.line_top, run.line_height));
+    }
+    last
+}
+
+/// Returns the position in the value of the text input under the pointer.
+fn hit_position(
+    state: &TextInputState,
+    input: &TextInput,
+    (block, node, transform, target): TextLayoutItem,
+    ui_scale: f32,
+    pointer_position: Vec2,
+) -> Option<usize> {
+    if state.preedit.is_some() {
+        return None;
+    }
+    let local_position = transform
+        .try_inverse()?
+        .transform_point2(pointer_position * target.scale_factor() / ui_scale)
+        + node.size() / 2.;
+    let cursor = block.buffer().hit(local_position.x, local_position.y)?;
+    let display = masked(&state.value, input.mask);
+    let line_start: usize = display
+        .split('\n')
+        .take(cursor.line)
+        .map(|line| line.len() + 1)
+        .sum();
+    Some(unmasked_position(
+        &state.value,
+        input.mask,
+        line_start + cursor.index,
+    ))
+}
+
+type TextLayoutItem<'a> = (
+    &'a ComputedTextBlock,
+    &'a ComputedNode,
+    &'a UiGlobalTransform,
+    &'a ComputedUiRenderTargetInfo,
+);
+
+fn text_input_on_insert(insert: On<Insert, TextInput>, mut world: DeferredWorld) {
+    let mut entity = world.entity_mut(insert.entity);
+    let input = entity.get::<TextInput>().unwrap();
+    let role = if input.mask.is_some() {
+        Role::PasswordInput
+    } else if input.multiline {
+        Role::MultilineTextInput
+    } else {
+        Role::TextInput
+    };
+    if let Some(mut accessibility) = entity.get_mut::<AccessibilityNode>() {
+        accessibility.set_role(role);
+    }
+}
+
+fn text_input_on_key_input(
+    mut ev: On<FocusedInput<KeyboardInput>>,
+    mut q_input: Query<(&TextInput, &mut TextInputState), Without<InteractionDisabled>>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mut clipboard: Option<ResMut<TextInputClipboard>>,
+    mut commands: Commands,
+) {
+    let entity = ev.focused_entity;
+    let Ok((input, mut state)) = q_input.get_mut(entity) else {
+        return;
+    };
+    let event = &ev.event().input;
+    if event.state != ButtonState::Pressed {
+        return;
+    }
+
+    let pressed = |codes: [KeyCode; 2]| keys.as_ref().is_some_and(|keys| keys.any_pressed(codes));
+    let shift = pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let ctrl = pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let alt = pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    // Ctrl+Alt is how AltGr is reported on Windows, so it types characters instead of being a
+    // shortcut.
+    let command = (ctrl && !alt) || pressed([KeyCode::SuperLeft, KeyCode::SuperRight]);
+    let word = ctrl || alt;
+
+    let mut changed = false;
+    match &event.logical_key {
+        Key::ArrowLeft if word => state.move_cursor(TextMotion::WordLeft, shift),
+        Key::ArrowLeft => state.move_cursor(TextMotion::Left, shift),
+        Key::ArrowRight if word => state.move_cursor(TextMotion::WordRight, shift),
+        Key::ArrowRight => state.move_cursor(TextMotion::Right, shift),
+        Key::ArrowUp if input.multiline => state.move_cursor(TextMotion::Up, shift),
+        Key::ArrowDown if input.multiline => state.move_cursor(TextMotion::Down, shift),
+        Key::Home if command => state.move_cursor(TextMotion::Start, shift),
+        Key::Home => state.move_cursor(TextMotion::LineStart, shift),
+        Key::End if command => state.move_cursor(TextMotion::End, shift),
+        Key::End => state.move_cursor(TextMotion::LineEnd, shift),
+        Key::Backspace if word => changed = state.delete(TextDeletion::WordBackward, input),
+        Key::Backspace => changed = state.delete(TextDeletion::Backward, input),
+        Key::Delete if word => changed = state.delete(TextDeletion::WordForward, input),
+        Key::Delete => changed = state.delete(TextDeletion::Forward, input),
+        Key::Enter if input.multiline => changed = state.insert("\n", input),
+        Key::Enter => {
+            if !event.repeat {
+                commands.trigger(Activate { entity });
+            }
+        }
+        Key::Character(c) if command => match c.to_lowercase().as_str() {
+            "a" => state.select_all(),
+            "c" if input.mask.is_none() && state.has_selection() => {
+                if let Some(clipboard) = clipboard.as_mut() {
+                    clipboard.0.write(state.selected_text());
+                }
+            }
+            "x" if input.mask.is_none() => {
+                if let Some(text) = state.cut(input) {
+                    if let Some(clipboard) = clipboard.as_mut() {
+                        clipboard.0.write(&text);
+                    }
+                    changed = true;
+                }
+            }
+            "v" => {
+                if let Some(text) = clipboard.as_mut().and_then(|clipboard| clipboard.0.read()) {
+                    changed = state.paste(&text, input);
+                }
+            }
+            "z" if shift => changed = state.redo(),
+            "z" => changed = state.undo(),
+            "y" => changed = state.redo(),
+            _ => return,
+        },
+        _ => match &event.text {
+            Some(text)
+                if !command && state.preedit.is_none() && text.chars().any(|c| !c.is_control()) =>
+            {
+                changed = state.insert(text, input);
+            }
+            _ => return,
+        },
+    }
+
+    ev.propagate(false);
+    if changed {
+        commands.trigger(ValueChange {
+            source: entity,
+            value: state.value.clone(),
+        });
+    }
+}
+
+fn text_input_on_ime(
+    mut ev: On<FocusedInput<Ime>>,
+    mut q_input: Query<(&TextInput, &mut TextInputState), Without<InteractionDisabled>>,
+    mut commands: Commands,
+) {
+    let entity = ev.focused_entity;
+    let Ok((input, mut state)) = q_input.get_mut(entity) else {
+        return;
+    };
+    ev.propagate(false);
+    match &ev.event().input {
+        Ime::Preedit { value, cursor, .. } if !value.is_empty() => {
+            state.set_preedit(value.clone(), cursor.map(|(start, _)| start));
+        }
+        Ime::Commit { value, .. } => {
+            state.clear_preedit();
+            if state.insert(value, input) {
+                commands.trigger(ValueChange {
+                    source: entity,
+                    value: state.value.clone(),
+                });
+            }
+        }
+        Ime::Preedit { .. } | Ime::Disabled { .. } => state.clear_preedit(),
+        Ime::Enabled { .. } => {}
+    }
+}
+
+fn text_input_on_pointer_press(
+    mut press: On<Pointer<Press>>,
+    mut q_input: Query<(&TextInput, &mut TextInputState, Has<InteractionDisabled>)>,
+    q_text: Query<TextLayoutItem, With<TextInputText>>,
+    q_children: Query<&Children>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    ui_scale: Res<UiScale>,
+    focus: Option<ResMut<InputFocus>>,
+    focus_visible: Option<ResMut<InputFocusVisible>>,
+) {
+    let Ok((input, mut state, disabled)) = q_input.get_mut(press.entity) else {
+        return;
+    };
+    press.propagate(false);
+    if disabled || press.button != PointerButton::Primary {
+        return;
+    }
+
+    // Clicking on a text input makes it the focused input,
+    // and hides the focus ring if it was visible.
+    if let Some(mut focus) = focus {
+        focus.0 = Some(press.entity);
+    }
+    if let Some(mut focus_visible) = focus_visible {
+        focus_visible.0 = false;
+    }
+
+    let extend =
+        keys.is_some_and(|keys| keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]));
+    if let Some(position) = q_children
+        .iter_descendants(press.entity)
+        .find_map(|child| q_text.get(child).ok())
+        .and_then(|text| {
+            hit_position(
+                &state,
+                input,
+                text,
+                ui_scale.0,
+                press.pointer_location.position,
+            )
+        })
+    {
+        state.set_cursor(position, extend);
+    }
+}
+
+fn text_input_on_drag(
+    mut drag: On<Pointer<Drag>>,
+    mut q_input: Query<(&TextInput, &mut TextInputState), Without<InteractionDisabled>>,
+    q_text: Query<TextLayoutItem, With<TextInputText>>,
+    q_children: Query<&Children>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok((input, mut state)) = q_input.get_mut(drag.entity) else {
+        return;
+    };
+    drag.propagate(false);
+    if drag.button != PointerButton::Primary {
+        return;
+    }
+
+    if let Some(position) = q_children
+        .iter_descendants(drag.entity)
+        .find_map(|child| q_text.get(child).ok())
+        .and_then(|text| {
+            hit_position(
+                &state,
+                input,
+                text,
+                ui_scale.0,
+                drag.pointer_location.position,
+            )
+        })
+    {
+        state.set_cursor(position, true);
+    }
+}
+
+/// Writes the displayed value of each changed [`TextInput`] into its [`TextInputText`], and copies
+/// the style of the [`TextInputText`] to the spans displaying the selection and the text after it.
+fn update_text_input_text(
+    mut q_input: Query<(
+        Entity,
+        Ref<TextInput>,
+        Ref<TextInputState>,
+        &mut AccessibilityNode,
+    )>,
+    q_children: Query<&Children>,
+    mut q_text: Query<
+        (
+            Entity,
+            &mut Text,
+            Ref<TextFont>,
+            Ref<TextColor>,
+            Ref<LineHeight>,
+            Option<&TextInputSpans>,
+        ),
+        With<TextInputText>,
+    >,
+    mut q_span: Query<
+        (
+            &mut TextSpan,
+            &mut TextFont,
+            &mut TextColor,
+            &mut LineHeight,
+        ),
+        Without<TextInputText>,
+    >,
+    mut commands: Commands,
+) {
+    for (entity, input, state, mut accessibility) in q_input.iter_mut() {
+        let input_changed = input.is_changed() || state.is_changed();
+        if input_changed {
+            accessibility.set_value(masked(&state.value, input.mask));
+        }
+
+        let Some(text_entity) = q_children
+            .iter_descendants(entity)
+            .find(|child| q_text.contains(*child))
+        else {
+            continue;
+        };
+        let (text_entity, mut text, font, color, line_height, spans) =
+            q_text.get_mut(text_entity).unwrap();
+        // The spans copy the style of the text entity, so they're also updated when it changes.
+        if !input_changed && !font.is_changed() && !color.is_changed() && !line_height.is_changed()
+        {
+            continue;
+        }
+
+        let ([before, middle, after], _) = state.display(input.mask);
+        if text.0 != before {
+            text.0 = before;
+        }
+
+        let spans = match spans {
+            Some(spans) => spans.0,
+            None => {
+                let spans = [middle.clone(), after.clone()].map(|span| {
+                    commands
+                        .spawn((
+                            TextSpan(span),
+                            font.clone(),
+                            *color,
+                            *line_height,
+                            ChildOf(text_entity),
+                        ))
+                        .id()
+                });
+                commands.entity(text_entity).insert(TextInputSpans(spans));
+                spans
+            }
+        };
+        for (span_entity, span_text) in spans.into_iter().zip([middle, after]) {
+            if let Ok((mut span, mut span_font, mut span_color, mut span_line_height)) =
+                q_span.get_mut(span_entity)
+            {
+                if span.0 != span_text {
+                    span.0 = span_text;
+                }
+                span_font.set_if_neq(font.clone());
+                span_color.set_if_neq(*color);
+                span_line_height.set_if_neq(*line_height);
+            }
+        }
+
+        let mut middle_span = commands.entity(spans[0]);
+        if state.preedit.is_some() {
+            middle_span
+                .insert(Underline)
+                .remove::<TextBackgroundColor>();
+        } else {
+            middle_span
+                .insert(TextBackgroundColor(input.selection_color))
+                .remove::<Underline>();
+        }
+    }
+}
+
+/// Positions the [`TextInputCaret`] of each [`TextInput`], and enables IME on the window of the
+/// focused text input while it has focus.
+fn update_text_input_caret(
+    focus: Option<Res<InputFocus>>,
+    ui_scale: Res<UiScale>,
+    q_input: Query<(
+        Entity,
+        &TextInput,
+        &TextInputState,
+        Option<&ComputedUiTargetCamera>,
+        Has<InteractionDisabled>,
+    )>,
+    q_children: Query<&Children>,
+    q_text: Query<TextLayoutItem, With<TextInputText>>,
+    q_layout: Query<(&ComputedNode, &UiGlobalTransform)>,
+    mut q_caret: Query<(&mut Node, &mut Visibility, &ChildOf), With<TextInputCaret>>,
+    q_camera: Query<&RenderTarget>,
+    q_primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut q_window: Query<&mut Window>,
+    mut ime_window: Local<Option<Entity>>,
+) {
+    let focused = focus.and_then(|focus| focus.0);
+    let mut focused_window = None;
+    let mut ime_position = None;
+
+    for (entity, input, state, target_camera, disabled) in q_input.iter() {
+        let is_focused = focused == Some(entity) && !disabled;
+        if is_focused {
+            focused_window = target_camera
+                .and_then(ComputedUiTargetCamera::get)
+                .and_then(|camera| q_camera.get(camera).ok())
+                .and_then(|target| target.normalize(q_primary_window.iter().next()))
+                .and_then(|target| match target {
+                    NormalizedRenderTarget::Window(window) => Some(window.entity()),
+                    _ => None,
+                });
+        }
+        let Some((block, text_node, text_transform, target)) = q_children
+            .iter_descendants(entity)
+            .find_map(|child| q_text.get(child).ok())
+        else {
+            continue;
+        };
+
+        // Top left of the caret and its height, in physical pixels relative to the top left
+        // of the text.
+        let (display, caret) = state.display(input.mask);
+        let caret = caret
+            .filter(|_| is_focused && (state.preedit.is_some() || !state.has_selection()))
+            .map(|caret| {
+                let display = display.concat();
+                let line = display[..caret].matches('\n').count();
+                let index = caret - line_start(&display, caret);
+                caret_geometry(block, line, index)
+                    .map_or((Vec2::ZERO, text_node.size().y), |(x, top, height)| {
+                        (Vec2::new(x, top), height)
+                    })
+            });
+        let text_top_left = text_transform.translation - text_node.size() / 2.;
+
+        if let Some((offset, height)) = caret {
+            ime_position = Some(
+                (text_top_left + offset + Vec2::new(0., height)) * ui_scale.0
+                    / target.scale_factor(),
+            );
+        }
+
+        let Some(caret_entity) = q_children
+            .iter_descendants(entity)
+            .find(|child| q_caret.contains(*child))
+        else {
+            continue;
+        };
+        let (mut node, mut visibility, child_of) = q_caret.get_mut(caret_entity).unwrap();
+        let Some((offset, height)) = caret else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        visibility.set_if_neq(Visibility::Inherited);
+
+        // Absolutely positioned nodes are placed relative to the padding box of their parent.
+        let Ok((parent_node, parent_transform)) = q_layout.get(child_of.parent()) else {
+            continue;
+        };
+        let parent_top_left =
+            parent_transform.translation - parent_node.size() / 2. + parent_node.border.min_inset;
+        let position =
+            (text_top_left + offset - parent_top_left) * parent_node.inverse_scale_factor;
+        let height = Val::Px(height * parent_node.inverse_scale_factor);
+        if node.position_type != PositionType::Absolute
+            || node.left != Val::Px(position.x)
+            || node.top != Val::Px(position.y)
+            || node.height != height
+        {
+            node.position_type = PositionType::Absolute;
+            node.left = Val::Px(position.x);
+            node.top = Val::Px(position.y);
+            node.height = height;
+        }
+    }
+
+    // Only toggle IME when the focus moves, so other users of the window's IME state aren't
+    // overridden every frame.
+    if *ime_window != focused_window {
+        if let Some(mut window) = ime_window.and_then(|window| q_window.get_mut(window).ok()) {
+            window.ime_enabled = false;
+        }
+        if let Some(mut window) = focused_window.and_then(|window| q_window.get_mut(window).ok()) {
+            window.ime_enabled = true;
+        }
+        *ime_window = focused_window;
+    }
+    if let Some(position) = ime_position
+        && let Some(mut window) = focused_window.and_then(|window| q_window.get_mut(window).ok())
+        && window.ime_position != position
+    {
+        window.ime_position = position;
+    }
+}
+
+/// Plugin that adds the observers and systems for the [`TextInput`] widget.
+pub struct TextInputPlugin;
+
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TextInputClipboard>()
+            .add_observer(text_input_on_insert)
+            .add_observer(text_input_on_key_input)
+            .add_observer(text_input_on_ime)
+            .add_observer(text_input_on_pointer_press)
+            .add_observer(text_input_on_drag)
+            .add_systems(
+                PreUpdate,
+                dispatch_focused_input::<Ime>.in_set(InputFocusSystems::Dispatch),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_text_input_text.before(UiSystems::Content),
+                    update_text_input_caret.in_set(UiSystems::PostLayout),
+                ),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::Update;
+    use bevy_input::InputPlugin;
+    use bevy_input_focus::InputDispatchPlugin;
+
+    #[test]
+    fn test_text_input_editing() {
+        let input = TextInput::default();
+        let mut state = TextInputState::new("hello world");
+
+        state.move_cursor(TextMotion::WordLeft, false);
+        assert_eq!(state.cursor(), 6);
+        state.move_cursor(TextMotion::WordLeft, true);
+        assert_eq!(state.selected_text(), "hello ");
+
+        assert!(state.insert("é", &input));
+        assert_eq!(state.value(), "éworld");
+        assert_eq!(state.cursor(), 2);
+
+        assert!(state.delete(TextDeletion::Backward, &input));
+        assert!(state.delete(TextDeletion::WordForward, &input));
+        assert_eq!(state.value(), "");
+        assert!(!state.delete(TextDeletion::Backward, &input));
+
+        // Single-line inputs drop line breaks and control characters.
+        assert!(state.paste("a\nb\tc", &input));
+        assert_eq!(state.value(), "abc");
+    }
+
+    #[test]
+    fn test_text_input_multiline_motion() {
+        let mut state = TextInputState::new("first line\nab\nthird");
+        state.move_cursor(TextMotion::Up, false);
+        assert_eq!(state.cursor(), 13);
+        state.move_cursor(TextMotion::Up, false);
+        assert_eq!(state.cursor(), 2);
+        state.move_cursor(TextMotion::LineEnd, false);
+        state.move_cursor(TextMotion::Down, false);
+        assert_eq!(state.cursor(), 13);
+        state.move_cursor(TextMotion::Down, false);
+        assert_eq!(state.cursor(), 16);
+    }
+
+    #[test]
+    fn test_text_input_constraints() {
+        let input = TextInput {
+            max_length: Some(4),
+            validator: Some(|value| value.chars().all(|c| c.is_ascii_digit())),
+            ..Default::default()
+        };
+        let mut state = TextInputState::default();
+
+        assert!(state.paste("123456", &input));
+        assert_eq!(state.value(), "1234");
+        assert!(!state.insert("5", &input));
+
+        state.select_all();
+        assert!(!state.insert("x", &input));
+        assert_eq!(state.value(), "1234");
+        assert!(state.has_selection());
+    }
+
+    #[test]
+    fn test_text_input_undo_redo() {
+        let input = TextInput::default();
+        let mut state = TextInputState::default();
+
+        // Consecutive typing is undone in a single step.
+        for c in ["a", "b", "c"] {
+            state.insert(c, &input);
+        }
+        state.move_cursor(TextMotion::Left, false);
+        state.insert("x", &input);
+        assert_eq!(state.value(), "abxc");
+
+        assert!(state.undo());
+        assert_eq!(state.value(), "abc");
+        assert!(state.undo());
+        assert_eq!(state.value(), "");
+        assert!(!state.undo());
+
+        assert!(state.redo());
+        assert_eq!(state.value(), "abc");
+        state.insert("d", &input);
+        assert!(!state.redo());
+    }
+
+    #[test]
+    fn test_text_input_masking() {
+        let mut state = TextInputState::new("pässword");
+        state.set_cursor(2, false);
+        state.set_preedit("ka", Some(1));
+        let ([before, middle, after], caret) = state.display(Some('*'));
+        assert_eq!(before, "*");
+        assert_eq!(middle, "ka");
+        assert_eq!(after, "*******");
+        assert_eq!(caret, Some(2));
+
+        assert_eq!(unmasked_position("pässword", Some('•'), 6), 3);
+        assert_eq!(unmasked_position("pässword", None, 100), 9);
+    }
+
+    #[test]
+    fn test_text_input_alt_gr_types_characters() {
+        fn key(key_code: KeyCode, logical_key: Key, text: Option<&str>) -> KeyboardInput {
+            KeyboardInput {
+                key_code,
+                logical_key,
+                state: ButtonState::Pressed,
+                text: text.map(Into::into),
+                repeat: false,
+                window: Entity::PLACEHOLDER,
+            }
+        }
+
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputDispatchPlugin))
+            .add_observer(text_input_on_key_input);
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        let entity = app
+            .world_mut()
+            .spawn((TextInput::default(), TextInputState::new("a")))
+            .id();
+        app.world_mut()
+            .insert_resource(InputFocus::from_entity(entity));
+
+        // Ctrl+A is a shortcut.
+        app.world_mut()
+            .write_message(key(KeyCode::ControlLeft, Key::Control, None));
+        app.world_mut()
+            .write_message(key(KeyCode::KeyA, Key::Character("a".into()), Some("a")));
+        app.update();
+        let state = app.world().get::<TextInputState>(entity).unwrap();
+        assert_eq!(state.value(), "a");
+        assert!(state.has_selection());
+
+        // AltGr, reported as Ctrl+Alt, types characters.
+        app.world_mut()
+            .write_message(key(KeyCode::AltRight, Key::AltGraph, None));
+        app.world_mut()
+            .write_message(key(KeyCode::KeyQ, Key::Character("@".into()), Some("@")));
+        app.update();
+        assert_eq!(
+            app.world().get::<TextInputState>(entity).unwrap().value(),
+            "@"
+        );
+    }
+
+    #[test]
+    fn test_text_input_spans_follow_text_style() {
+        let mut app = App::new();
+        app.add_systems(Update, update_text_input_text);
+        let text = app
+            .world_mut()
+            .spawn((
+                Text::default(),
+                TextInputText,
+                TextFont::from_font_size(30.),
+                TextColor(Color::WHITE),
+            ))
+            .id();
+        app.world_mut()
+            .spawn((TextInput::default(), TextInputState::new("hello")))
+            .add_child(text);
+
+        fn span_styles(app: &App, text: Entity) -> Vec<(f32, Color)> {
+            let world = app.world();
+            world
+                .get::<Children>(text)
+                .unwrap()
+                .iter()
+                .map(|&span| {
+                    (
+                        world.get::<TextFont>(span).unwrap().font_size,
+                        world.get::<TextColor>(span).unwrap().0,
+                    )
+                })
+                .collect()
+        }
+
+        app.update();
+        assert_eq!(span_styles(&app, text), [(30., Color::WHITE); 2]);
+
+        app.world_mut().get_mut::<TextColor>(text).unwrap().0 = Color::BLACK;
+        app.update();
+        assert_eq!(span_styles(&app, text), [(30., Color::BLACK); 2]);
+    }
+}
@@ -53,6 +53,7 @@ static DEFAULT_WINDOW_TITLE: LazyLock<String> = LazyLock::new(|| {
     derive(Reflect),
     reflect(Component, Debug, Default, PartialEq, Clone)
 )]
+#[cfg_attr(feature = "replay", require(bevy_input::replay::ReplayWindow))]
 pub struct PrimaryWindow;
 
 /// Reference to a [`Window`], whether it be a direct link to a specific entity or